@@ -14,7 +14,10 @@ impl Config {
         }
     }
 
-    pub fn socket_addr(&self) -> std::net::SocketAddr {
-        std::net::SocketAddr::from(([127,0,0,1], self.port))
+    /// Address to listen on; fails if `host` isn't an IP address
+    pub fn socket_addr(&self) -> Result<std::net::SocketAddr, String> {
+        format!("{}:{}", self.host, self.port)
+            .parse()
+            .map_err(|e| format!("invalid host {:?}: {}", self.host, e))
     }
 }
@@ -1,14 +1,14 @@
 // NOTE: This is currently only a skeleton which opens a websocket connection to the frontend and echoes messages received from the frontend back to the frontend.
 use axum::{
-    routing::{get}, 
+    http::{header, Method},
+    routing::{get, post},
     Router,
 };
+use tower_http::cors::{Any, CorsLayer};
 use std::{
     net::SocketAddr,
     sync::Arc,
 };
-use tokio;
-use tracing_subscriber;
 
 // Declare project modules
 mod config;
@@ -17,8 +17,12 @@ mod routes;
 mod types;
 mod ws;
 
-use models::appstate::AppState;
+use models::{accounts::AccountStore, appstate::AppState, db, ratings::RatingStore};
 use config::Config;
+use routes::{
+    account_handler::{login_handler, register_handler},
+    stats_handler::{leaderboard_handler, player_stats_handler},
+};
 use ws::ws_handler;
 
 
@@ -33,15 +37,39 @@ async fn main() {
         .init();
 
     // Define socket address and utilize logger
-    let addr: SocketAddr = config.socket_addr();
+    let addr: SocketAddr = match config.socket_addr() {
+        Ok(addr) => addr,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
     tracing::info!("listening on {}", addr);
 
+    // Open the database for accounts and ratings
+    let db_path = db::db_path();
+    let database = db::open(&db_path)
+        .unwrap_or_else(|e| panic!("could not open {}: {}", db_path.display(), e));
+    let accounts = AccountStore::new(database.clone()).expect("accounts tables");
+    let ratings = RatingStore::new(database).expect("ratings tables");
+
     // Init game state
-    let state = Arc::new(AppState::default());
+    let state = Arc::new(AppState { rooms: Default::default(), accounts, ratings });
+
+    // The client is served from another origin and calls the account endpoints with fetch
+    let cors = CorsLayer::new()
+        .allow_origin(Any)
+        .allow_methods([Method::GET, Method::POST])
+        .allow_headers([header::CONTENT_TYPE]);
 
     // Init router with routes
     let app = Router::new()
         .route("/ws", get(ws_handler))
+        .route("/register", post(register_handler))
+        .route("/login", post(login_handler))
+        .route("/players/{name}/stats", get(player_stats_handler))
+        .route("/leaderboard/{game}", get(leaderboard_handler))
+        .layer(cors)
         .with_state(state);
 
     // Init listener
@@ -57,7 +85,7 @@ mod tests {
     fn config_default_works() {
         let config = Config::default();
         // Just check that socket address parses
-        let addr: SocketAddr = config.socket_addr();
+        let addr: SocketAddr = config.socket_addr().unwrap();
         assert!(addr.ip().is_loopback());
     }
 
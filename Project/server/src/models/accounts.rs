@@ -0,0 +1,194 @@
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use redb::{Database, ReadableTable, TableDefinition};
+use sha2::Sha256;
+
+use crate::models::{
+    db::{self, describe, storage_error},
+    minimax::BOT_NAME,
+};
+
+/// How long a login token stays valid
+pub const TOKEN_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+pub const MAX_NAME_LEN: usize = 20;
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// player name -> argon2 PHC hash string
+const USERS: TableDefinition<&str, &str> = TableDefinition::new("users");
+/// Server-wide settings, e.g. the token signing key
+const META: TableDefinition<&str, &[u8]> = TableDefinition::new("meta");
+const SECRET_KEY: &str = "token_secret";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Optional player accounts. Passwords are stored as argon2 hashes; a login hands out a token
+/// `<base64 name:expiry>.<hex HMAC-SHA256>` signed with a key kept in the same database
+/// (or taken from `ACCOUNTS_SECRET`), so tokens survive restarts.
+pub struct AccountStore {
+    db: Arc<Database>,
+    secret: Vec<u8>,
+}
+
+impl AccountStore {
+    /// Accounts that only live as long as the process, for tests and `AppState::default()`
+    pub fn in_memory() -> Self {
+        Self::new(db::in_memory()).expect("in-memory database")
+    }
+
+    pub fn new(db: Arc<Database>) -> Result<Self, String> {
+        let txn = db.begin_write().map_err(describe)?;
+        let secret = {
+            txn.open_table(USERS).map_err(describe)?;
+            let mut meta = txn.open_table(META).map_err(describe)?;
+            match std::env::var("ACCOUNTS_SECRET") {
+                Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+                _ => {
+                    let stored = meta.get(SECRET_KEY).map_err(describe)?.map(|v| v.value().to_vec());
+                    match stored {
+                        Some(secret) => secret,
+                        None => {
+                            let mut secret = vec![0u8; 32];
+                            rand::rng().fill_bytes(&mut secret);
+                            meta.insert(SECRET_KEY, secret.as_slice()).map_err(describe)?;
+                            secret
+                        }
+                    }
+                }
+            }
+        };
+        txn.commit().map_err(describe)?;
+        Ok(Self { db, secret })
+    }
+
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.password_hash(name).ok().flatten().is_some()
+    }
+
+    /// Create an account and log it in
+    pub fn register(&self, name: &str, password: &str) -> Result<String, &'static str> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LEN || name == BOT_NAME {
+            return Err("invalid_name");
+        }
+        if password.chars().count() < MIN_PASSWORD_LEN {
+            return Err("weak_password");
+        }
+
+        let mut salt = [0u8; 16];
+        rand::rng().fill_bytes(&mut salt);
+        let salt = SaltString::encode_b64(&salt).map_err(|_| "storage_error")?;
+        let hash = Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|_| "storage_error")?
+            .to_string();
+
+        let txn = self.db.begin_write().map_err(storage_error)?;
+        {
+            let mut users = txn.open_table(USERS).map_err(storage_error)?;
+            if users.get(name).map_err(storage_error)?.is_some() {
+                return Err("name_taken");
+            }
+            users.insert(name, hash.as_str()).map_err(storage_error)?;
+        }
+        txn.commit().map_err(storage_error)?;
+        Ok(self.issue_token(name))
+    }
+
+    /// Check a password and hand out a fresh token
+    pub fn login(&self, name: &str, password: &str) -> Result<String, &'static str> {
+        let name = name.trim();
+        let hash = self.password_hash(name)?.ok_or("invalid_credentials")?;
+        let parsed = PasswordHash::new(&hash).map_err(|_| "storage_error")?;
+        Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .map_err(|_| "invalid_credentials")?;
+        Ok(self.issue_token(name))
+    }
+
+    /// Name the token was issued to, if the signature checks out and it hasn't expired
+    pub fn verify_token(&self, token: &str) -> Option<String> {
+        let (body, signature) = token.split_once('.')?;
+        let signature = hex::decode(signature).ok()?;
+        self.mac(body).verify_slice(&signature).ok()?;
+
+        let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(body).ok()?).ok()?;
+        let (name, expires) = decoded.rsplit_once(':')?;
+        (expires.parse::<u64>().ok()? > now_secs()).then(|| name.to_string())
+    }
+
+    fn issue_token(&self, name: &str) -> String {
+        let body = URL_SAFE_NO_PAD.encode(format!("{}:{}", name, now_secs() + TOKEN_TTL_SECS));
+        let signature = hex::encode(self.mac(&body).finalize().into_bytes());
+        format!("{}.{}", body, signature)
+    }
+
+    fn mac(&self, body: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC takes any key length");
+        mac.update(body.as_bytes());
+        mac
+    }
+
+    fn password_hash(&self, name: &str) -> Result<Option<String>, &'static str> {
+        let txn = self.db.begin_read().map_err(storage_error)?;
+        let users = txn.open_table(USERS).map_err(storage_error)?;
+        let hash = users.get(name).map_err(storage_error)?.map(|v| v.value().to_string());
+        Ok(hash)
+    }
+}
+
+impl Default for AccountStore {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_then_login_issues_tokens_for_that_name() {
+        let accounts = AccountStore::in_memory();
+        let token = accounts.register("alice", "correct horse").unwrap();
+        assert_eq!(accounts.verify_token(&token).as_deref(), Some("alice"));
+        assert!(accounts.is_registered("alice"));
+
+        assert_eq!(accounts.register("alice", "another password"), Err("name_taken"));
+        assert_eq!(accounts.register("bob", "short"), Err("weak_password"));
+        assert_eq!(accounts.login("alice", "wrong password"), Err("invalid_credentials"));
+        assert_eq!(accounts.login("nobody", "correct horse"), Err("invalid_credentials"));
+
+        let token = accounts.login("alice", "correct horse").unwrap();
+        assert_eq!(accounts.verify_token(&token).as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn tampered_or_foreign_tokens_are_rejected() {
+        let accounts = AccountStore::in_memory();
+        let token = accounts.register("alice", "correct horse").unwrap();
+
+        let (_, signature) = token.split_once('.').unwrap();
+        let forged = format!("{}.{}", URL_SAFE_NO_PAD.encode(format!("mallory:{}", u64::MAX)), signature);
+        assert_eq!(accounts.verify_token(&forged), None);
+        assert_eq!(accounts.verify_token("not-a-token"), None);
+
+        // Each in-memory store gets its own random key unless ACCOUNTS_SECRET is set
+        if std::env::var("ACCOUNTS_SECRET").is_err() {
+            assert_eq!(AccountStore::in_memory().verify_token(&token), None);
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use tokio::sync::RwLock;
 use std::collections::HashMap;
 
-use crate::models::gameroom::GameRoom;
+use crate::models::{accounts::AccountStore, gameroom::GameRoom, ratings::RatingStore};
 
 // Holds state of the application backend
 #[derive(Default)]
 pub struct AppState {
     pub rooms: RwLock<HashMap<String, GameRoom>>, // key, value pair (room_id: String, room_object: GameRoom)
-}
\ No newline at end of file
+    pub accounts: AccountStore, // Registered players; in-memory unless main opens the database file
+    pub ratings: RatingStore, // Ratings and stats for finished games; shares the accounts' database
+}
@@ -0,0 +1,271 @@
+use crate::models::{
+    seats::TwoSeats,
+    tictactoe::model::{GameWinner, Player},
+};
+
+pub const GRID_SIZE: usize = 10;
+
+/// Ships every fleet must contain, with their lengths
+pub const FLEET: [(&str, usize); 5] = [
+    ("carrier", 5),
+    ("battleship", 4),
+    ("cruiser", 3),
+    ("submarine", 3),
+    ("destroyer", 2),
+];
+
+/// Where a player wants a ship: bow at (row, col), running right or down
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShipPlacement {
+    pub kind: String,
+    pub row: usize,
+    pub col: usize,
+    pub horizontal: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ship {
+    pub kind: String,
+    pub cells: Vec<(usize, usize)>,
+    pub hits: Vec<bool>, // Parallel to `cells`
+}
+
+impl Ship {
+    pub fn is_sunk(&self) -> bool {
+        self.hits.iter().all(|&h| h)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shot {
+    pub row: usize,
+    pub col: usize,
+    pub hit: bool,
+    pub sunk: Option<String>, // Kind of ship this shot sank
+}
+
+/// Battleship: each player places a hidden fleet on their own 10x10 grid, then they take turns firing.
+/// Seat 0 is player 1, seat 1 is player 2.
+#[derive(Debug, Clone)]
+pub struct BattleshipModel {
+    pub fleets: [Option<Vec<Ship>>; 2], // None until placed
+    pub shots: [Vec<Shot>; 2], // Shots fired by each seat at the other's grid
+    pub whos_turn: Player,
+    pub winner: GameWinner,
+    pub player1_name: Option<String>,
+    pub player2_name: Option<String>,
+    pub extra_shot_on_hit: bool, // House rule: a hit earns another shot instead of passing the turn
+}
+
+impl BattleshipModel {
+    pub fn new() -> Self {
+        Self {
+            fleets: [None, None],
+            shots: [Vec::new(), Vec::new()],
+            whos_turn: Player::Player1,
+            winner: GameWinner::Pending,
+            player1_name: None,
+            player2_name: None,
+            extra_shot_on_hit: false,
+        }
+    }
+
+    /// Clear both grids for a new game, keeping the seats and house rules
+    pub fn reset(&mut self) {
+        *self = Self {
+            player1_name: self.player1_name.take(),
+            player2_name: self.player2_name.take(),
+            extra_shot_on_hit: self.extra_shot_on_hit,
+            ..Self::new()
+        };
+    }
+
+    pub fn placing(&self) -> bool {
+        self.fleets.iter().any(|f| f.is_none())
+    }
+
+    pub fn current_player_name(&self) -> Option<&str> {
+        if self.placing() {
+            return None;
+        }
+        match self.whos_turn {
+            Player::Player1 => self.player1_name.as_deref(),
+            Player::Player2 => self.player2_name.as_deref(),
+        }
+    }
+
+    pub fn winner_name(&self) -> Option<&str> {
+        match self.winner {
+            GameWinner::Player1 => self.player1_name.as_deref(),
+            GameWinner::Player2 => self.player2_name.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Place the whole fleet for `seat` at once: exactly one of each ship in FLEET,
+    /// inside the grid and not overlapping.
+    pub fn place_fleet(&mut self, seat: usize, placements: &[ShipPlacement]) -> Result<(), &'static str> {
+        if self.fleets[seat].is_some() {
+            return Err("already_placed");
+        }
+        let mut kinds: Vec<&str> = placements.iter().map(|p| p.kind.as_str()).collect();
+        kinds.sort_unstable();
+        let mut expected: Vec<&str> = FLEET.iter().map(|(kind, _)| *kind).collect();
+        expected.sort_unstable();
+        if kinds != expected {
+            return Err("wrong_fleet");
+        }
+
+        let mut ships: Vec<Ship> = Vec::new();
+        for placement in placements {
+            let length = FLEET.iter().find(|(kind, _)| *kind == placement.kind).map(|(_, len)| *len).unwrap_or(0);
+            let (dr, dc) = if placement.horizontal { (0, 1) } else { (1, 0) };
+            let cells: Vec<(usize, usize)> = (0..length).map(|i| (placement.row + i * dr, placement.col + i * dc)).collect();
+
+            if cells.iter().any(|&(r, c)| r >= GRID_SIZE || c >= GRID_SIZE) {
+                return Err("out_of_bounds");
+            }
+            if ships.iter().any(|ship| ship.cells.iter().any(|cell| cells.contains(cell))) {
+                return Err("overlap");
+            }
+            ships.push(Ship { kind: placement.kind.clone(), hits: vec![false; cells.len()], cells });
+        }
+
+        self.fleets[seat] = Some(ships);
+        Ok(())
+    }
+
+    /// Fire at (row, col) on the opponent's grid for the player whose turn it is
+    pub fn fire(&mut self, row: usize, col: usize) -> Result<Shot, &'static str> {
+        if self.placing() {
+            return Err("placing_ships");
+        }
+        if self.winner != GameWinner::Pending {
+            return Err("game_over");
+        }
+        if row >= GRID_SIZE || col >= GRID_SIZE {
+            return Err("out_of_bounds");
+        }
+        let seat = match self.whos_turn {
+            Player::Player1 => 0,
+            Player::Player2 => 1,
+        };
+        if self.shots[seat].iter().any(|s| s.row == row && s.col == col) {
+            return Err("already_fired");
+        }
+
+        let fleet = self.fleets[1 - seat].as_mut().expect("both fleets are placed");
+        let mut shot = Shot { row, col, hit: false, sunk: None };
+        for ship in fleet.iter_mut() {
+            if let Some(i) = ship.cells.iter().position(|&cell| cell == (row, col)) {
+                ship.hits[i] = true;
+                shot.hit = true;
+                if ship.is_sunk() {
+                    shot.sunk = Some(ship.kind.clone());
+                }
+            }
+        }
+
+        if fleet.iter().all(|ship| ship.is_sunk()) {
+            self.winner = if seat == 0 { GameWinner::Player1 } else { GameWinner::Player2 };
+        } else if !(shot.hit && self.extra_shot_on_hit) {
+            self.whos_turn = match self.whos_turn {
+                Player::Player1 => Player::Player2,
+                Player::Player2 => Player::Player1,
+            };
+        }
+        self.shots[seat].push(shot.clone());
+        Ok(shot)
+    }
+}
+
+impl Default for BattleshipModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TwoSeats for BattleshipModel {
+    fn seat_names(&self) -> [&Option<String>; 2] {
+        [&self.player1_name, &self.player2_name]
+    }
+
+    fn seat_names_mut(&mut self) -> [&mut Option<String>; 2] {
+        [&mut self.player1_name, &mut self.player2_name]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One ship per row, all starting in column 0
+    fn fleet() -> Vec<ShipPlacement> {
+        FLEET
+            .iter()
+            .enumerate()
+            .map(|(row, (kind, _))| ShipPlacement { kind: kind.to_string(), row, col: 0, horizontal: true })
+            .collect()
+    }
+
+    #[test]
+    fn placement_is_validated() {
+        let mut game = BattleshipModel::new();
+        let mut bad = fleet();
+        bad.pop();
+        assert_eq!(game.place_fleet(0, &bad), Err("wrong_fleet"));
+
+        let mut bad = fleet();
+        bad[0].col = 6;
+        assert_eq!(game.place_fleet(0, &bad), Err("out_of_bounds"));
+
+        let mut bad = fleet();
+        bad[4] = ShipPlacement { kind: "destroyer".into(), row: 0, col: 4, horizontal: false };
+        assert_eq!(game.place_fleet(0, &bad), Err("overlap"));
+
+        let mut vertical = fleet();
+        vertical[4] = ShipPlacement { kind: "destroyer".into(), row: 0, col: 9, horizontal: false };
+        game.place_fleet(0, &vertical).unwrap();
+        assert_eq!(game.place_fleet(0, &fleet()), Err("already_placed"));
+    }
+
+    #[test]
+    fn shots_alternate_and_sinking_everything_wins() {
+        let mut game = BattleshipModel::new();
+        assert_eq!(game.fire(0, 0), Err("placing_ships"));
+        game.place_fleet(0, &fleet()).unwrap();
+        game.place_fleet(1, &fleet()).unwrap();
+
+        // Player 2 misses on the empty rows while player 1 sinks ship after ship
+        let mut misses = (FLEET.len()..GRID_SIZE).flat_map(|row| (0..GRID_SIZE).map(move |col| (row, col)));
+        for (row, (_, len)) in FLEET.iter().enumerate() {
+            for col in 0..*len {
+                assert!(game.fire(row, col).unwrap().hit);
+                if game.winner == GameWinner::Pending {
+                    assert_eq!(game.whos_turn, Player::Player2);
+                    let (miss_row, miss_col) = misses.next().unwrap();
+                    assert!(!game.fire(miss_row, miss_col).unwrap().hit);
+                }
+            }
+            assert_eq!(game.shots[0].last().unwrap().sunk.as_deref(), Some(FLEET[row].0));
+        }
+        assert_eq!(game.winner, GameWinner::Player1);
+        assert_eq!(game.fire(9, 8), Err("game_over"));
+    }
+
+    #[test]
+    fn extra_shot_on_hit_keeps_the_turn() {
+        let mut game = BattleshipModel::new();
+        game.extra_shot_on_hit = true;
+        game.place_fleet(0, &fleet()).unwrap();
+        game.place_fleet(1, &fleet()).unwrap();
+
+        assert!(game.fire(0, 0).unwrap().hit);
+        assert_eq!(game.whos_turn, Player::Player1);
+        assert!(!game.fire(9, 9).unwrap().hit);
+        assert_eq!(game.whos_turn, Player::Player2);
+
+        game.reset();
+        assert!(game.extra_shot_on_hit);
+    }
+}
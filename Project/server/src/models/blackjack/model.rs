@@ -0,0 +1,442 @@
+use crate::models::cards::{Card, Rank, Shoe, CARD_BACK};
+
+pub const DEFAULT_DECKS: usize = 6;
+pub const DEFAULT_STARTING_CHIPS: u32 = 1000;
+pub const DEFAULT_MIN_BET: u32 = 10;
+pub const MAX_HANDS: usize = 4; // Per player, after splits
+pub const DEALER_STANDS_ON: u8 = 17; // Dealer stands on every 17, soft or hard
+
+/// Best total for a hand and whether an ace is still counted as 11
+pub fn hand_value(cards: &[Card]) -> (u8, bool) {
+    let mut total: u8 = 0;
+    let mut soft_aces = 0;
+    for card in cards {
+        total += match card.rank {
+            Rank::Ace => {
+                soft_aces += 1;
+                11
+            }
+            Rank::Jack | Rank::Queen | Rank::King => 10,
+            rank => rank.value(),
+        };
+    }
+    while total > 21 && soft_aces > 0 {
+        total -= 10;
+        soft_aces -= 1;
+    }
+    (total, soft_aces > 0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlackjackPhase {
+    Betting,
+    PlayerTurns,
+    RoundOver,
+    GameOver, // Nobody can cover the minimum bet
+}
+
+impl BlackjackPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Betting => "betting",
+            Self::PlayerTurns => "player_turns",
+            Self::RoundOver => "round_over",
+            Self::GameOver => "game_over",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandOutcome {
+    Blackjack, // Pays 3:2
+    Win,
+    Push,
+    Lose,
+}
+
+impl HandOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Blackjack => "blackjack",
+            Self::Win => "win",
+            Self::Push => "push",
+            Self::Lose => "lose",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BlackjackHand {
+    pub cards: Vec<Card>, // cards[0] is the hole card, hidden from other players until the round ends
+    pub bet: u32,
+    pub doubled: bool,
+    pub from_split: bool,
+    pub done: bool,
+    pub outcome: Option<HandOutcome>,
+}
+
+impl BlackjackHand {
+    fn new(cards: Vec<Card>, bet: u32, from_split: bool) -> Self {
+        Self { cards, bet, doubled: false, from_split, done: false, outcome: None }
+    }
+
+    pub fn value(&self) -> u8 {
+        hand_value(&self.cards).0
+    }
+
+    pub fn is_bust(&self) -> bool {
+        self.value() > 21
+    }
+
+    /// Two-card 21 that didn't come from a split
+    pub fn is_blackjack(&self) -> bool {
+        !self.from_split && self.cards.len() == 2 && self.value() == 21
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BlackjackPlayer {
+    pub name: String,
+    pub chips: u32,
+    pub bet: Option<u32>, // Placed during betting, moved onto the first hand when cards are dealt
+    pub hands: Vec<BlackjackHand>,
+    pub active_hand: usize,
+    pub net: i64, // Chips won or lost last round
+}
+
+/// Multiplayer Blackjack against a dealer that stands on 17. Chips carry over from round to round.
+#[derive(Debug, Clone)]
+pub struct BlackjackModel {
+    pub players: Vec<BlackjackPlayer>,
+    pub dealer: Vec<Card>, // dealer[1] is the hole card
+    pub shoe: Shoe,
+    pub phase: BlackjackPhase,
+    pub current: Option<usize>, // Index into `players` whose turn it is
+    pub starting_chips: u32,
+    pub min_bet: u32,
+    pub decks: usize,
+    pub seed: Option<u64>, // Fixed shuffle order; only tests set it
+}
+
+impl BlackjackModel {
+    pub fn new() -> Self {
+        Self {
+            players: Vec::new(),
+            dealer: Vec::new(),
+            shoe: Shoe::new(DEFAULT_DECKS, None),
+            phase: BlackjackPhase::Betting,
+            current: None,
+            starting_chips: DEFAULT_STARTING_CHIPS,
+            min_bet: DEFAULT_MIN_BET,
+            decks: DEFAULT_DECKS,
+            seed: None,
+        }
+    }
+
+    /// New table for the seated players, everyone on starting chips
+    pub fn start(&mut self, seats: &[String]) {
+        self.shoe = Shoe::new(self.decks, self.seed);
+        self.players = seats
+            .iter()
+            .map(|name| BlackjackPlayer {
+                name: name.clone(),
+                chips: self.starting_chips,
+                bet: None,
+                hands: Vec::new(),
+                active_hand: 0,
+                net: 0,
+            })
+            .collect();
+        self.dealer.clear();
+        self.current = None;
+        self.phase = BlackjackPhase::Betting;
+    }
+
+    fn player_index(&self, name: &str) -> Option<usize> {
+        self.players.iter().position(|p| p.name == name)
+    }
+
+    pub fn current_player_name(&self) -> Option<&str> {
+        self.current.map(|i| self.players[i].name.as_str())
+    }
+
+    /// Players still able to bet this round
+    fn can_bet(&self, player: &BlackjackPlayer) -> bool {
+        player.bet.is_some() || player.chips >= self.min_bet
+    }
+
+    /// Place a bet for the next hand. Cards are dealt once everyone who can bet has.
+    pub fn place_bet(&mut self, name: &str, amount: u32) -> Result<(), &'static str> {
+        if self.phase != BlackjackPhase::Betting {
+            return Err("not_betting");
+        }
+        let idx = self.player_index(name).ok_or("unknown_player")?;
+        let min_bet = self.min_bet;
+        let player = &mut self.players[idx];
+        if player.bet.is_some() {
+            return Err("already_bet");
+        }
+        if amount < min_bet {
+            return Err("bet_too_small");
+        }
+        if amount > player.chips {
+            return Err("not_enough_chips");
+        }
+        player.chips -= amount;
+        player.bet = Some(amount);
+
+        if self.players.iter().filter(|p| self.can_bet(p)).all(|p| p.bet.is_some()) {
+            self.deal();
+        }
+        Ok(())
+    }
+
+    fn deal(&mut self) {
+        if self.shoe.remaining() < self.shoe.size() / 4 {
+            self.shoe.shuffle();
+        }
+        for idx in 0..self.players.len() {
+            if let Some(bet) = self.players[idx].bet.take() {
+                let cards = vec![self.shoe.draw(), self.shoe.draw()];
+                let player = &mut self.players[idx];
+                player.hands = vec![BlackjackHand::new(cards, bet, false)];
+                player.active_hand = 0;
+            }
+        }
+        self.dealer = vec![self.shoe.draw(), self.shoe.draw()];
+        self.phase = BlackjackPhase::PlayerTurns;
+
+        // Dealer peeks: a dealer blackjack ends the round straight away
+        if hand_value(&self.dealer).0 == 21 {
+            self.finish_round();
+            return;
+        }
+        self.current = Some(0);
+        self.advance();
+    }
+
+    /// Move `current` / `active_hand` to the next hand that still needs a decision,
+    /// or let the dealer play once every hand is done.
+    fn advance(&mut self) {
+        let start = self.current.unwrap_or(0);
+        for idx in start..self.players.len() {
+            let player = &mut self.players[idx];
+            let first_hand = if idx == start { player.active_hand } else { 0 };
+            for hand_idx in first_hand..player.hands.len() {
+                let hand = &mut player.hands[hand_idx];
+                if !hand.done && hand.value() >= 21 {
+                    hand.done = true; // Blackjack, 21 or bust: nothing left to decide
+                }
+                if !hand.done {
+                    player.active_hand = hand_idx;
+                    self.current = Some(idx);
+                    return;
+                }
+            }
+        }
+        self.finish_round();
+    }
+
+    /// hit / stand / double / split for the current player's active hand
+    pub fn act(&mut self, name: &str, action: &str) -> Result<(), &'static str> {
+        if self.phase != BlackjackPhase::PlayerTurns {
+            return Err("not_your_turn");
+        }
+        let idx = self.current.ok_or("not_your_turn")?;
+        if self.players[idx].name != name {
+            return Err("not_your_turn");
+        }
+
+        let player = &mut self.players[idx];
+        let active = player.active_hand;
+        match action {
+            "hit" => {
+                let card = self.shoe.draw();
+                player.hands[active].cards.push(card);
+            }
+            "stand" => player.hands[active].done = true,
+            "double" => {
+                let hand = &player.hands[active];
+                if hand.cards.len() != 2 {
+                    return Err("cannot_double");
+                }
+                if player.chips < hand.bet {
+                    return Err("not_enough_chips");
+                }
+                player.chips -= hand.bet;
+                let card = self.shoe.draw();
+                let hand = &mut player.hands[active];
+                hand.bet *= 2;
+                hand.doubled = true;
+                hand.cards.push(card);
+                hand.done = true;
+            }
+            "split" => {
+                let hand = &player.hands[active];
+                if hand.cards.len() != 2 || hand.cards[0].rank != hand.cards[1].rank {
+                    return Err("cannot_split");
+                }
+                if player.hands.len() >= MAX_HANDS {
+                    return Err("too_many_hands");
+                }
+                if player.chips < hand.bet {
+                    return Err("not_enough_chips");
+                }
+                let bet = hand.bet;
+                player.chips -= bet;
+
+                let moved = player.hands[active].cards.pop().expect("two cards checked above");
+                let split_aces = moved.rank == Rank::Ace;
+                let (first, second) = (self.shoe.draw(), self.shoe.draw());
+                let hand = &mut player.hands[active];
+                hand.from_split = true;
+                hand.cards.push(first);
+                let mut new_hand = BlackjackHand::new(vec![moved, second], bet, true);
+                // Split aces get one card each
+                hand.done = split_aces;
+                new_hand.done = split_aces;
+                player.hands.insert(active + 1, new_hand);
+            }
+            _ => return Err("unknown_action"),
+        }
+
+        self.advance();
+        Ok(())
+    }
+
+    /// Dealer draws to 17 (unless every hand is already bust), then every hand is paid out.
+    fn finish_round(&mut self) {
+        let anyone_standing = self.players.iter().flat_map(|p| &p.hands).any(|h| !h.is_bust());
+        while anyone_standing && hand_value(&self.dealer).0 < DEALER_STANDS_ON {
+            let card = self.shoe.draw();
+            self.dealer.push(card);
+        }
+
+        let dealer_total = hand_value(&self.dealer).0;
+        let dealer_blackjack = self.dealer.len() == 2 && dealer_total == 21;
+        for player in &mut self.players {
+            let mut net: i64 = 0;
+            for hand in &mut player.hands {
+                let outcome = if hand.is_bust() {
+                    HandOutcome::Lose
+                } else if hand.is_blackjack() && !dealer_blackjack {
+                    HandOutcome::Blackjack
+                } else if dealer_blackjack && !hand.is_blackjack() {
+                    HandOutcome::Lose
+                } else if dealer_total > 21 || hand.value() > dealer_total {
+                    HandOutcome::Win
+                } else if hand.value() == dealer_total {
+                    HandOutcome::Push
+                } else {
+                    HandOutcome::Lose
+                };
+                let payout = match outcome {
+                    HandOutcome::Blackjack => hand.bet + hand.bet * 3 / 2,
+                    HandOutcome::Win => hand.bet * 2,
+                    HandOutcome::Push => hand.bet,
+                    HandOutcome::Lose => 0,
+                };
+                player.chips += payout;
+                net += payout as i64 - hand.bet as i64;
+                hand.done = true;
+                hand.outcome = Some(outcome);
+            }
+            player.net = net;
+        }
+
+        self.current = None;
+        self.phase = BlackjackPhase::RoundOver;
+    }
+
+    /// Clear the table for the next round of betting
+    pub fn next_round(&mut self) -> Result<(), &'static str> {
+        if self.phase != BlackjackPhase::RoundOver {
+            return Err("round_in_progress");
+        }
+        for player in &mut self.players {
+            player.hands.clear();
+            player.active_hand = 0;
+            player.net = 0;
+        }
+        self.dealer.clear();
+        self.phase = if self.players.iter().any(|p| p.chips >= self.min_bet) {
+            BlackjackPhase::Betting
+        } else {
+            BlackjackPhase::GameOver
+        };
+        Ok(())
+    }
+
+    /// Dealer's cards as seen by the players: the hole card stays face down until the round ends
+    pub fn dealer_view(&self) -> Vec<String> {
+        self.dealer
+            .iter()
+            .enumerate()
+            .map(|(i, card)| if i == 1 && self.phase == BlackjackPhase::PlayerTurns { CARD_BACK.to_string() } else { card.code() })
+            .collect()
+    }
+
+    /// A hand's cards as seen by `viewer`: other players' hole cards stay face down until the round ends
+    pub fn hand_view(&self, owner: &str, hand: &BlackjackHand, viewer: Option<&str>) -> (Vec<String>, bool) {
+        let hidden = self.phase == BlackjackPhase::PlayerTurns && viewer != Some(owner);
+        let cards = hand
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(i, card)| if i == 0 && hidden { CARD_BACK.to_string() } else { card.code() })
+            .collect();
+        (cards, hidden)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::cards::Suit;
+
+    fn cards(codes: &[&str]) -> Vec<Card> {
+        codes.iter().map(|c| Card::from_code(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn soft_aces_drop_to_one() {
+        assert_eq!(hand_value(&cards(&["ah", "6d"])), (17, true));
+        assert_eq!(hand_value(&cards(&["ah", "6d", "10c"])), (17, false));
+        assert_eq!(hand_value(&cards(&["ah", "as", "9c"])), (21, true));
+    }
+
+    #[test]
+    fn seeded_round_pays_out_and_keeps_chips() {
+        let mut game = BlackjackModel::new();
+        game.seed = Some(42);
+        game.start(&["Ada".to_string(), "Alan".to_string()]);
+        game.place_bet("Ada", 100).unwrap();
+        assert_eq!(game.phase, BlackjackPhase::Betting);
+        game.place_bet("Alan", 50).unwrap();
+
+        while let Some(name) = game.current_player_name().map(|n| n.to_string()) {
+            game.act(&name, "stand").unwrap();
+        }
+        assert_eq!(game.phase, BlackjackPhase::RoundOver);
+        for player in &game.players {
+            assert_eq!(player.chips as i64, DEFAULT_STARTING_CHIPS as i64 + player.net);
+        }
+        game.next_round().unwrap();
+        assert_eq!(game.phase, BlackjackPhase::Betting);
+    }
+
+    #[test]
+    fn split_makes_two_hands() {
+        let mut game = BlackjackModel::new();
+        game.start(&["Ada".to_string()]);
+        game.players[0].hands = vec![BlackjackHand::new(vec![Card::new(Rank::Eight, Suit::Clubs), Card::new(Rank::Eight, Suit::Hearts)], 10, false)];
+        game.dealer = cards(&["10h", "7s"]);
+        game.phase = BlackjackPhase::PlayerTurns;
+        game.current = Some(0);
+
+        game.act("Ada", "split").unwrap();
+        assert_eq!(game.players[0].hands.len(), 2);
+        assert_eq!(game.players[0].chips, DEFAULT_STARTING_CHIPS - 10);
+        assert!(game.players[0].hands.iter().all(|h| h.from_split && h.cards[0].rank == Rank::Eight));
+    }
+}
@@ -0,0 +1,199 @@
+// Standard 52-card deck shared by the card games (Blackjack, Hold'em, ...).
+// Cards travel as short codes matching the client's card images: "2c", "10s", "qh", "ad".
+
+use std::fmt;
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Image name for a face-down card
+pub const CARD_BACK: &str = "back";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+    pub fn code(self) -> char {
+        match self {
+            Suit::Clubs => 'c',
+            Suit::Diamonds => 'd',
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+        }
+    }
+
+    pub fn from_code(code: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.code() == code.to_ascii_lowercase())
+    }
+}
+
+/// Ranks compare in poker order, deuce low and ace high
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    Two = 2,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven, Rank::Eight,
+        Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
+    ];
+
+    /// 2-14, ace high
+    pub fn value(self) -> u8 {
+        self as u8
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Rank::Two => "2",
+            Rank::Three => "3",
+            Rank::Four => "4",
+            Rank::Five => "5",
+            Rank::Six => "6",
+            Rank::Seven => "7",
+            Rank::Eight => "8",
+            Rank::Nine => "9",
+            Rank::Ten => "10",
+            Rank::Jack => "j",
+            Rank::Queen => "q",
+            Rank::King => "k",
+            Rank::Ace => "a",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.to_lowercase();
+        Self::ALL.into_iter().find(|r| r.code() == code)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Card {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+impl Card {
+    pub fn new(rank: Rank, suit: Suit) -> Self {
+        Self { rank, suit }
+    }
+
+    /// "10s", "ah"
+    pub fn code(&self) -> String {
+        format!("{}{}", self.rank.code(), self.suit.code())
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.trim();
+        let suit = Suit::from_code(code.chars().last()?)?;
+        let rank = Rank::from_code(&code[..code.len() - 1])?;
+        Some(Self { rank, suit })
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Card::from_code(&code).ok_or_else(|| de::Error::custom(format!("unknown card '{}'", code)))
+    }
+}
+
+/// All 52 cards, clubs first, deuce to ace
+pub fn standard_deck() -> Vec<Card> {
+    Suit::ALL
+        .into_iter()
+        .flat_map(|suit| Rank::ALL.into_iter().map(move |rank| Card::new(rank, suit)))
+        .collect()
+}
+
+/// One or more decks shuffled together. Seed it to replay the same deal order.
+#[derive(Debug, Clone)]
+pub struct Shoe {
+    cards: Vec<Card>,
+    decks: usize,
+    rng: StdRng,
+}
+
+impl Shoe {
+    pub fn new(decks: usize, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+        let mut shoe = Self { cards: Vec::new(), decks: decks.max(1), rng };
+        shoe.shuffle();
+        shoe
+    }
+
+    /// Put every card back and shuffle
+    pub fn shuffle(&mut self) {
+        self.cards = (0..self.decks).flat_map(|_| standard_deck()).collect();
+        self.cards.shuffle(&mut self.rng);
+    }
+
+    /// Next card; an empty shoe is reshuffled first
+    pub fn draw(&mut self) -> Card {
+        if self.cards.is_empty() {
+            self.shuffle();
+        }
+        self.cards.pop().expect("a freshly shuffled shoe has cards")
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn size(&self) -> usize {
+        self.decks * 52
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_and_seeded_shoes_match() {
+        for card in standard_deck() {
+            assert_eq!(Card::from_code(&card.code()), Some(card));
+        }
+        assert_eq!(Card::from_code("10s"), Some(Card::new(Rank::Ten, Suit::Spades)));
+
+        let mut a = Shoe::new(2, Some(7));
+        let mut b = Shoe::new(2, Some(7));
+        assert_eq!(a.remaining(), 104);
+        assert!((0..104).all(|_| a.draw() == b.draw()));
+    }
+}
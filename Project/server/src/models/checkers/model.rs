@@ -0,0 +1,366 @@
+use crate::models::{
+    minimax::{BotPlayer, MinimaxGame, WIN_SCORE},
+    seats::TwoSeats,
+    tictactoe::model::{GameWinner, Player},
+};
+
+pub const SIZE: usize = 8;
+pub const DEFAULT_BOT_DEPTH: u32 = 6;
+pub const MAX_BOT_DEPTH: u32 = 8;
+/// Moves by both players in a row without a capture or a man moving before the game is drawn
+/// (40 moves each, as in tournament English draughts)
+pub const NO_PROGRESS_LIMIT: u32 = 80;
+
+const MAN: i8 = 1;
+const KING: i8 = 2;
+
+/// A complete turn: the squares the piece visits, from its start to where it stops.
+/// Jumps list every landing square of the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckersMove {
+    pub path: Vec<(usize, usize)>,
+    pub captured: Vec<(usize, usize)>,
+}
+
+/// Board and rules, without seats. Row 0 is the top. Player 1's pieces are positive and start
+/// at the bottom moving up; player 2's are negative. Men are 1, kings 2.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Board {
+    pub cells: [[i8; SIZE]; SIZE],
+    pub to_move: i8, // 1 = player 1, -1 = player 2
+    pub quiet_plies: u32, // Moves since the last capture or man move
+}
+
+impl Board {
+    pub fn new() -> Self {
+        let mut cells = [[0; SIZE]; SIZE];
+        for (r, row) in cells.iter_mut().enumerate() {
+            for (c, cell) in row.iter_mut().enumerate() {
+                if (r + c) % 2 == 1 {
+                    if r < 3 {
+                        *cell = -MAN;
+                    } else if r >= SIZE - 3 {
+                        *cell = MAN;
+                    }
+                }
+            }
+        }
+        Self { cells, to_move: 1, quiet_plies: 0 }
+    }
+
+    fn directions(piece: i8) -> &'static [(isize, isize)] {
+        match piece {
+            MAN => &[(-1, -1), (-1, 1)],
+            p if p == -MAN => &[(1, -1), (1, 1)],
+            _ => &[(-1, -1), (-1, 1), (1, -1), (1, 1)],
+        }
+    }
+
+    fn step(r: usize, c: usize, dr: isize, dc: isize, n: isize) -> Option<(usize, usize)> {
+        let (r, c) = (r as isize + dr * n, c as isize + dc * n);
+        let range = 0..SIZE as isize;
+        (range.contains(&r) && range.contains(&c)).then_some((r as usize, c as usize))
+    }
+
+    fn king_row(piece: i8) -> usize {
+        if piece > 0 { 0 } else { SIZE - 1 }
+    }
+
+    /// Every legal turn for the side to move. Captures are compulsory and a chain must be
+    /// jumped to the end; a man that reaches the far row is crowned and stops there.
+    pub fn legal_moves(&self) -> Vec<CheckersMove> {
+        let mut captures = Vec::new();
+        let mut steps = Vec::new();
+
+        for r in 0..SIZE {
+            for c in 0..SIZE {
+                let piece = self.cells[r][c];
+                if piece == 0 || piece.signum() != self.to_move {
+                    continue;
+                }
+                let mut cells = self.cells;
+                cells[r][c] = 0; // The jumping piece no longer blocks its start square
+                self.jumps(&cells, piece, vec![(r, c)], Vec::new(), &mut captures);
+
+                for &(dr, dc) in Self::directions(piece) {
+                    if let Some((nr, nc)) = Self::step(r, c, dr, dc, 1)
+                        && self.cells[nr][nc] == 0
+                    {
+                        steps.push(CheckersMove { path: vec![(r, c), (nr, nc)], captured: Vec::new() });
+                    }
+                }
+            }
+        }
+
+        if captures.is_empty() { steps } else { captures }
+    }
+
+    fn jumps(
+        &self,
+        cells: &[[i8; SIZE]; SIZE],
+        piece: i8,
+        path: Vec<(usize, usize)>,
+        captured: Vec<(usize, usize)>,
+        out: &mut Vec<CheckersMove>,
+    ) {
+        let (r, c) = *path.last().expect("path starts with the piece's square");
+        let mut extended = false;
+
+        for &(dr, dc) in Self::directions(piece) {
+            let (Some(over), Some(land)) = (Self::step(r, c, dr, dc, 1), Self::step(r, c, dr, dc, 2)) else {
+                continue;
+            };
+            let jumped = cells[over.0][over.1];
+            if jumped == 0 || jumped.signum() == piece.signum() || captured.contains(&over) || cells[land.0][land.1] != 0 {
+                continue;
+            }
+
+            extended = true;
+            let mut path = path.clone();
+            path.push(land);
+            let mut captured = captured.clone();
+            captured.push(over);
+            if piece.abs() == MAN && land.0 == Self::king_row(piece) {
+                out.push(CheckersMove { path, captured });
+            } else {
+                self.jumps(cells, piece, path, captured, out);
+            }
+        }
+
+        if !extended && !captured.is_empty() {
+            out.push(CheckersMove { path, captured });
+        }
+    }
+
+    /// Play a move taken from `legal_moves` and pass the turn
+    pub fn apply(&mut self, mv: &CheckersMove) {
+        let (from, to) = (mv.path[0], *mv.path.last().expect("moves have a destination"));
+        let mut piece = self.cells[from.0][from.1];
+        let man_moved = piece.abs() == MAN;
+
+        self.cells[from.0][from.1] = 0;
+        for &(r, c) in &mv.captured {
+            self.cells[r][c] = 0;
+        }
+        if man_moved && to.0 == Self::king_row(piece) {
+            piece = KING * piece.signum();
+        }
+        self.cells[to.0][to.1] = piece;
+
+        self.quiet_plies = if man_moved || !mv.captured.is_empty() { 0 } else { self.quiet_plies + 1 };
+        self.to_move = -self.to_move;
+    }
+
+    pub fn drawn(&self) -> bool {
+        self.quiet_plies >= NO_PROGRESS_LIMIT
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// English draughts on an 8x8 board for two seats, optionally against the server's bot.
+#[derive(Debug, Clone)]
+pub struct CheckersModel {
+    pub board: Board,
+    pub whos_turn: Player,
+    pub winner: GameWinner,
+    pub player1_name: Option<String>,
+    pub player2_name: Option<String>,
+    pub last_move: Option<CheckersMove>,
+    pub vs_bot: bool, // Player 2 is the server's minimax bot
+    pub bot_depth: u32, // Search depth in plies
+}
+
+impl CheckersModel {
+    pub fn new() -> Self {
+        Self {
+            board: Board::new(),
+            whos_turn: Player::Player1,
+            winner: GameWinner::Pending,
+            player1_name: None,
+            player2_name: None,
+            last_move: None,
+            vs_bot: false,
+            bot_depth: DEFAULT_BOT_DEPTH,
+        }
+    }
+
+    /// Fresh board with the same bot settings
+    pub fn reset(&mut self) {
+        *self = Self { vs_bot: self.vs_bot, bot_depth: self.bot_depth, ..Self::new() };
+    }
+
+    pub fn current_player_name(&self) -> Option<&str> {
+        match self.whos_turn {
+            Player::Player1 => self.player1_name.as_deref(),
+            Player::Player2 => self.player2_name.as_deref(),
+        }
+    }
+
+    pub fn winner_name(&self) -> Option<&str> {
+        match self.winner {
+            GameWinner::Player1 => self.player1_name.as_deref(),
+            GameWinner::Player2 => self.player2_name.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn legal_moves(&self) -> Vec<CheckersMove> {
+        if self.winner != GameWinner::Pending {
+            return Vec::new();
+        }
+        self.board.legal_moves()
+    }
+
+    /// Play the turn that follows `path` for the player whose turn it is
+    pub fn play(&mut self, path: &[(usize, usize)]) -> Result<(), &'static str> {
+        if self.winner != GameWinner::Pending {
+            return Err("game_over");
+        }
+        let legal = self.board.legal_moves();
+        let must_capture = legal.first().is_some_and(|m| !m.captured.is_empty());
+        let Some(mv) = legal.into_iter().find(|m| m.path == path) else {
+            return Err(if must_capture { "capture_required" } else { "illegal_move" });
+        };
+
+        self.board.apply(&mv);
+        self.last_move = Some(mv);
+
+        // No moves left (or no pieces) loses; a long run without progress is a draw
+        if self.board.legal_moves().is_empty() {
+            self.winner = if self.whos_turn == Player::Player1 { GameWinner::Player1 } else { GameWinner::Player2 };
+        } else if self.board.drawn() {
+            self.winner = GameWinner::Tie;
+        }
+        self.whos_turn = if self.board.to_move == 1 { Player::Player1 } else { Player::Player2 };
+        Ok(())
+    }
+}
+
+/// The bot plays player 2 and reports the move it chose
+impl BotPlayer for CheckersModel {
+    type Position = Board;
+    type Played = CheckersMove;
+
+    fn bot_search(&self) -> Option<(Board, u32)> {
+        if !self.vs_bot || self.winner != GameWinner::Pending || self.whos_turn != Player::Player2 {
+            return None;
+        }
+        Some((self.board, self.bot_depth))
+    }
+
+    fn play_bot_move(&mut self, choice: usize) -> Option<CheckersMove> {
+        let mv = self.board.legal_moves().into_iter().nth(choice)?;
+        self.play(&mv.path).ok()?;
+        Some(mv)
+    }
+}
+
+impl Default for CheckersModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TwoSeats for CheckersModel {
+    fn seat_names(&self) -> [&Option<String>; 2] {
+        [&self.player1_name, &self.player2_name]
+    }
+
+    fn seat_names_mut(&mut self) -> [&mut Option<String>; 2] {
+        [&mut self.player1_name, &mut self.player2_name]
+    }
+}
+
+/// The bot's moves are indexes into `legal_moves`
+impl MinimaxGame for Board {
+    type Move = usize;
+
+    fn legal_moves(&self) -> Vec<usize> {
+        (0..Board::legal_moves(self).len()).collect()
+    }
+
+    fn apply(&mut self, index: usize) {
+        if let Some(mv) = Board::legal_moves(self).into_iter().nth(index) {
+            Board::apply(self, &mv);
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.drawn() || Board::legal_moves(self).is_empty()
+    }
+
+    fn evaluate(&self) -> i32 {
+        if self.drawn() {
+            return 0;
+        }
+        if Board::legal_moves(self).is_empty() {
+            return -WIN_SCORE;
+        }
+        // Material, plus a little for men that have advanced
+        let mut score = 0;
+        for (r, row) in self.cells.iter().enumerate() {
+            for &piece in row {
+                let value = match piece.abs() {
+                    MAN => {
+                        let advanced = if piece > 0 { SIZE - 1 - r } else { r };
+                        100 + 2 * advanced as i32
+                    }
+                    KING => 160,
+                    _ => 0,
+                };
+                score += value * piece.signum() as i32 * self.to_move as i32;
+            }
+        }
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::minimax;
+
+    fn empty_board() -> Board {
+        Board { cells: [[0; SIZE]; SIZE], to_move: 1, quiet_plies: 0 }
+    }
+
+    #[test]
+    fn captures_are_forced_and_chains_are_jumped_to_the_end() {
+        let mut game = CheckersModel::new();
+        assert_eq!(game.legal_moves().len(), 7);
+
+        // Player 1 man at F2 can double-jump over E3 and C3
+        game.board = empty_board();
+        game.board.cells[5][1] = MAN;
+        game.board.cells[4][2] = -MAN;
+        game.board.cells[2][2] = -MAN;
+        game.board.cells[7][7] = MAN;
+
+        assert_eq!(game.play(&[(7, 7), (6, 6)]), Err("capture_required"));
+        assert_eq!(game.play(&[(5, 1), (3, 3)]), Err("capture_required"));
+        game.play(&[(5, 1), (3, 3), (1, 1)]).unwrap();
+        assert_eq!(game.board.cells[1][1], MAN);
+        assert_eq!(game.winner, GameWinner::Player1);
+    }
+
+    #[test]
+    fn men_are_crowned_and_the_bot_avoids_hanging_a_piece() {
+        let mut game = CheckersModel::new();
+        game.board = empty_board();
+        game.board.cells[1][2] = MAN;
+        game.board.cells[2][1] = -MAN;
+        game.board.cells[4][3] = MAN;
+        game.play(&[(1, 2), (0, 3)]).unwrap();
+        assert_eq!(game.board.cells[0][3], KING);
+
+        // Stepping to D3 would be jumped by the man on E4; the bot steps the other way
+        game.vs_bot = true;
+        let played = minimax::play_bot_turns(&mut game);
+        assert_eq!(played[0].path, vec![(2, 1), (3, 0)]);
+    }
+}
@@ -0,0 +1,276 @@
+use crate::models::{
+    minimax::{BotPlayer, MinimaxGame, WIN_SCORE},
+    seats::TwoSeats,
+    series::Series,
+    tictactoe::model::{GameWinner, Player},
+};
+
+pub const ROWS: usize = 6;
+pub const COLS: usize = 7;
+pub const CONNECT: usize = 4;
+pub const DEFAULT_BOT_DEPTH: u32 = 6;
+pub const MAX_BOT_DEPTH: u32 = 8;
+
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// Cells of the line of `mark`s of length >= CONNECT through (row, col), if any
+fn line_through(cells: &[[i8; COLS]; ROWS], row: usize, col: usize) -> Option<Vec<(usize, usize)>> {
+    let mark = cells[row][col];
+    if mark == 0 {
+        return None;
+    }
+    let run = |dr: isize, dc: isize| {
+        let mut count = 0;
+        let (mut r, mut c) = (row as isize + dr, col as isize + dc);
+        while (0..ROWS as isize).contains(&r) && (0..COLS as isize).contains(&c) && cells[r as usize][c as usize] == mark {
+            count += 1;
+            r += dr;
+            c += dc;
+        }
+        count
+    };
+
+    DIRECTIONS.iter().find_map(|&(dr, dc)| {
+        let back = run(-dr, -dc);
+        let len = back + 1 + run(dr, dc);
+        (len >= CONNECT as isize).then(|| {
+            (0..len)
+                .map(|i| ((row as isize + (i - back) * dr) as usize, (col as isize + (i - back) * dc) as usize))
+                .collect()
+        })
+    })
+}
+
+/// Connect Four: 7 columns x 6 rows, discs drop to the lowest free cell, four in a row wins.
+/// Row 0 is the top row. Player 1's discs are 1, player 2's are -1.
+#[derive(Debug, Clone)]
+pub struct ConnectFourModel {
+    pub board: [[i8; COLS]; ROWS],
+    pub whos_turn: Player,
+    pub winner: GameWinner,
+    pub player1_name: Option<String>,
+    pub player2_name: Option<String>,
+    pub series: Series,
+    pub winning_line: Vec<(usize, usize)>,
+    pub last_move: Option<(usize, usize)>,
+    pub vs_bot: bool, // Player 2 is the server's minimax bot
+    pub bot_depth: u32, // Search depth in plies
+}
+
+impl ConnectFourModel {
+    pub fn new() -> Self {
+        Self {
+            board: [[0; COLS]; ROWS],
+            whos_turn: Player::Player1,
+            winner: GameWinner::Pending,
+            player1_name: None,
+            player2_name: None,
+            series: Series::default(),
+            winning_line: Vec::new(),
+            last_move: None,
+            vs_bot: false,
+            bot_depth: DEFAULT_BOT_DEPTH,
+        }
+    }
+
+    /// Fresh series with the same length and bot settings
+    pub fn new_series(&mut self) {
+        let series = Series::new(self.series.best_of);
+        *self = Self { series, vs_bot: self.vs_bot, bot_depth: self.bot_depth, ..Self::new() };
+    }
+
+    /// Clear the board for the next game of the series; the first move alternates each game.
+    pub fn next_game(&mut self) -> bool {
+        if !self.series.next_game() {
+            return false;
+        }
+        self.board = [[0; COLS]; ROWS];
+        self.winner = GameWinner::Pending;
+        self.winning_line.clear();
+        self.last_move = None;
+        self.whos_turn = if self.series.first_seat() == 0 { Player::Player1 } else { Player::Player2 };
+        true
+    }
+
+    pub fn current_player_name(&self) -> Option<&str> {
+        match self.whos_turn {
+            Player::Player1 => self.player1_name.as_deref(),
+            Player::Player2 => self.player2_name.as_deref(),
+        }
+    }
+
+    pub fn winner_name(&self) -> Option<&str> {
+        match self.winner {
+            GameWinner::Player1 => self.player1_name.as_deref(),
+            GameWinner::Player2 => self.player2_name.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Drop a disc for the player whose turn it is into `col` (0-based). Returns the row it landed in.
+    pub fn drop_disc(&mut self, col: usize) -> Result<usize, &'static str> {
+        if self.winner != GameWinner::Pending {
+            return Err("game_over");
+        }
+        if col >= COLS {
+            return Err("invalid_column");
+        }
+        let Some(row) = (0..ROWS).rev().find(|&r| self.board[r][col] == 0) else {
+            return Err("column_full");
+        };
+
+        self.board[row][col] = if self.whos_turn == Player::Player1 { 1 } else { -1 };
+        self.last_move = Some((row, col));
+
+        if let Some(line) = line_through(&self.board, row, col) {
+            self.winning_line = line;
+            self.winner = if self.whos_turn == Player::Player1 { GameWinner::Player1 } else { GameWinner::Player2 };
+        } else if self.board[0].iter().all(|&c| c != 0) {
+            self.winner = GameWinner::Tie;
+        }
+
+        if self.winner == GameWinner::Pending {
+            self.whos_turn = match self.whos_turn {
+                Player::Player1 => Player::Player2,
+                Player::Player2 => Player::Player1,
+            };
+        } else {
+            self.series.record(match self.winner {
+                GameWinner::Player1 => Some(0),
+                GameWinner::Player2 => Some(1),
+                _ => None,
+            });
+        }
+        Ok(row)
+    }
+}
+
+/// The bot plays player 2 and reports the column it chose
+impl BotPlayer for ConnectFourModel {
+    type Position = Position;
+    type Played = usize;
+
+    fn bot_search(&self) -> Option<(Position, u32)> {
+        if !self.vs_bot || self.winner != GameWinner::Pending || self.whos_turn != Player::Player2 {
+            return None;
+        }
+        Some((Position { cells: self.board, to_move: -1, won: false }, self.bot_depth))
+    }
+
+    fn play_bot_move(&mut self, col: usize) -> Option<usize> {
+        self.drop_disc(col).ok()?;
+        Some(col)
+    }
+}
+
+impl TwoSeats for ConnectFourModel {
+    fn seat_names(&self) -> [&Option<String>; 2] {
+        [&self.player1_name, &self.player2_name]
+    }
+
+    fn seat_names_mut(&mut self) -> [&mut Option<String>; 2] {
+        [&mut self.player1_name, &mut self.player2_name]
+    }
+}
+
+/// Lightweight board for the bot's search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    cells: [[i8; COLS]; ROWS],
+    to_move: i8,
+    won: bool, // The previous move connected four
+}
+
+impl MinimaxGame for Position {
+    type Move = usize;
+
+    fn legal_moves(&self) -> Vec<usize> {
+        // Centre columns first
+        [3, 2, 4, 1, 5, 0, 6].into_iter().filter(|&c| self.cells[0][c] == 0).collect()
+    }
+
+    fn apply(&mut self, col: usize) {
+        if let Some(row) = (0..ROWS).rev().find(|&r| self.cells[r][col] == 0) {
+            self.cells[row][col] = self.to_move;
+            self.won = line_through(&self.cells, row, col).is_some();
+        }
+        self.to_move = -self.to_move;
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.won || self.cells[0].iter().all(|&c| c != 0)
+    }
+
+    fn evaluate(&self) -> i32 {
+        if self.won {
+            return -WIN_SCORE;
+        }
+        // Score every window of four: open lines with more of one side's discs favour that side
+        let mut score = 0;
+        for r in 0..ROWS as isize {
+            for c in 0..COLS as isize {
+                for (dr, dc) in DIRECTIONS {
+                    let end_r = r + dr * (CONNECT as isize - 1);
+                    let end_c = c + dc * (CONNECT as isize - 1);
+                    if !(0..ROWS as isize).contains(&end_r) || !(0..COLS as isize).contains(&end_c) {
+                        continue;
+                    }
+                    let (mut mine, mut theirs) = (0, 0);
+                    for i in 0..CONNECT as isize {
+                        match self.cells[(r + i * dr) as usize][(c + i * dc) as usize] {
+                            0 => {}
+                            m if m == self.to_move => mine += 1,
+                            _ => theirs += 1,
+                        }
+                    }
+                    score += match (mine, theirs) {
+                        (3, 0) => 50,
+                        (2, 0) => 5,
+                        (0, 3) => -50,
+                        (0, 2) => -5,
+                        _ => 0,
+                    };
+                }
+            }
+        }
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::minimax;
+
+    #[test]
+    fn vertical_four_wins_and_full_column_is_rejected() {
+        let mut game = ConnectFourModel::new();
+        for _ in 0..3 {
+            game.drop_disc(0).unwrap();
+            game.drop_disc(1).unwrap();
+        }
+        assert_eq!(game.drop_disc(0), Ok(2));
+        assert_eq!(game.winner, GameWinner::Player1);
+        assert_eq!(game.winning_line, vec![(2, 0), (3, 0), (4, 0), (5, 0)]);
+
+        let mut game = ConnectFourModel::new();
+        for _ in 0..ROWS {
+            game.drop_disc(6).unwrap();
+        }
+        assert_eq!(game.drop_disc(6), Err("column_full"));
+    }
+
+    #[test]
+    fn bot_blocks_three_in_a_row() {
+        let mut game = ConnectFourModel::new();
+        game.vs_bot = true;
+        // Player 1 has the bottom row from column 0 to 2; the bot must take column 3
+        for col in 0..3 {
+            game.board[5][col] = 1;
+        }
+        game.board[4][0] = -1;
+        game.board[4][1] = -1;
+        game.whos_turn = Player::Player2;
+        assert_eq!(minimax::play_bot_turns(&mut game), [3]);
+    }
+}
@@ -0,0 +1,169 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::models::cards::{standard_deck, Card, Rank, Suit};
+use crate::models::shedding::SheddingTable;
+
+/// Crazy Eights on a standard deck: match the top card's suit or rank, eights are wild.
+#[derive(Clone, Debug, Default)]
+pub struct CrazyEightsModel {
+    pub table: SheddingTable<Card>,     // seats, turn order, hands and draw pile
+    pub discard: Vec<Card>,             // discard pile (top = last)
+    pub chosen_suit: Option<Suit>,      // Suit called with the eight on top
+}
+
+impl CrazyEightsModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        self.table.reset();
+        self.discard.clear();
+        self.chosen_suit = None;
+    }
+
+    pub fn add_player(&mut self, name: &str) {
+        self.table.add_player(name);
+    }
+
+    /// Deal 7 cards each with two players, 5 with more, and turn up a starter that isn't an eight
+    pub fn start(&mut self) {
+        let hand_size = if self.table.players.len() == 2 { 7 } else { 5 };
+        self.table.start(standard_deck(), hand_size);
+        self.discard.clear();
+        self.chosen_suit = None;
+
+        while let Some(card) = self.table.deck.pop() {
+            if card.rank == Rank::Eight {
+                self.table.deck.insert(0, card);
+                continue;
+            }
+            self.discard.push(card);
+            break;
+        }
+    }
+
+    pub fn top(&self) -> Option<&Card> {
+        self.discard.last()
+    }
+
+    /// The suit that must be followed: the one called on an eight, else the top card's
+    pub fn active_suit(&self) -> Option<Suit> {
+        self.chosen_suit.or(self.top().map(|c| c.suit))
+    }
+
+    pub fn can_play(&self, card: &Card) -> bool {
+        let Some(top) = self.top() else { return false; };
+        card.rank == Rank::Eight
+            || Some(card.suit) == self.active_suit()
+            || (self.chosen_suit.is_none() && card.rank == top.rank)
+    }
+
+    pub fn has_playable(&self, player: &str) -> bool {
+        self.table.hand(player).iter().any(|c| self.can_play(c))
+    }
+
+    fn check_turn(&self, player: &str) -> Result<(), &'static str> {
+        if self.table.winner.is_some() {
+            return Err("game_over");
+        }
+        if !self.table.is_players_turn(player) {
+            return Err("not_your_turn");
+        }
+        Ok(())
+    }
+
+    /// Play a card; an eight needs the suit to call
+    pub fn play(&mut self, player: &str, card: &Card, suit: Option<Suit>) -> Result<(), &'static str> {
+        self.check_turn(player)?;
+        if !self.table.hand(player).contains(card) {
+            return Err("not_in_hand");
+        }
+        if !self.can_play(card) {
+            return Err("illegal_card");
+        }
+        let chosen = match card.rank {
+            Rank::Eight => Some(suit.ok_or("suit_required")?),
+            _ => None,
+        };
+
+        self.table.remove_one_card(player, card);
+        self.discard.push(*card);
+        self.chosen_suit = chosen;
+
+        if self.table.hand_is_empty(player) {
+            self.table.winner = Some(player.to_string());
+        } else {
+            self.table.advance_turn(1);
+        }
+        Ok(())
+    }
+
+    /// Draw one card, reshuffling the discard pile under the top card when the stock runs out
+    pub fn draw(&mut self, player: &str) -> Result<Card, &'static str> {
+        self.check_turn(player)?;
+        if self.table.deck.is_empty() {
+            self.refill_deck();
+        }
+        self.table.draw_one(player).ok_or("deck_empty")
+    }
+
+    /// Pass is only allowed when nothing can be drawn or played
+    pub fn pass(&mut self, player: &str) -> Result<(), &'static str> {
+        self.check_turn(player)?;
+        if !self.table.deck.is_empty() || self.discard.len() > 1 {
+            return Err("must_draw");
+        }
+        if self.has_playable(player) {
+            return Err("must_play");
+        }
+        self.table.advance_turn(1);
+        Ok(())
+    }
+
+    fn refill_deck(&mut self) {
+        let Some(top) = self.discard.pop() else { return; };
+        let mut cards = std::mem::take(&mut self.discard);
+        match self.table.seed {
+            Some(seed) => cards.shuffle(&mut StdRng::seed_from_u64(seed)),
+            None => cards.shuffle(&mut rand::rng()),
+        }
+        self.table.deck = cards;
+        self.discard.push(top);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(code: &str) -> Card {
+        Card::from_code(code).unwrap()
+    }
+
+    #[test]
+    fn eights_call_a_suit_that_must_be_followed() {
+        let mut game = CrazyEightsModel::new();
+        game.add_player("Ada");
+        game.add_player("Alan");
+        game.table.started = true;
+        game.discard = vec![card("5h")];
+        game.table.hands.insert("Ada".into(), vec![card("8c"), card("kd")]);
+        game.table.hands.insert("Alan".into(), vec![card("5s"), card("2s"), card("qh")]);
+
+        assert_eq!(game.play("Ada", &card("kd"), None), Err("illegal_card"));
+        assert_eq!(game.play("Ada", &card("8c"), None), Err("suit_required"));
+        game.play("Ada", &card("8c"), Some(Suit::Spades)).unwrap();
+
+        // A matching rank no longer counts once a suit has been called
+        assert_eq!(game.play("Alan", &card("qh"), None), Err("illegal_card"));
+        game.play("Alan", &card("5s"), None).unwrap();
+        assert_eq!(game.chosen_suit, None);
+        assert_eq!(game.pass("Ada"), Err("must_draw"));
+
+        game.play("Ada", &card("kd"), None).unwrap_err();
+        game.table.hands.insert("Ada".into(), vec![card("5d")]);
+        game.play("Ada", &card("5d"), None).unwrap();
+        assert_eq!(game.table.winner.as_deref(), Some("Ada"));
+    }
+}
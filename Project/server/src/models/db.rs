@@ -0,0 +1,34 @@
+use std::{path::PathBuf, sync::Arc};
+
+use redb::{backends::InMemoryBackend, Database};
+
+/// Database file the server keeps accounts and ratings in, relative to where it runs.
+/// Set `GAME_DB_PATH` to use another one.
+pub const DEFAULT_DB_PATH: &str = "game_data.redb";
+
+pub fn db_path() -> PathBuf {
+    std::env::var("GAME_DB_PATH").map(PathBuf::from).unwrap_or_else(|_| PathBuf::from(DEFAULT_DB_PATH))
+}
+
+/// Open (or create) the database file. redb allows one handle per file, so the stores share it.
+pub fn open(path: impl Into<PathBuf>) -> Result<Arc<Database>, String> {
+    Database::create(path.into()).map(Arc::new).map_err(describe)
+}
+
+/// A database that only lives as long as the process, for tests and `AppState::default()`
+pub fn in_memory() -> Arc<Database> {
+    let db = Database::builder()
+        .create_with_backend(InMemoryBackend::new())
+        .expect("in-memory database");
+    Arc::new(db)
+}
+
+pub fn describe(err: impl Into<redb::Error>) -> String {
+    err.into().to_string()
+}
+
+/// Log a database error and turn it into the status code handlers reply with
+pub fn storage_error(err: impl Into<redb::Error>) -> &'static str {
+    eprintln!("[Database] {}", describe(err));
+    "storage_error"
+}
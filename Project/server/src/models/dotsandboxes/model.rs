@@ -0,0 +1,212 @@
+pub const DEFAULT_SIZE: usize = 4;
+pub const MIN_SIZE: usize = 1;
+pub const MAX_SIZE: usize = 10;
+
+/// A line between two neighbouring dots. Horizontal edges run along the top of box (row, col),
+/// so `row` goes up to `rows`; vertical edges run down the left side, so `col` goes up to `cols`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub horizontal: bool,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Dots and Boxes for 2-4 players on a grid of `rows` x `cols` boxes. Players are indexed in seat order.
+#[derive(Debug, Clone)]
+pub struct DotsAndBoxesModel {
+    pub rows: usize,
+    pub cols: usize,
+    pub horizontal: Vec<Vec<bool>>, // (rows + 1) x cols
+    pub vertical: Vec<Vec<bool>>, // rows x (cols + 1)
+    pub owners: Vec<Vec<Option<usize>>>, // rows x cols, index of the player who closed each box
+    pub players: Vec<String>,
+    pub current_idx: usize,
+    pub scores: Vec<u32>, // Parallel to `players`
+    pub last_edge: Option<Edge>,
+    pub completed: Vec<(usize, usize)>, // Boxes closed by the last edge
+    pub started: bool,
+}
+
+impl DotsAndBoxesModel {
+    pub fn new() -> Self {
+        let mut game = Self {
+            rows: DEFAULT_SIZE,
+            cols: DEFAULT_SIZE,
+            horizontal: Vec::new(),
+            vertical: Vec::new(),
+            owners: Vec::new(),
+            players: Vec::new(),
+            current_idx: 0,
+            scores: Vec::new(),
+            last_edge: None,
+            completed: Vec::new(),
+            started: false,
+        };
+        game.clear_board();
+        game
+    }
+
+    pub fn configure(&mut self, rows: usize, cols: usize) -> Result<(), String> {
+        let size = MIN_SIZE..=MAX_SIZE;
+        if !size.contains(&rows) || !size.contains(&cols) {
+            return Err(format!("grid must be between {0}x{0} and {1}x{1} boxes", MIN_SIZE, MAX_SIZE));
+        }
+        self.rows = rows;
+        self.cols = cols;
+        self.clear_board();
+        Ok(())
+    }
+
+    fn clear_board(&mut self) {
+        self.horizontal = vec![vec![false; self.cols]; self.rows + 1];
+        self.vertical = vec![vec![false; self.cols + 1]; self.rows];
+        self.owners = vec![vec![None; self.cols]; self.rows];
+        self.last_edge = None;
+        self.completed.clear();
+    }
+
+    /// Back to an empty, unstarted grid of the same size
+    pub fn reset(&mut self) {
+        self.clear_board();
+        self.players.clear();
+        self.scores.clear();
+        self.current_idx = 0;
+        self.started = false;
+    }
+
+    /// Seat the players on an empty grid; the first seat moves first
+    pub fn start(&mut self, players: &[String]) {
+        self.reset();
+        self.players = players.to_vec();
+        self.scores = vec![0; players.len()];
+        self.started = true;
+    }
+
+    pub fn current_player(&self) -> Option<&String> {
+        if self.is_over() {
+            return None;
+        }
+        self.players.get(self.current_idx)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.started && self.owners.iter().flatten().all(|o| o.is_some())
+    }
+
+    /// Players with the highest score once every box is closed (more than one on a tie)
+    pub fn winners(&self) -> Vec<String> {
+        if !self.is_over() {
+            return Vec::new();
+        }
+        let best = self.scores.iter().copied().max().unwrap_or(0);
+        self.players.iter().zip(&self.scores).filter(|(_, s)| **s == best).map(|(p, _)| p.clone()).collect()
+    }
+
+    fn is_drawn(&self, edge: Edge) -> Option<bool> {
+        let lines = if edge.horizontal { &self.horizontal } else { &self.vertical };
+        lines.get(edge.row)?.get(edge.col).copied()
+    }
+
+    fn box_closed(&self, row: usize, col: usize) -> bool {
+        self.horizontal[row][col] && self.horizontal[row + 1][col] && self.vertical[row][col] && self.vertical[row][col + 1]
+    }
+
+    /// Draw `edge` for `player`. Closing one or two boxes scores them and the same player
+    /// moves again; otherwise the turn passes to the next seat.
+    pub fn draw_edge(&mut self, player: &str, edge: Edge) -> Result<(), &'static str> {
+        if !self.started {
+            return Err("not_started");
+        }
+        if self.is_over() {
+            return Err("game_over");
+        }
+        if self.current_player().map(|p| p.as_str()) != Some(player) {
+            return Err("not_your_turn");
+        }
+        match self.is_drawn(edge) {
+            None => return Err("invalid_edge"),
+            Some(true) => return Err("edge_taken"),
+            Some(false) => {}
+        }
+
+        if edge.horizontal {
+            self.horizontal[edge.row][edge.col] = true;
+        } else {
+            self.vertical[edge.row][edge.col] = true;
+        }
+
+        // The boxes on either side of the edge
+        let mut sides = Vec::new();
+        if edge.horizontal {
+            if edge.row > 0 {
+                sides.push((edge.row - 1, edge.col));
+            }
+            if edge.row < self.rows {
+                sides.push((edge.row, edge.col));
+            }
+        } else {
+            if edge.col > 0 {
+                sides.push((edge.row, edge.col - 1));
+            }
+            if edge.col < self.cols {
+                sides.push((edge.row, edge.col));
+            }
+        }
+
+        self.completed = sides.into_iter().filter(|&(r, c)| self.box_closed(r, c)).collect();
+        for &(r, c) in &self.completed {
+            self.owners[r][c] = Some(self.current_idx);
+            self.scores[self.current_idx] += 1;
+        }
+        self.last_edge = Some(edge);
+
+        if self.completed.is_empty() {
+            self.current_idx = (self.current_idx + 1) % self.players.len();
+        }
+        Ok(())
+    }
+}
+
+impl Default for DotsAndBoxesModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn h(row: usize, col: usize) -> Edge {
+        Edge { horizontal: true, row, col }
+    }
+
+    fn v(row: usize, col: usize) -> Edge {
+        Edge { horizontal: false, row, col }
+    }
+
+    #[test]
+    fn closing_a_box_scores_and_keeps_the_turn() {
+        let mut game = DotsAndBoxesModel::new();
+        game.configure(1, 2).unwrap();
+        game.start(&["Ada".into(), "Alan".into(), "Grace".into()]);
+
+        game.draw_edge("Ada", h(0, 0)).unwrap();
+        assert_eq!(game.draw_edge("Ada", h(1, 0)), Err("not_your_turn"));
+        game.draw_edge("Alan", h(1, 0)).unwrap();
+        game.draw_edge("Grace", v(0, 0)).unwrap();
+        assert_eq!(game.draw_edge("Ada", v(0, 0)), Err("edge_taken"));
+        assert_eq!(game.draw_edge("Ada", v(0, 3)), Err("invalid_edge"));
+
+        game.draw_edge("Ada", v(0, 1)).unwrap();
+        assert_eq!(game.owners[0][0], Some(0));
+        assert_eq!(game.current_player().map(|p| p.as_str()), Some("Ada"));
+
+        game.draw_edge("Ada", h(0, 1)).unwrap();
+        game.draw_edge("Alan", h(1, 1)).unwrap();
+        game.draw_edge("Grace", v(0, 2)).unwrap();
+        assert!(game.is_over());
+        assert_eq!(game.scores, vec![1, 0, 1]);
+        assert_eq!(game.winners(), vec!["Ada".to_string(), "Grace".to_string()]);
+    }
+}
@@ -1,39 +1,162 @@
+use std::collections::HashMap;
+
 use tokio::sync::mpsc::UnboundedSender;
 use axum::extract::ws::Message;
 
 use crate::models::{
-    rockpaperscissors::model::RockPaperScissorsModel,
-    tictactoe::model::TicTacToeModel,
+    connectfour::model::ConnectFourModel,
+    battleship::model::BattleshipModel,
+    blackjack::model::BlackjackModel,
+    checkers::model::CheckersModel,
+    reversi::model::ReversiModel,
+    dotsandboxes::model::DotsAndBoxesModel,
+    hangman::model::HangmanModel,
+    trivia::model::TriviaModel,
+    pictionary::model::PictionaryModel,
+    mancala::model::MancalaModel,
+    crazyeights::model::CrazyEightsModel,
+    gofish::model::GoFishModel,
+    holdem::model::HoldemModel,
+    lobby::Lobby,
+    takeback::Takebacks,
+    rockpaperscissors::{model::RockPaperScissorsModel, tournament::TournamentModel},
+    tictactoe::{model::TicTacToeModel, ultimate::UltimateTicTacToeModel},
     uno::model::UnoModel,
 };
+use crate::types::ServerMessage;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum GameType {
     TicTacToe(TicTacToeModel),
+    UltimateTicTacToe(UltimateTicTacToeModel),
+    ConnectFour(ConnectFourModel),
     RockPaperScissors(RockPaperScissorsModel),
+    RpsTournament(TournamentModel),
     Uno(UnoModel),
+    Blackjack(BlackjackModel),
+    CrazyEights(CrazyEightsModel),
+    GoFish(GoFishModel),
+    TexasHoldem(HoldemModel),
+    Battleship(BattleshipModel),
+    Checkers(CheckersModel),
+    Reversi(ReversiModel),
+    DotsAndBoxes(DotsAndBoxesModel),
+    Hangman(HangmanModel),
+    Trivia(TriviaModel),
+    Pictionary(PictionaryModel),
+    Mancala(MancalaModel),
     // List other game types here
 }
 
+impl GameType {
+    /// Name used for this game in `GameRoomPayload.game`
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameType::TicTacToe(_) => "tictactoe",
+            GameType::UltimateTicTacToe(_) => "ultimate_tictactoe",
+            GameType::ConnectFour(_) => "connectfour",
+            GameType::RockPaperScissors(_) => "rockpaperscissors",
+            GameType::RpsTournament(_) => "rps_tournament",
+            GameType::Uno(_) => "uno",
+            GameType::Blackjack(_) => "blackjack",
+            GameType::CrazyEights(_) => "crazy_eights",
+            GameType::GoFish(_) => "go_fish",
+            GameType::TexasHoldem(_) => "texas_holdem",
+            GameType::Battleship(_) => "battleship",
+            GameType::Checkers(_) => "checkers",
+            GameType::Reversi(_) => "reversi",
+            GameType::DotsAndBoxes(_) => "dots_and_boxes",
+            GameType::Hangman(_) => "hangman",
+            GameType::Trivia(_) => "trivia",
+            GameType::Pictionary(_) => "pictionary",
+            GameType::Mancala(_) => "mancala",
+        }
+    }
+
+    /// (min, max) seated players for this game
+    pub fn seat_limits(&self) -> (usize, usize) {
+        match self {
+            GameType::TicTacToe(_) => (2, 2),
+            GameType::UltimateTicTacToe(_) => (2, 2),
+            GameType::ConnectFour(_) => (2, 2),
+            GameType::RockPaperScissors(_) => (2, 2),
+            GameType::RpsTournament(_) => (3, 16),
+            GameType::Uno(_) => (2, 10),
+            GameType::Blackjack(_) => (1, 7),
+            GameType::CrazyEights(_) => (2, 7),
+            GameType::GoFish(_) => (2, 6),
+            GameType::TexasHoldem(_) => (2, 9),
+            GameType::Battleship(_) => (2, 2),
+            GameType::Checkers(_) => (2, 2),
+            GameType::Reversi(_) => (2, 2),
+            GameType::DotsAndBoxes(_) => (2, 4),
+            GameType::Hangman(_) => (1, 8),
+            GameType::Trivia(_) => (1, 12),
+            GameType::Pictionary(_) => (2, 12),
+            GameType::Mancala(_) => (2, 2),
+        }
+    }
+}
+
 /// A game room that holds users, chat, and a game of type T.
 #[derive(Debug)]
 pub struct GameRoom {
     pub game_id: String,
     pub users: Vec<String>,
     pub txs: Vec<UnboundedSender<Message>>, // transmitters for all members
-    pub chat_log: Vec<String>,
+    pub members: HashMap<String, UnboundedSender<Message>>, // player name -> their socket, for private views
+    pub lobby: Lobby, // seats / ready-check shared by every game type
     pub game: GameType, // the actual game model
+    pub takebacks: Takebacks, // undo history for the current game
+    pub result_recorded: bool, // The finished game's result already went into the ratings
 }
 
 impl GameRoom {
     /// Create a new game room with a specific game model
     pub fn new(game_id: String, game: GameType) -> Self {
+        let (min_players, max_players) = game.seat_limits();
         Self {
             game_id,
             users: Vec::new(),
             txs: Vec::new(),
-            chat_log: Vec::new(),
+            members: HashMap::new(),
+            lobby: Lobby::new(min_players, max_players),
             game,
+            takebacks: Takebacks::new(),
+            result_recorded: false,
+        }
+    }
+
+    /// True if `player` joined this room on the connection `tx`
+    pub fn is_member(&self, player: &str, tx: &UnboundedSender<Message>) -> bool {
+        self.members.get(player).is_some_and(|member| member.same_channel(tx))
+    }
+
+    /// Send a message to one member only (private hands, hole cards)
+    pub fn send_to(&self, player: &str, msg: &ServerMessage) {
+        if let Some(tx) = self.members.get(player) {
+            let serialized = serde_json::to_string(msg).unwrap();
+            let _ = tx.send(Message::Text(serialized.into()));
+        }
+    }
+
+    /// Send a message to every member of the room except `player` (relayed strokes)
+    pub fn broadcast_except(&self, player: &str, msg: &ServerMessage) {
+        let skip = self.members.get(player);
+        let serialized = serde_json::to_string(msg).unwrap();
+        for tx in &self.txs {
+            if skip.is_some_and(|s| s.same_channel(tx)) {
+                continue;
+            }
+            let _ = tx.send(Message::Text(serialized.clone().into()));
+        }
+    }
+
+    /// Send a message to every member of the room
+    pub fn broadcast(&self, msg: &ServerMessage) {
+        let serialized = serde_json::to_string(msg).unwrap();
+        for tx in &self.txs {
+            let _ = tx.send(Message::Text(serialized.clone().into()));
         }
     }
 }
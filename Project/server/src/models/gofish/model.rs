@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use crate::models::cards::{standard_deck, Card, Rank};
+use crate::models::shedding::{PlayerId, SheddingTable};
+
+/// The result of the last ask, shown to everyone
+#[derive(Clone, Debug, PartialEq)]
+pub struct GoFishAsk {
+    pub asker: PlayerId,
+    pub target: PlayerId,
+    pub rank: Rank,
+    pub received: usize,        // Cards handed over; 0 means "go fish"
+    pub lucky_draw: bool,       // Drew the asked rank from the pond
+}
+
+/// Go Fish on a standard deck: ask for ranks you hold, lay down books of four.
+#[derive(Clone, Debug, Default)]
+pub struct GoFishModel {
+    pub table: SheddingTable<Card>,             // seats, turn order, hands and the pond
+    pub books: HashMap<PlayerId, Vec<Rank>>,    // Completed books per player
+    pub last_ask: Option<GoFishAsk>,
+    pub game_over: bool,
+}
+
+impl GoFishModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        self.table.reset();
+        self.books.clear();
+        self.last_ask = None;
+        self.game_over = false;
+    }
+
+    pub fn add_player(&mut self, name: &str) {
+        self.table.add_player(name);
+    }
+
+    /// Deal 7 cards each to two or three players, 5 to more
+    pub fn start(&mut self) {
+        let hand_size = if self.table.players.len() <= 3 { 7 } else { 5 };
+        self.table.start(standard_deck(), hand_size);
+        self.books = self.table.players.iter().map(|p| (p.clone(), Vec::new())).collect();
+        self.last_ask = None;
+        self.game_over = false;
+
+        for player in self.table.players.clone() {
+            self.lay_down_books(&player);
+        }
+    }
+
+    /// Ask `target` for every card of `rank`. The asker keeps the turn if they get any
+    /// or fish the asked rank from the pond.
+    pub fn ask(&mut self, player: &str, target: &str, rank: Rank) -> Result<(), &'static str> {
+        if self.game_over {
+            return Err("game_over");
+        }
+        if !self.table.is_players_turn(player) {
+            return Err("not_your_turn");
+        }
+        if player == target || !self.table.players.iter().any(|p| p == target) {
+            return Err("invalid_target");
+        }
+        if !self.table.hand(player).iter().any(|c| c.rank == rank) {
+            return Err("rank_not_in_hand");
+        }
+
+        let taken: Vec<Card> = self.table.hand(target).iter().filter(|c| c.rank == rank).copied().collect();
+        let mut ask = GoFishAsk {
+            asker: player.to_string(),
+            target: target.to_string(),
+            rank,
+            received: taken.len(),
+            lucky_draw: false,
+        };
+
+        if taken.is_empty() {
+            let drawn = self.table.draw_one(player);
+            ask.lucky_draw = drawn.is_some_and(|c| c.rank == rank);
+        } else {
+            for card in &taken {
+                self.table.remove_one_card(target, card);
+            }
+            self.table.hands.entry(player.to_string()).or_default().extend(taken);
+        }
+
+        self.lay_down_books(player);
+        let keeps_turn = ask.received > 0 || ask.lucky_draw;
+        self.last_ask = Some(ask);
+
+        for p in [player.to_string(), target.to_string()] {
+            self.refill_empty_hand(&p);
+        }
+        self.finish_turn(keeps_turn);
+        Ok(())
+    }
+
+    pub fn book_count(&self, player: &str) -> usize {
+        self.books.get(player).map(|b| b.len()).unwrap_or(0)
+    }
+
+    /// Players with the most books once the game is over (more than one on a tie)
+    pub fn winners(&self) -> Vec<PlayerId> {
+        if !self.game_over {
+            return Vec::new();
+        }
+        let best = self.table.players.iter().map(|p| self.book_count(p)).max().unwrap_or(0);
+        self.table.players.iter().filter(|p| self.book_count(p) == best).cloned().collect()
+    }
+
+    fn lay_down_books(&mut self, player: &str) {
+        let Some(hand) = self.table.hands.get_mut(player) else { return; };
+        for rank in Rank::ALL {
+            if hand.iter().filter(|c| c.rank == rank).count() == 4 {
+                hand.retain(|c| c.rank != rank);
+                self.books.entry(player.to_string()).or_default().push(rank);
+            }
+        }
+    }
+
+    /// A player who runs out of cards draws one from the pond, if there is one
+    fn refill_empty_hand(&mut self, player: &str) {
+        if self.table.hand_is_empty(player) && self.table.draw_one(player).is_some() {
+            self.lay_down_books(player);
+        }
+    }
+
+    fn can_ask(&self, player: &str) -> bool {
+        !self.table.hand_is_empty(player)
+    }
+
+    /// Move to the next player who still holds cards; the game ends once all 13 books are down
+    fn finish_turn(&mut self, keeps_turn: bool) {
+        let books: usize = self.books.values().map(|b| b.len()).sum();
+        if books == Rank::ALL.len() || self.table.players.iter().all(|p| !self.can_ask(p)) {
+            self.game_over = true;
+            let winners = self.winners();
+            if let [winner] = winners.as_slice() {
+                self.table.winner = Some(winner.clone());
+            }
+            return;
+        }
+
+        let current = self.table.current_player().cloned().unwrap_or_default();
+        if keeps_turn && self.can_ask(&current) {
+            return;
+        }
+        for _ in 0..self.table.players.len() {
+            self.table.advance_turn(1);
+            let next = self.table.current_player().cloned().unwrap_or_default();
+            self.refill_empty_hand(&next);
+            if self.can_ask(&next) {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(codes: &[&str]) -> Vec<Card> {
+        codes.iter().map(|c| Card::from_code(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn asking_takes_cards_completes_books_and_passes_on_go_fish() {
+        let mut game = GoFishModel::new();
+        game.add_player("Ada");
+        game.add_player("Alan");
+        game.table.started = true;
+        game.table.deck = cards(&["2c", "9d"]);
+        game.table.hands.insert("Ada".into(), cards(&["7c", "7d", "3s"]));
+        game.table.hands.insert("Alan".into(), cards(&["7h", "7s", "kd"]));
+
+        assert_eq!(game.ask("Ada", "Alan", Rank::King), Err("rank_not_in_hand"));
+        game.ask("Ada", "Alan", Rank::Seven).unwrap();
+        assert_eq!(game.books["Ada"], vec![Rank::Seven]);
+        assert!(game.table.is_players_turn("Ada"));
+
+        // Go fish: draws the 9d, not a three, so the turn passes
+        game.ask("Ada", "Alan", Rank::Three).unwrap();
+        assert_eq!(game.last_ask.as_ref().unwrap().received, 0);
+        assert_eq!(game.table.hand("Ada").len(), 2);
+        assert!(game.table.is_players_turn("Alan"));
+    }
+}
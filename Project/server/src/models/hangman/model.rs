@@ -0,0 +1,305 @@
+use std::sync::OnceLock;
+
+use rand::{rngs::StdRng, seq::IndexedRandom, SeedableRng};
+
+pub const DEFAULT_LIVES: u32 = 6;
+pub const MAX_LIVES: u32 = 12;
+
+/// The bundled word list, grouped by category
+const WORDS: &str = include_str!("words.txt");
+
+/// Categories and their words, parsed from `words.txt` once
+pub fn word_list() -> &'static [(String, Vec<String>)] {
+    static LIST: OnceLock<Vec<(String, Vec<String>)>> = OnceLock::new();
+    LIST.get_or_init(|| parse_word_list(WORDS))
+}
+
+fn parse_word_list(text: &str) -> Vec<(String, Vec<String>)> {
+    let mut categories: Vec<(String, Vec<String>)> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            categories.push((name.to_lowercase(), Vec::new()));
+        } else if let Some((_, words)) = categories.last_mut() {
+            words.push(line.to_lowercase());
+        }
+    }
+    categories
+}
+
+pub fn category_names() -> Vec<&'static str> {
+    word_list().iter().map(|(name, _)| name.as_str()).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HangmanMode {
+    Coop, // Everyone shares the lives and wins or loses together
+    Versus, // Shared lives, but correct guesses score for the guesser
+}
+
+impl HangmanMode {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "coop" => Some(HangmanMode::Coop),
+            "versus" => Some(HangmanMode::Versus),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HangmanMode::Coop => "coop",
+            HangmanMode::Versus => "versus",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HangmanOutcome {
+    Playing,
+    Solved(String), // Name of the player who revealed the last letter or guessed the word
+    Hanged,
+}
+
+/// Hangman for one or more players taking turns at guessing a secret word from the bundled list.
+/// The secret stays in the model; views only ever get `masked_word` until the game is over.
+#[derive(Debug, Clone)]
+pub struct HangmanModel {
+    pub mode: HangmanMode,
+    pub categories: Vec<String>, // Categories the room draws from; empty means all of them
+    pub max_lives: u32,
+    pub seed: Option<u64>, // Fixed word choice; only tests set it
+    pub players: Vec<String>, // Seat order
+    pub current_idx: usize,
+    pub scores: Vec<u32>, // Parallel to `players`; only Versus scores
+    pub category: String, // Category of the current word
+    secret: String,
+    pub correct_letters: Vec<char>,
+    pub wrong_letters: Vec<char>,
+    pub wrong_words: Vec<String>,
+    pub lives: u32,
+    pub outcome: HangmanOutcome,
+    pub started: bool,
+}
+
+impl HangmanModel {
+    pub fn new() -> Self {
+        Self {
+            mode: HangmanMode::Coop,
+            categories: Vec::new(),
+            max_lives: DEFAULT_LIVES,
+            seed: None,
+            players: Vec::new(),
+            current_idx: 0,
+            scores: Vec::new(),
+            category: String::new(),
+            secret: String::new(),
+            correct_letters: Vec::new(),
+            wrong_letters: Vec::new(),
+            wrong_words: Vec::new(),
+            lives: DEFAULT_LIVES,
+            outcome: HangmanOutcome::Playing,
+            started: false,
+        }
+    }
+
+    /// Back to the lobby, keeping the room's settings
+    pub fn reset(&mut self) {
+        *self = Self {
+            mode: self.mode,
+            categories: std::mem::take(&mut self.categories),
+            max_lives: self.max_lives,
+            seed: self.seed,
+            ..Self::new()
+        };
+    }
+
+    /// Restrict the room to the given categories. Unknown names are refused.
+    pub fn set_categories(&mut self, names: &[String]) -> Result<(), String> {
+        let known = category_names();
+        let names: Vec<String> = names.iter().map(|n| n.trim().to_lowercase()).collect();
+        if let Some(unknown) = names.iter().find(|n| !known.contains(&n.as_str())) {
+            return Err(format!("unknown category '{}'", unknown));
+        }
+        self.categories = names;
+        Ok(())
+    }
+
+    /// Seat the players and pick a secret word from the room's categories
+    pub fn start(&mut self, players: &[String]) {
+        self.reset();
+        let pool: Vec<(&str, &str)> = word_list()
+            .iter()
+            .filter(|(name, _)| self.categories.is_empty() || self.categories.contains(name))
+            .flat_map(|(name, words)| words.iter().map(move |w| (name.as_str(), w.as_str())))
+            .collect();
+        let choice = match self.seed {
+            Some(seed) => pool.choose(&mut StdRng::seed_from_u64(seed)),
+            None => pool.choose(&mut rand::rng()),
+        };
+        let (category, word) = choice.copied().unwrap_or(("", ""));
+        self.start_with_word(players, category, word);
+    }
+
+    pub fn start_with_word(&mut self, players: &[String], category: &str, word: &str) {
+        self.players = players.to_vec();
+        self.scores = vec![0; players.len()];
+        self.category = category.to_string();
+        self.secret = word.to_lowercase();
+        self.lives = self.max_lives;
+        self.started = true;
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome != HangmanOutcome::Playing
+    }
+
+    pub fn current_player(&self) -> Option<&String> {
+        if !self.started || self.is_over() {
+            return None;
+        }
+        self.players.get(self.current_idx)
+    }
+
+    /// The word with unguessed letters as `_`; the whole word once the game is over
+    pub fn masked_word(&self) -> String {
+        self.secret
+            .chars()
+            .map(|c| if self.is_over() || !c.is_ascii_alphabetic() || self.correct_letters.contains(&c) { c } else { '_' })
+            .collect()
+    }
+
+    /// The secret, but only after the game has ended
+    pub fn revealed_secret(&self) -> Option<&str> {
+        self.is_over().then_some(self.secret.as_str())
+    }
+
+    /// Coop: everyone if the word was found. Versus: the top scorers.
+    pub fn winners(&self) -> Vec<String> {
+        match (&self.outcome, self.mode) {
+            (HangmanOutcome::Playing, _) => Vec::new(),
+            (HangmanOutcome::Hanged, HangmanMode::Coop) => Vec::new(),
+            (HangmanOutcome::Solved(_), HangmanMode::Coop) => self.players.clone(),
+            (_, HangmanMode::Versus) => {
+                let best = self.scores.iter().copied().max().unwrap_or(0);
+                if best == 0 {
+                    return Vec::new();
+                }
+                self.players.iter().zip(&self.scores).filter(|(_, s)| **s == best).map(|(p, _)| p.clone()).collect()
+            }
+        }
+    }
+
+    /// Guess one letter, or the whole word. A wrong guess costs a life; the turn passes either way.
+    pub fn guess(&mut self, player: &str, guess: &str) -> Result<(), &'static str> {
+        if !self.started {
+            return Err("not_started");
+        }
+        if self.is_over() {
+            return Err("game_over");
+        }
+        if self.current_player().map(|p| p.as_str()) != Some(player) {
+            return Err("not_your_turn");
+        }
+        let guess = guess.trim().to_lowercase();
+        if guess.is_empty() || !guess.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err("invalid_guess");
+        }
+
+        let mut chars = guess.chars();
+        if let (Some(letter), None) = (chars.next(), chars.next()) {
+            if self.correct_letters.contains(&letter) || self.wrong_letters.contains(&letter) {
+                return Err("already_guessed");
+            }
+            let hits = self.secret.chars().filter(|&c| c == letter).count() as u32;
+            if hits > 0 {
+                self.correct_letters.push(letter);
+                self.score(hits);
+                if !self.masked_word().contains('_') {
+                    self.outcome = HangmanOutcome::Solved(player.to_string());
+                }
+            } else {
+                self.wrong_letters.push(letter);
+                self.lose_life();
+            }
+        } else {
+            if self.wrong_words.contains(&guess) {
+                return Err("already_guessed");
+            }
+            if guess == self.secret {
+                let hidden = self.masked_word().chars().filter(|&c| c == '_').count() as u32;
+                self.score(hidden);
+                self.outcome = HangmanOutcome::Solved(player.to_string());
+            } else {
+                self.wrong_words.push(guess);
+                self.lose_life();
+            }
+        }
+
+        if !self.is_over() {
+            self.current_idx = (self.current_idx + 1) % self.players.len();
+        }
+        Ok(())
+    }
+
+    fn score(&mut self, points: u32) {
+        if self.mode == HangmanMode::Versus {
+            self.scores[self.current_idx] += points;
+        }
+    }
+
+    fn lose_life(&mut self) {
+        self.lives = self.lives.saturating_sub(1);
+        if self.lives == 0 {
+            self.outcome = HangmanOutcome::Hanged;
+        }
+    }
+}
+
+impl Default for HangmanModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_list_parses_into_categories() {
+        assert!(category_names().contains(&"animals"));
+        assert!(word_list().iter().all(|(_, words)| !words.is_empty()));
+
+        let mut game = HangmanModel::new();
+        assert!(game.set_categories(&["dragons".into()]).is_err());
+        game.set_categories(&["Food".into()]).unwrap();
+        game.start(&["Ada".into()]);
+        assert_eq!(game.category, "food");
+        assert!(game.masked_word().chars().all(|c| c == '_'));
+        assert_eq!(game.revealed_secret(), None);
+    }
+
+    #[test]
+    fn versus_scores_letters_and_wrong_guesses_cost_lives() {
+        let mut game = HangmanModel::new();
+        game.mode = HangmanMode::Versus;
+        game.max_lives = 2;
+        game.start_with_word(&["Ada".into(), "Alan".into()], "animals", "llama");
+
+        game.guess("Ada", "l").unwrap();
+        assert_eq!(game.masked_word(), "ll___");
+        assert_eq!(game.guess("Ada", "a"), Err("not_your_turn"));
+        assert_eq!(game.guess("Alan", "L"), Err("already_guessed"));
+        game.guess("Alan", "lemur").unwrap();
+        assert_eq!(game.lives, 1);
+
+        game.guess("Ada", "llama").unwrap();
+        assert_eq!(game.outcome, HangmanOutcome::Solved("Ada".into()));
+        assert_eq!(game.scores, vec![5, 0]);
+        assert_eq!(game.revealed_secret(), Some("llama"));
+        assert_eq!(game.winners(), vec!["Ada".to_string()]);
+    }
+}
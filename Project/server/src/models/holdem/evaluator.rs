@@ -0,0 +1,155 @@
+//! Poker hand ranking: the best five cards out of five to seven.
+
+use crate::models::cards::Card;
+
+/// Hand categories, weakest first, so they compare in poker order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandCategory {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl HandCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HandCategory::HighCard => "high_card",
+            HandCategory::Pair => "pair",
+            HandCategory::TwoPair => "two_pair",
+            HandCategory::ThreeOfAKind => "three_of_a_kind",
+            HandCategory::Straight => "straight",
+            HandCategory::Flush => "flush",
+            HandCategory::FullHouse => "full_house",
+            HandCategory::FourOfAKind => "four_of_a_kind",
+            HandCategory::StraightFlush => "straight_flush",
+        }
+    }
+}
+
+/// Strength of a five-card hand. Compares by category, then by the tiebreak
+/// rank values (made cards before kickers, highest first).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HandRank {
+    pub category: HandCategory,
+    pub tiebreak: Vec<u8>,
+}
+
+/// Rank exactly five cards
+pub fn evaluate_five(cards: &[Card; 5]) -> HandRank {
+    let mut values: Vec<u8> = cards.iter().map(|c| c.rank.value()).collect();
+    values.sort_unstable_by(|a, b| b.cmp(a));
+
+    let flush = cards.iter().all(|c| c.suit == cards[0].suit);
+    let distinct = {
+        let mut v = values.clone();
+        v.dedup();
+        v.len() == 5
+    };
+    let straight_high = if !distinct {
+        None
+    } else if values[0] - values[4] == 4 {
+        Some(values[0])
+    } else if values == [14, 5, 4, 3, 2] {
+        Some(5) // The wheel: ace plays low
+    } else {
+        None
+    };
+
+    // (count, value) groups, biggest group first, then highest value
+    let mut groups: Vec<(usize, u8)> = Vec::new();
+    for &value in &values {
+        match groups.iter_mut().find(|(_, v)| *v == value) {
+            Some(group) => group.0 += 1,
+            None => groups.push((1, value)),
+        }
+    }
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+    let counts: Vec<usize> = groups.iter().map(|(count, _)| *count).collect();
+    let grouped: Vec<u8> = groups.iter().map(|(_, value)| *value).collect();
+
+    let (category, tiebreak) = match (straight_high, flush, counts.as_slice()) {
+        (Some(high), true, _) => (HandCategory::StraightFlush, vec![high]),
+        (_, _, [4, 1]) => (HandCategory::FourOfAKind, grouped),
+        (_, _, [3, 2]) => (HandCategory::FullHouse, grouped),
+        (_, true, _) => (HandCategory::Flush, values),
+        (Some(high), false, _) => (HandCategory::Straight, vec![high]),
+        (_, _, [3, 1, 1]) => (HandCategory::ThreeOfAKind, grouped),
+        (_, _, [2, 2, 1]) => (HandCategory::TwoPair, grouped),
+        (_, _, [2, 1, 1, 1]) => (HandCategory::Pair, grouped),
+        _ => (HandCategory::HighCard, values),
+    };
+    HandRank { category, tiebreak }
+}
+
+/// Best five-card hand out of five to seven cards, with the cards that make it
+pub fn best_hand(cards: &[Card]) -> Option<(HandRank, [Card; 5])> {
+    let n = cards.len();
+    if n < 5 {
+        return None;
+    }
+
+    let mut best: Option<(HandRank, [Card; 5])> = None;
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        let five = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+                        let rank = evaluate_five(&five);
+                        if best.as_ref().is_none_or(|(top, _)| rank > *top) {
+                            best = Some((rank, five));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(codes: &str) -> Vec<Card> {
+        codes.split_whitespace().map(|c| Card::from_code(c).unwrap()).collect()
+    }
+
+    fn rank(codes: &str) -> HandRank {
+        best_hand(&hand(codes)).unwrap().0
+    }
+
+    #[test]
+    fn categories_are_found_in_seven_cards() {
+        assert_eq!(rank("ah kh qh jh 10h 2c 3d").category, HandCategory::StraightFlush);
+        assert_eq!(rank("9c 9d 9h 9s kd 2c 3d").category, HandCategory::FourOfAKind);
+        assert_eq!(rank("9c 9d 9h ks kd 2c 2d").category, HandCategory::FullHouse);
+        assert_eq!(rank("2h 7h 9h jh kh kc kd").category, HandCategory::Flush);
+        assert_eq!(rank("as 2d 3c 4h 5s kd kc").category, HandCategory::Straight);
+        assert_eq!(rank("7s 7d 7c 2h 9s jd qc").category, HandCategory::ThreeOfAKind);
+        assert_eq!(rank("7s 7d 2c 2h 9s 9d qc").category, HandCategory::TwoPair);
+        assert_eq!(rank("7s 7d 2c 3h 9s jd qc").category, HandCategory::Pair);
+        assert_eq!(rank("7s 8d 2c 3h 9s jd qc").category, HandCategory::HighCard);
+    }
+
+    #[test]
+    fn ties_are_broken_by_made_cards_then_kickers() {
+        // The wheel is the lowest straight
+        assert!(rank("as 2d 3c 4h 5s") < rank("2d 3c 4h 5s 6d"));
+        // Two pair: third pair on the board doesn't count, best kicker does
+        assert_eq!(rank("ks kd 9c 9h 4s 4d 2c"), rank("ks kd 9c 9h 4s 4d 3c"));
+        assert!(rank("ks kd 9c 9h as 4d 2c") > rank("ks kd 9c 9h qs 4d 3c"));
+        // Full house compares trips first
+        assert!(rank("3s 3d 3c 2h 2s") > rank("2s 2d 2c as ad"));
+        // Flush compares every card
+        assert!(rank("ah jh 9h 6h 3h") > rank("ac jc 9c 6c 2c"));
+        // Identical boards split
+        assert_eq!(rank("as ks qs jd 9c 2h 3d"), rank("as ks qs jd 9c 2c 3h"));
+    }
+}
@@ -0,0 +1,560 @@
+use std::collections::HashMap;
+
+use crate::models::cards::{Card, Shoe};
+use crate::models::holdem::evaluator::{best_hand, HandCategory, HandRank};
+
+pub const DEFAULT_STARTING_STACK: u32 = 1000;
+pub const DEFAULT_SMALL_BLIND: u32 = 5;
+pub const DEFAULT_BIG_BLIND: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoldemPhase {
+    Waiting,
+    Preflop,
+    Flop,
+    Turn,
+    River,
+    HandOver,
+    GameOver, // Fewer than two players have chips left
+}
+
+impl HoldemPhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HoldemPhase::Waiting => "waiting",
+            HoldemPhase::Preflop => "preflop",
+            HoldemPhase::Flop => "flop",
+            HoldemPhase::Turn => "turn",
+            HoldemPhase::River => "river",
+            HoldemPhase::HandOver => "hand_over",
+            HoldemPhase::GameOver => "game_over",
+        }
+    }
+
+    fn betting(self) -> bool {
+        matches!(self, HoldemPhase::Preflop | HoldemPhase::Flop | HoldemPhase::Turn | HoldemPhase::River)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HoldemSeat {
+    pub name: String,
+    pub stack: u32,
+    pub hole: Vec<Card>, // Empty when not dealt in
+    pub bet: u32, // Chips put in on the current street
+    pub committed: u32, // Chips put in this hand, all streets
+    pub folded: bool,
+    pub all_in: bool,
+    pub acted: bool, // Has acted since the last raise
+    pub shown: bool, // Hole cards revealed at showdown
+    pub won: u32, // Chips won last hand
+    pub hand: Option<HandRank>, // Best five cards, worked out at showdown
+}
+
+impl HoldemSeat {
+    fn new(name: &str, stack: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            stack,
+            hole: Vec::new(),
+            bet: 0,
+            committed: 0,
+            folded: false,
+            all_in: false,
+            acted: false,
+            shown: false,
+            won: 0,
+            hand: None,
+        }
+    }
+
+    pub fn in_hand(&self) -> bool {
+        !self.hole.is_empty() && !self.folded
+    }
+
+    fn can_act(&self) -> bool {
+        self.in_hand() && !self.all_in
+    }
+}
+
+/// How one pot (main or side) was split
+#[derive(Debug, Clone, PartialEq)]
+pub struct PotResult {
+    pub amount: u32,
+    pub winners: Vec<String>,
+    pub hand: Option<HandCategory>, // None when everyone else folded
+}
+
+/// No-Limit Texas Hold'em table. Stacks carry over from hand to hand.
+#[derive(Debug, Clone)]
+pub struct HoldemModel {
+    pub seats: Vec<HoldemSeat>,
+    pub board: Vec<Card>,
+    shoe: Shoe,
+    pub phase: HoldemPhase,
+    pub dealer: usize, // Seat with the button
+    pub current: Option<usize>, // Seat to act
+    pub current_bet: u32, // Highest bet on this street
+    pub min_raise: u32, // Smallest raise increment allowed
+    pub last_aggressor: Option<usize>,
+    pub hand_number: u32,
+    pub results: Vec<PotResult>, // Pots of the last finished hand
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub starting_stack: u32,
+    pub seed: Option<u64>, // Fixed shuffle order; only tests set it
+}
+
+impl HoldemModel {
+    pub fn new() -> Self {
+        Self {
+            seats: Vec::new(),
+            board: Vec::new(),
+            shoe: Shoe::new(1, None),
+            phase: HoldemPhase::Waiting,
+            dealer: 0,
+            current: None,
+            current_bet: 0,
+            min_raise: DEFAULT_BIG_BLIND,
+            last_aggressor: None,
+            hand_number: 0,
+            results: Vec::new(),
+            small_blind: DEFAULT_SMALL_BLIND,
+            big_blind: DEFAULT_BIG_BLIND,
+            starting_stack: DEFAULT_STARTING_STACK,
+            seed: None,
+        }
+    }
+
+    /// Seat the players and deal the first hand. Players who were already at the
+    /// table keep their stack; newcomers and busted players buy in again.
+    pub fn start(&mut self, players: &[String]) {
+        let stacks: HashMap<&str, u32> = self
+            .seats
+            .iter()
+            .filter(|s| s.stack > 0)
+            .map(|s| (s.name.as_str(), s.stack))
+            .collect();
+        let seats = players
+            .iter()
+            .map(|name| HoldemSeat::new(name, stacks.get(name.as_str()).copied().unwrap_or(self.starting_stack)))
+            .collect();
+
+        self.seats = seats;
+        self.shoe = Shoe::new(1, self.seed);
+        self.hand_number = 0;
+        self.dealer = self.seats.len().saturating_sub(1); // The button moves to seat 0 for the first hand
+        self.start_hand();
+    }
+
+    /// Back to the lobby: drop the hand in progress but keep everyone's stack
+    pub fn reset(&mut self) {
+        self.clear_hand();
+        self.phase = HoldemPhase::Waiting;
+    }
+
+    pub fn next_hand(&mut self) -> Result<(), &'static str> {
+        if self.phase != HoldemPhase::HandOver {
+            return Err("hand_in_progress");
+        }
+        self.start_hand();
+        Ok(())
+    }
+
+    pub fn current_player_name(&self) -> Option<&str> {
+        self.current.map(|i| self.seats[i].name.as_str())
+    }
+
+    pub fn pot(&self) -> u32 {
+        self.seats.iter().map(|s| s.committed).sum()
+    }
+
+    fn clear_hand(&mut self) {
+        for seat in &mut self.seats {
+            let stack = seat.stack;
+            *seat = HoldemSeat::new(&seat.name, stack);
+        }
+        self.board.clear();
+        self.results.clear();
+        self.current = None;
+        self.current_bet = 0;
+        self.min_raise = self.big_blind;
+        self.last_aggressor = None;
+    }
+
+    fn start_hand(&mut self) {
+        self.clear_hand();
+        let funded = self.seats.iter().filter(|s| s.stack > 0).count();
+        if funded < 2 {
+            self.phase = HoldemPhase::GameOver;
+            return;
+        }
+
+        self.hand_number += 1;
+        self.dealer = self.next_with_chips(self.dealer);
+        self.shoe.shuffle();
+        for idx in 0..self.seats.len() {
+            if self.seats[idx].stack > 0 {
+                self.seats[idx].hole = vec![self.shoe.draw(), self.shoe.draw()];
+            }
+        }
+
+        // Heads-up the button posts the small blind and acts first before the flop
+        let small = if funded == 2 { self.dealer } else { self.next_with_chips(self.dealer) };
+        let big = self.next_with_chips(small);
+        self.put(small, self.small_blind);
+        self.put(big, self.big_blind);
+        self.current_bet = self.big_blind;
+        self.phase = HoldemPhase::Preflop;
+
+        match self.next_to_act(big) {
+            Some(next) => self.current = Some(next),
+            None => self.end_street(),
+        }
+    }
+
+    /// fold / check / call / bet / raise / all_in. `amount` is the total to bet or raise to this street.
+    pub fn act(&mut self, name: &str, action: &str, amount: Option<u32>) -> Result<(), &'static str> {
+        if !self.phase.betting() {
+            return Err("hand_not_in_progress");
+        }
+        let idx = self.current.filter(|&i| self.seats[i].name == name).ok_or("not_your_turn")?;
+        let seat = &self.seats[idx];
+        let to_call = self.current_bet.saturating_sub(seat.bet);
+
+        match action {
+            "fold" => self.seats[idx].folded = true,
+            "check" => {
+                if to_call > 0 {
+                    return Err("cannot_check");
+                }
+            }
+            "call" => {
+                if to_call == 0 {
+                    return Err("nothing_to_call");
+                }
+                self.put(idx, to_call);
+            }
+            "bet" | "raise" => self.raise_to(idx, amount.ok_or("missing_amount")?)?,
+            "all_in" => {
+                let total = seat.bet + seat.stack;
+                if total > self.current_bet {
+                    self.raise_to(idx, total)?;
+                } else {
+                    self.put(idx, seat.stack);
+                }
+            }
+            _ => return Err("unknown_action"),
+        }
+
+        self.seats[idx].acted = true;
+        if self.seats.iter().filter(|s| s.in_hand()).count() == 1 {
+            self.award_uncontested();
+            return Ok(());
+        }
+        match self.next_to_act(idx) {
+            Some(next) => self.current = Some(next),
+            None => self.end_street(),
+        }
+        Ok(())
+    }
+
+    /// Raise the seat's street bet to `total`. Short all-ins may raise by less than the minimum.
+    fn raise_to(&mut self, idx: usize, total: u32) -> Result<(), &'static str> {
+        let seat = &self.seats[idx];
+        let max = seat.bet + seat.stack;
+        if total > max {
+            return Err("not_enough_chips");
+        }
+        if total <= self.current_bet {
+            return Err("raise_too_small");
+        }
+        let raise = total - self.current_bet;
+        if raise < self.min_raise && total < max {
+            return Err("raise_too_small");
+        }
+        // A short all-in since this seat last acted doesn't reopen the betting: call or fold only
+        if seat.acted {
+            return Err("betting_not_reopened");
+        }
+
+        self.current_bet = total;
+        self.put(idx, total - seat.bet);
+        self.last_aggressor = Some(idx);
+        if raise >= self.min_raise {
+            self.min_raise = raise;
+            for (i, other) in self.seats.iter_mut().enumerate() {
+                if i != idx {
+                    other.acted = false;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Move chips from a stack into the pot, all-in if that's everything
+    fn put(&mut self, idx: usize, amount: u32) {
+        let seat = &mut self.seats[idx];
+        let amount = amount.min(seat.stack);
+        seat.stack -= amount;
+        seat.bet += amount;
+        seat.committed += amount;
+        if seat.stack == 0 {
+            seat.all_in = true;
+        }
+    }
+
+    fn next_with_chips(&self, from: usize) -> usize {
+        let n = self.seats.len();
+        (1..=n).map(|i| (from + i) % n).find(|&i| self.seats[i].stack > 0).unwrap_or(from)
+    }
+
+    /// Next seat after `from` that still owes a decision this street
+    fn next_to_act(&self, from: usize) -> Option<usize> {
+        let n = self.seats.len();
+        (1..=n).map(|i| (from + i) % n).find(|&i| {
+            let seat = &self.seats[i];
+            seat.can_act() && (!seat.acted || seat.bet < self.current_bet)
+        })
+    }
+
+    /// Close the betting round and deal the next street. With fewer than two players
+    /// able to bet, the board is run out to showdown.
+    fn end_street(&mut self) {
+        self.current = None;
+        loop {
+            for seat in &mut self.seats {
+                seat.bet = 0;
+                seat.acted = false;
+            }
+            self.current_bet = 0;
+            self.min_raise = self.big_blind;
+
+            let (next_phase, cards) = match self.phase {
+                HoldemPhase::Preflop => (HoldemPhase::Flop, 3),
+                HoldemPhase::Flop => (HoldemPhase::Turn, 1),
+                HoldemPhase::Turn => (HoldemPhase::River, 1),
+                _ => {
+                    self.showdown();
+                    return;
+                }
+            };
+            self.shoe.draw(); // Burn
+            for _ in 0..cards {
+                self.board.push(self.shoe.draw());
+            }
+            self.phase = next_phase;
+
+            if self.seats.iter().filter(|s| s.can_act()).count() >= 2 {
+                self.current = self.next_to_act(self.dealer);
+                if self.current.is_some() {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn award_uncontested(&mut self) {
+        let amount = self.pot();
+        if let Some(winner) = self.seats.iter_mut().find(|s| s.in_hand()) {
+            winner.stack += amount;
+            winner.won = amount;
+            self.results = vec![PotResult { amount, winners: vec![winner.name.clone()], hand: None }];
+        }
+        self.finish_hand();
+    }
+
+    /// Main pot and side pots: (amount, seats eligible to win it)
+    pub fn side_pots(&self) -> Vec<(u32, Vec<usize>)> {
+        let mut left: Vec<u32> = self.seats.iter().map(|s| s.committed).collect();
+        let mut pots: Vec<(u32, Vec<usize>)> = Vec::new();
+
+        while let Some(level) = (0..self.seats.len())
+            .filter(|&i| self.seats[i].in_hand() && left[i] > 0)
+            .map(|i| left[i])
+            .min()
+        {
+            let mut amount = 0;
+            let mut eligible = Vec::new();
+            for (i, chips) in left.iter_mut().enumerate() {
+                let take = (*chips).min(level);
+                amount += take;
+                *chips -= take;
+                if self.seats[i].in_hand() && take == level {
+                    eligible.push(i);
+                }
+            }
+            match pots.last_mut() {
+                Some((pot, last)) if *last == eligible => *pot += amount,
+                _ => pots.push((amount, eligible)),
+            }
+        }
+
+        // Folded chips above every live player's contribution go to the last pot
+        let rest: u32 = left.iter().sum();
+        if let Some((pot, _)) = pots.last_mut() {
+            *pot += rest;
+        }
+        pots
+    }
+
+    fn showdown(&mut self) {
+        for seat in &mut self.seats {
+            if seat.in_hand() {
+                let cards: Vec<Card> = seat.hole.iter().chain(self.board.iter()).copied().collect();
+                seat.hand = best_hand(&cards).map(|(rank, _)| rank);
+            }
+        }
+
+        // Seats in order from the left of the button, for odd chips and showing order
+        let n = self.seats.len();
+        let order: Vec<usize> = (1..=n).map(|i| (self.dealer + i) % n).collect();
+
+        for (amount, eligible) in self.side_pots() {
+            let best = eligible.iter().filter_map(|&i| self.seats[i].hand.clone()).max();
+            let winners: Vec<usize> = order
+                .iter()
+                .copied()
+                .filter(|i| eligible.contains(i) && self.seats[*i].hand == best)
+                .collect();
+            if winners.is_empty() {
+                continue;
+            }
+            let share = amount / winners.len() as u32;
+            let odd = amount % winners.len() as u32;
+            for (k, &i) in winners.iter().enumerate() {
+                let won = share + if k == 0 { odd } else { 0 };
+                self.seats[i].stack += won;
+                self.seats[i].won += won;
+                self.seats[i].shown = true; // Winners must show to claim the pot
+            }
+            self.results.push(PotResult {
+                amount,
+                winners: winners.iter().map(|&i| self.seats[i].name.clone()).collect(),
+                hand: best.map(|rank| rank.category),
+            });
+        }
+
+        // The last aggressor shows first; everyone after only shows a hand that beats or ties it
+        let first = self
+            .last_aggressor
+            .filter(|&i| self.seats[i].in_hand())
+            .and_then(|i| order.iter().position(|&o| o == i))
+            .unwrap_or(0);
+        let mut best_shown: Option<HandRank> = None;
+        for k in 0..n {
+            let seat = &mut self.seats[order[(first + k) % n]];
+            if !seat.in_hand() {
+                continue;
+            }
+            if best_shown.is_none() || seat.hand >= best_shown {
+                seat.shown = true;
+                best_shown = seat.hand.clone();
+            }
+        }
+
+        self.finish_hand();
+    }
+
+    fn finish_hand(&mut self) {
+        self.current = None;
+        self.phase = if self.seats.iter().filter(|s| s.stack > 0).count() < 2 {
+            HoldemPhase::GameOver
+        } else {
+            HoldemPhase::HandOver
+        };
+    }
+}
+
+impl Default for HoldemModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(codes: &str) -> Vec<Card> {
+        codes.split_whitespace().map(|c| Card::from_code(c).unwrap()).collect()
+    }
+
+    fn table(players: usize) -> HoldemModel {
+        let names: Vec<String> = (0..players).map(|i| format!("P{}", i)).collect();
+        let mut game = HoldemModel::new();
+        game.seed = Some(3);
+        game.start(&names);
+        game
+    }
+
+    #[test]
+    fn all_in_side_pots_pay_the_right_players() {
+        // P0 is all-in for 100, P1 puts in 400, P2 folds after putting in 300
+        let mut game = table(3);
+        game.board = cards("2c 7d 9h jc kd");
+        let commits = [(100, "ah as", false), (400, "qh qs", false), (300, "3c 4d", true)];
+        for (seat, (committed, hole, folded)) in game.seats.iter_mut().zip(commits) {
+            seat.stack = 0;
+            seat.committed = committed;
+            seat.hole = cards(hole);
+            seat.folded = folded;
+            seat.all_in = !folded;
+        }
+
+        assert_eq!(game.side_pots(), vec![(300, vec![0, 1]), (500, vec![1])]);
+
+        game.showdown();
+        assert_eq!(game.seats[0].stack, 300);
+        assert_eq!(game.seats[1].stack, 500);
+        assert!(game.seats[0].shown && game.seats[1].shown && !game.seats[2].shown);
+    }
+
+    #[test]
+    fn betting_round_moves_streets_and_folding_ends_the_hand() {
+        let mut game = table(3);
+
+        // Button P0, blinds P1 / P2, so P0 acts first
+        assert_eq!(game.current_player_name(), Some("P0"));
+        assert_eq!(game.act("P1", "call", None), Err("not_your_turn"));
+        assert_eq!(game.act("P0", "raise", Some(15)), Err("raise_too_small"));
+        game.act("P0", "raise", Some(30)).unwrap();
+        game.act("P1", "call", None).unwrap();
+        game.act("P2", "call", None).unwrap();
+        assert_eq!(game.phase, HoldemPhase::Flop);
+        assert_eq!(game.board.len(), 3);
+        assert_eq!(game.pot(), 90);
+
+        game.act("P1", "check", None).unwrap();
+        game.act("P2", "bet", Some(50)).unwrap();
+        game.act("P0", "fold", None).unwrap();
+        game.act("P1", "fold", None).unwrap();
+        assert_eq!(game.phase, HoldemPhase::HandOver);
+        assert_eq!(game.seats[2].stack, 1000 + 60);
+        assert!(game.seats.iter().all(|s| !s.shown));
+
+        game.next_hand().unwrap();
+        assert_eq!(game.dealer, 1);
+    }
+
+    #[test]
+    fn short_all_in_does_not_reopen_the_betting() {
+        let mut game = table(3);
+        game.seats[2].stack = 140; // P2 has 150 behind with the big blind
+
+        game.act("P0", "raise", Some(100)).unwrap();
+        game.act("P1", "call", None).unwrap();
+        // All-in for 50 more is less than the 90 raise before it
+        game.act("P2", "all_in", None).unwrap();
+        assert_eq!(game.current_bet, 150);
+        assert_eq!(game.min_raise, 90);
+
+        // P0 and P1 already acted, so they may only call or fold
+        assert_eq!(game.current_player_name(), Some("P0"));
+        assert_eq!(game.act("P0", "raise", Some(300)), Err("betting_not_reopened"));
+        game.act("P0", "call", None).unwrap();
+        game.act("P1", "call", None).unwrap();
+        assert_eq!(game.phase, HoldemPhase::Flop);
+        assert_eq!(game.pot(), 450);
+    }
+}
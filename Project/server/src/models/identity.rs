@@ -0,0 +1,96 @@
+use crate::models::accounts::AccountStore;
+use crate::types::ClientMessage;
+
+/// Who a WebSocket connection plays as. A logged-in connection is its account from the start;
+/// an anonymous one is bound to the name of its first successful join. From then on every
+/// message has to name that player.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectionIdentity {
+    name: Option<String>,
+}
+
+impl ConnectionIdentity {
+    pub fn anonymous() -> Self {
+        Self::default()
+    }
+
+    pub fn account(name: String) -> Self {
+        Self { name: Some(name) }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Bind an anonymous connection once its first join went through
+    pub fn bind(&mut self, name: &str) {
+        if self.name.is_none() {
+            self.name = Some(name.to_string());
+        }
+    }
+
+    /// Check the name a message claims before any handler sees it
+    pub fn admit(&self, msg: &ClientMessage, accounts: &AccountStore) -> Result<(), String> {
+        let Some(claimed) = msg.sender() else {
+            return Ok(());
+        };
+        match &self.name {
+            Some(name) if claimed == name => Ok(()),
+            Some(name) => Err(format!("identity_mismatch: this connection plays as '{}'", name)),
+            None if !msg.is_join() => Err("not_joined: join a room before sending anything else".to_string()),
+            None if accounts.is_registered(claimed.trim()) => {
+                Err(format!("name_registered: log in to play as '{}'", claimed))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(json: &str) -> ClientMessage {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn anonymous_connection_is_bound_by_its_first_join() {
+        let accounts = AccountStore::in_memory();
+        accounts.register("carol", "correct horse").unwrap();
+        let mut identity = ConnectionIdentity::anonymous();
+
+        let chat = message(r#"{"type":"Chat","data":{"game_id":"r","player_name":"bob","chat_message":"hi","time":""}}"#);
+        assert!(identity.admit(&chat, &accounts).unwrap_err().starts_with("not_joined"));
+
+        let join_as_carol = message(r#"{"type":"GameRoom","data":{"game":"uno","action":"join","player_name":"carol","game_id":"r"}}"#);
+        assert!(identity.admit(&join_as_carol, &accounts).unwrap_err().starts_with("name_registered"));
+
+        let join = message(r#"{"type":"GameRoom","data":{"game":"uno","action":"join","player_name":"bob","game_id":"r"}}"#);
+        identity.admit(&join, &accounts).unwrap();
+        identity.bind("bob");
+        identity.admit(&chat, &accounts).unwrap();
+
+        // Later joins can't switch names
+        let join_as_alice = message(r#"{"type":"GameRoom","data":{"game":"uno","action":"join","player_name":"alice","game_id":"r2"}}"#);
+        assert!(identity.admit(&join_as_alice, &accounts).unwrap_err().starts_with("identity_mismatch"));
+    }
+
+    #[test]
+    fn game_messages_naming_another_player_are_refused() {
+        let accounts = AccountStore::in_memory();
+        let identity = ConnectionIdentity::account("bob".to_string());
+
+        let impersonations = [
+            r#"{"type":"TicTacToe","data":{"game_id":"r","whos_turn":"alice","choice":"A1"}}"#,
+            r#"{"type":"RockPaperScissors","data":{"game_id":"r","player_name":"alice","choice":"rock"}}"#,
+            r#"{"type":"Uno","data":{"game_id":"r","player_name":"alice","action":"draw_card"}}"#,
+        ];
+        for json in impersonations {
+            assert!(identity.admit(&message(json), &accounts).unwrap_err().starts_with("identity_mismatch"));
+        }
+
+        let own_move = message(r#"{"type":"TicTacToe","data":{"game_id":"r","whos_turn":"bob","choice":"A1"}}"#);
+        identity.admit(&own_move, &accounts).unwrap();
+    }
+}
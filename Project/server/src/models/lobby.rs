@@ -0,0 +1,164 @@
+/// Seconds counted down (and broadcast) between "everyone ready" and the game starting.
+pub const DEFAULT_COUNTDOWN_SECS: u8 = 3;
+
+/// Phase of the shared pre-game lobby every room goes through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LobbyPhase {
+    Waiting,   // seats filling up / players toggling ready
+    Countdown, // everyone seated is ready, start countdown running
+    InGame,    // game model has been started
+}
+
+/// Seats and ready flags for a room. Users beyond `max_players` stay in the room as spectators.
+#[derive(Debug, Clone)]
+pub struct Lobby {
+    pub seats: Vec<String>, // seated players in join order
+    pub ready: Vec<String>, // seated players who toggled ready
+    pub min_players: usize,
+    pub max_players: usize,
+    pub phase: LobbyPhase,
+    pub countdown_secs: u8,
+    pub countdown_id: u64, // bumped on every countdown so stale timers can bail out
+}
+
+impl Lobby {
+    pub fn new(min_players: usize, max_players: usize) -> Self {
+        Self {
+            seats: Vec::new(),
+            ready: Vec::new(),
+            min_players,
+            max_players,
+            phase: LobbyPhase::Waiting,
+            countdown_secs: DEFAULT_COUNTDOWN_SECS,
+            countdown_id: 0,
+        }
+    }
+
+    /// Seat a player if there is a free seat. Returns true if the player is (now) seated.
+    /// A new, unready player cancels any running countdown.
+    pub fn sit(&mut self, name: &str) -> bool {
+        if self.is_seated(name) {
+            return true;
+        }
+        if self.seats.len() >= self.max_players || self.phase == LobbyPhase::InGame {
+            return false;
+        }
+        self.seats.push(name.to_string());
+        if self.phase == LobbyPhase::Countdown {
+            self.phase = LobbyPhase::Waiting;
+        }
+        true
+    }
+
+    /// Remove a player from their seat. Any running countdown is cancelled.
+    pub fn stand(&mut self, name: &str) {
+        self.seats.retain(|s| s != name);
+        self.ready.retain(|s| s != name);
+        if self.phase == LobbyPhase::Countdown {
+            self.phase = LobbyPhase::Waiting;
+        }
+    }
+
+    pub fn is_seated(&self, name: &str) -> bool {
+        self.seats.iter().any(|s| s == name)
+    }
+
+    /// Flip a seated player's ready flag. Returns the new flag.
+    pub fn toggle_ready(&mut self, name: &str) -> Result<bool, &'static str> {
+        if self.phase == LobbyPhase::InGame {
+            return Err("in_game");
+        }
+        if !self.is_seated(name) {
+            return Err("not_seated");
+        }
+
+        if let Some(pos) = self.ready.iter().position(|r| r == name) {
+            self.ready.remove(pos);
+            // Unreadying cancels a running countdown
+            self.phase = LobbyPhase::Waiting;
+            Ok(false)
+        } else {
+            self.ready.push(name.to_string());
+            Ok(true)
+        }
+    }
+
+    /// All seats needed are filled and every seated player is ready.
+    pub fn all_ready(&self) -> bool {
+        self.seats.len() >= self.min_players
+            && self.seats.iter().all(|s| self.ready.contains(s))
+    }
+
+    /// Enter the countdown phase. Returns the id the countdown timer must check against.
+    pub fn begin_countdown(&mut self) -> u64 {
+        self.phase = LobbyPhase::Countdown;
+        self.countdown_id += 1;
+        self.countdown_id
+    }
+
+    /// True while the countdown identified by `id` is still the live one.
+    pub fn countdown_active(&self, id: u64) -> bool {
+        self.phase == LobbyPhase::Countdown && self.countdown_id == id
+    }
+
+    /// Send everyone back to the lobby; players must ready up again.
+    pub fn back_to_lobby(&mut self) {
+        self.phase = LobbyPhase::Waiting;
+        self.ready.clear();
+    }
+
+    pub fn phase_str(&self) -> &'static str {
+        match self.phase {
+            LobbyPhase::Waiting => "waiting",
+            LobbyPhase::Countdown => "countdown",
+            LobbyPhase::InGame => "in_game",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_only_when_all_seats_ready() {
+        let mut lobby = Lobby::new(2, 2);
+        assert!(lobby.sit("Ada"));
+        assert_eq!(lobby.toggle_ready("Ada"), Ok(true));
+        assert!(!lobby.all_ready());
+
+        assert!(lobby.sit("Alan"));
+        assert!(!lobby.sit("Grace")); // spectator
+        assert_eq!(lobby.toggle_ready("Grace"), Err("not_seated"));
+        assert_eq!(lobby.toggle_ready("Alan"), Ok(true));
+        assert!(lobby.all_ready());
+    }
+
+    #[test]
+    fn unready_cancels_countdown() {
+        let mut lobby = Lobby::new(2, 2);
+        lobby.sit("Ada");
+        lobby.sit("Alan");
+        lobby.toggle_ready("Ada").unwrap();
+        lobby.toggle_ready("Alan").unwrap();
+
+        let id = lobby.begin_countdown();
+        assert!(lobby.countdown_active(id));
+        assert_eq!(lobby.toggle_ready("Ada"), Ok(false));
+        assert!(!lobby.countdown_active(id));
+    }
+
+    #[test]
+    fn sitting_down_cancels_countdown() {
+        let mut lobby = Lobby::new(2, 3);
+        lobby.sit("Ada");
+        lobby.sit("Alan");
+        lobby.toggle_ready("Ada").unwrap();
+        lobby.toggle_ready("Alan").unwrap();
+
+        let id = lobby.begin_countdown();
+        assert!(lobby.sit("Grace"));
+        assert!(!lobby.countdown_active(id));
+        assert!(!lobby.all_ready());
+    }
+}
@@ -0,0 +1,333 @@
+use crate::models::{
+    minimax::{BotPlayer, MinimaxGame, WIN_SCORE},
+    seats::TwoSeats,
+    tictactoe::model::{GameWinner, Player},
+};
+
+pub const DEFAULT_PITS: usize = 6;
+pub const MIN_PITS: usize = 3;
+pub const MAX_PITS: usize = 8;
+pub const DEFAULT_SEEDS: u32 = 4;
+pub const MAX_SEEDS: u32 = 8;
+pub const DEFAULT_BOT_DEPTH: u32 = 6;
+pub const MAX_BOT_DEPTH: u32 = 10;
+
+/// Board and rules, without seats. `cells` runs counter-clockwise: player 1's pits
+/// (0..pits), player 1's store, player 2's pits, player 2's store. Each side's pits are
+/// numbered from that player's left, so sowing always moves to higher numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Board {
+    pub cells: Vec<u32>,
+    pub pits: usize,
+    pub to_move: i8, // 1 = player 1, -1 = player 2
+}
+
+impl Board {
+    pub fn new(pits: usize, seeds: u32) -> Self {
+        let mut cells = vec![seeds; 2 * pits + 2];
+        cells[pits] = 0;
+        cells[2 * pits + 1] = 0;
+        Self { cells, pits, to_move: 1 }
+    }
+
+    fn store(&self, side: i8) -> usize {
+        if side == 1 { self.pits } else { 2 * self.pits + 1 }
+    }
+
+    fn first_pit(&self, side: i8) -> usize {
+        if side == 1 { 0 } else { self.pits + 1 }
+    }
+
+    /// Seeds in `side`'s pits, from that player's left
+    pub fn side_pits(&self, side: i8) -> &[u32] {
+        let first = self.first_pit(side);
+        &self.cells[first..first + self.pits]
+    }
+
+    pub fn store_count(&self, side: i8) -> u32 {
+        self.cells[self.store(side)]
+    }
+
+    /// Pits the side to move may sow from
+    pub fn legal_moves(&self) -> Vec<usize> {
+        if self.is_over() {
+            return Vec::new();
+        }
+        (0..self.pits).filter(|&pit| self.side_pits(self.to_move)[pit] > 0).collect()
+    }
+
+    /// Sow one of the mover's pits. Returns where the last seed landed and what it captured.
+    /// Ending in your own store earns another turn; ending in an empty pit of your own captures
+    /// it and the pit opposite. Once a side has no seeds left, each player banks their own.
+    pub fn sow(&mut self, pit: usize) -> Sowing {
+        let side = self.to_move;
+        let skip = self.store(-side);
+        let mut pos = self.first_pit(side) + pit;
+        let mut seeds = std::mem::take(&mut self.cells[pos]);
+        while seeds > 0 {
+            pos = (pos + 1) % self.cells.len();
+            if pos == skip {
+                continue;
+            }
+            self.cells[pos] += 1;
+            seeds -= 1;
+        }
+
+        let mut sowing = Sowing { last: pos, captured: 0, extra_turn: pos == self.store(side) };
+        let own_pits = self.first_pit(side)..self.first_pit(side) + self.pits;
+        if own_pits.contains(&pos) && self.cells[pos] == 1 && self.cells[2 * self.pits - pos] > 0 {
+            let opposite = 2 * self.pits - pos;
+            sowing.captured = self.cells[opposite] + 1;
+            self.cells[pos] = 0;
+            self.cells[opposite] = 0;
+            let store = self.store(side);
+            self.cells[store] += sowing.captured;
+        }
+
+        if self.is_over() {
+            for s in [1, -1] {
+                let banked: u32 = self.side_pits(s).iter().sum();
+                let first = self.first_pit(s);
+                self.cells[first..first + self.pits].iter_mut().for_each(|c| *c = 0);
+                let store = self.store(s);
+                self.cells[store] += banked;
+            }
+            sowing.extra_turn = false;
+        }
+        if !sowing.extra_turn {
+            self.to_move = -side;
+        }
+        sowing
+    }
+
+    /// A side with empty pits ends the game
+    pub fn is_over(&self) -> bool {
+        self.side_pits(1).iter().all(|&s| s == 0) || self.side_pits(-1).iter().all(|&s| s == 0)
+    }
+}
+
+/// What a sowing did, for the snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sowing {
+    pub last: usize, // Index into `cells`
+    pub captured: u32, // Seeds moved to the store by a capture, including the capturing seed
+    pub extra_turn: bool,
+}
+
+/// Kalah for two seats, optionally against the server's bot
+#[derive(Debug, Clone)]
+pub struct MancalaModel {
+    pub board: Board,
+    pub seeds: u32, // Seeds per pit at the start
+    pub whos_turn: Player,
+    pub winner: GameWinner,
+    pub player1_name: Option<String>,
+    pub player2_name: Option<String>,
+    pub last_move: Option<(Player, usize)>, // Who sowed which pit
+    pub last_sowing: Option<Sowing>,
+    pub vs_bot: bool, // Player 2 is the server's minimax bot
+    pub bot_depth: u32, // Search depth in plies
+}
+
+impl MancalaModel {
+    pub fn new() -> Self {
+        Self {
+            board: Board::new(DEFAULT_PITS, DEFAULT_SEEDS),
+            seeds: DEFAULT_SEEDS,
+            whos_turn: Player::Player1,
+            winner: GameWinner::Pending,
+            player1_name: None,
+            player2_name: None,
+            last_move: None,
+            last_sowing: None,
+            vs_bot: false,
+            bot_depth: DEFAULT_BOT_DEPTH,
+        }
+    }
+
+    pub fn configure(&mut self, pits: usize, seeds: u32) -> Result<(), String> {
+        if !(MIN_PITS..=MAX_PITS).contains(&pits) {
+            return Err(format!("pits per side must be between {} and {}", MIN_PITS, MAX_PITS));
+        }
+        if !(1..=MAX_SEEDS).contains(&seeds) {
+            return Err(format!("seeds per pit must be between 1 and {}", MAX_SEEDS));
+        }
+        self.seeds = seeds;
+        self.board = Board::new(pits, seeds);
+        Ok(())
+    }
+
+    /// Fresh board with the same size and bot settings
+    pub fn reset(&mut self) {
+        *self = Self {
+            board: Board::new(self.board.pits, self.seeds),
+            seeds: self.seeds,
+            vs_bot: self.vs_bot,
+            bot_depth: self.bot_depth,
+            ..Self::new()
+        };
+    }
+
+    pub fn current_player_name(&self) -> Option<&str> {
+        match self.whos_turn {
+            Player::Player1 => self.player1_name.as_deref(),
+            Player::Player2 => self.player2_name.as_deref(),
+        }
+    }
+
+    pub fn winner_name(&self) -> Option<&str> {
+        match self.winner {
+            GameWinner::Player1 => self.player1_name.as_deref(),
+            GameWinner::Player2 => self.player2_name.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn legal_moves(&self) -> Vec<usize> {
+        if self.winner != GameWinner::Pending {
+            return Vec::new();
+        }
+        self.board.legal_moves()
+    }
+
+    /// Sow `pit` (0-based from the mover's left) for the player whose turn it is
+    pub fn play(&mut self, pit: usize) -> Result<Sowing, &'static str> {
+        if self.winner != GameWinner::Pending {
+            return Err("game_over");
+        }
+        if pit >= self.board.pits {
+            return Err("invalid_pit");
+        }
+        if self.board.side_pits(self.board.to_move)[pit] == 0 {
+            return Err("empty_pit");
+        }
+
+        let sowing = self.board.sow(pit);
+        self.last_move = Some((self.whos_turn.clone(), pit));
+        self.last_sowing = Some(sowing);
+
+        if self.board.is_over() {
+            self.winner = match self.board.store_count(1).cmp(&self.board.store_count(-1)) {
+                std::cmp::Ordering::Greater => GameWinner::Player1,
+                std::cmp::Ordering::Less => GameWinner::Player2,
+                std::cmp::Ordering::Equal => GameWinner::Tie,
+            };
+        }
+        self.whos_turn = if self.board.to_move == 1 { Player::Player1 } else { Player::Player2 };
+        Ok(sowing)
+    }
+}
+
+/// The bot plays player 2 and reports the pits it sowed. An extra turn leaves it to move,
+/// so it searches again.
+impl BotPlayer for MancalaModel {
+    type Position = Board;
+    type Played = usize;
+
+    fn bot_search(&self) -> Option<(Board, u32)> {
+        if !self.vs_bot || self.winner != GameWinner::Pending || self.whos_turn != Player::Player2 {
+            return None;
+        }
+        Some((self.board.clone(), self.bot_depth))
+    }
+
+    fn play_bot_move(&mut self, pit: usize) -> Option<usize> {
+        self.play(pit).ok()?;
+        Some(pit)
+    }
+}
+
+impl Default for MancalaModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TwoSeats for MancalaModel {
+    fn seat_names(&self) -> [&Option<String>; 2] {
+        [&self.player1_name, &self.player2_name]
+    }
+
+    fn seat_names_mut(&mut self) -> [&mut Option<String>; 2] {
+        [&mut self.player1_name, &mut self.player2_name]
+    }
+}
+
+/// The bot's moves are pit numbers. An extra turn keeps the same side to move.
+impl MinimaxGame for Board {
+    type Move = usize;
+
+    fn legal_moves(&self) -> Vec<usize> {
+        Board::legal_moves(self)
+    }
+
+    fn apply(&mut self, pit: usize) {
+        self.sow(pit);
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.is_over()
+    }
+
+    fn evaluate(&self) -> i32 {
+        let lead = self.store_count(self.to_move) as i32 - self.store_count(-self.to_move) as i32;
+        if self.is_over() {
+            return match lead.signum() {
+                1 => WIN_SCORE,
+                -1 => -WIN_SCORE,
+                _ => 0,
+            };
+        }
+        // Seeds still on your side are likely, but not certain, to end up yours
+        let on_side = self.side_pits(self.to_move).iter().sum::<u32>() as i32
+            - self.side_pits(-self.to_move).iter().sum::<u32>() as i32;
+        4 * lead + on_side
+    }
+
+    fn side_to_move(&self) -> Option<i8> {
+        Some(self.to_move)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::minimax;
+
+    #[test]
+    fn store_landings_give_another_turn_and_empty_pits_capture() {
+        let mut game = MancalaModel::new();
+        // Pit 2 holds 4 seeds: 3, 4, 5, store
+        let sowing = game.play(2).unwrap();
+        assert!(sowing.extra_turn);
+        assert_eq!(game.whos_turn, Player::Player1);
+        assert_eq!(game.play(2), Err("empty_pit"));
+
+        // The single seed in pit 1 lands in empty pit 2 and takes the 4 opposite
+        game.board.cells = vec![0, 1, 0, 0, 0, 3, 0, 4, 4, 4, 4, 4, 4, 0];
+        let sowing = game.play(1).unwrap();
+        assert_eq!(sowing.captured, 5);
+        assert_eq!(game.board.store_count(1), 5);
+        assert_eq!(game.whos_turn, Player::Player2);
+    }
+
+    #[test]
+    fn empty_side_sweeps_and_the_bot_takes_its_extra_turn() {
+        let mut game = MancalaModel::new();
+        game.board.cells = vec![0, 0, 0, 0, 0, 1, 10, 2, 0, 0, 0, 0, 0, 3];
+        game.play(5).unwrap();
+        assert_eq!(game.board.store_count(1), 11);
+        assert_eq!(game.board.store_count(-1), 5);
+        assert_eq!(game.winner, GameWinner::Player1);
+
+        // Bot (player 2) can chain three store landings (5, 4, 5) to empty its side and win
+        let mut game = MancalaModel::new();
+        game.vs_bot = true;
+        game.board.cells = vec![1, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 2, 1, 5];
+        game.board.to_move = -1;
+        game.whos_turn = Player::Player2;
+        let played = minimax::play_bot_turns(&mut game);
+        assert_eq!(played, vec![5, 4, 5]);
+        assert_eq!(game.winner, GameWinner::Player2);
+    }
+}
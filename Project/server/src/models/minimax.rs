@@ -0,0 +1,105 @@
+// Generic minimax (negamax with alpha-beta pruning) for two-player, perfect-information games.
+// Bots for Connect Four and later board games implement `MinimaxGame` on a cheap, copyable
+// position type rather than on the full room model.
+
+/// Seat name of the server's bot in single-player rooms
+pub const BOT_NAME: &str = "Bot";
+
+/// Score for a won position; wins found sooner score higher
+pub const WIN_SCORE: i32 = 1_000_000;
+
+pub trait MinimaxGame: Clone {
+    type Move: Copy;
+
+    /// Legal moves for the side to move. Put the likely best moves first; it speeds up pruning.
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    /// Play a move for the side to move and pass the turn (unless it earns another; see `side_to_move`)
+    fn apply(&mut self, mv: Self::Move);
+
+    fn is_terminal(&self) -> bool;
+
+    /// Static score from the point of view of the side to move. For a terminal position
+    /// return `-WIN_SCORE` if the side to move has lost, 0 for a draw.
+    fn evaluate(&self) -> i32;
+
+    /// Whose turn it is, for games where a move can earn the same side another one (Kalah).
+    /// Games that always alternate keep the default.
+    fn side_to_move(&self) -> Option<i8> {
+        None
+    }
+}
+
+/// A room model with a seat the server's bot plays. The bot searches a copy of the position,
+/// so the room doesn't stay locked while it thinks.
+pub trait BotPlayer {
+    type Position: MinimaxGame<Move: Send> + PartialEq + Send + 'static;
+    /// What the model reports for a move the bot played
+    type Played;
+
+    /// Position to search, and how deep, while it is the bot's turn
+    fn bot_search(&self) -> Option<(Self::Position, u32)>;
+
+    /// Play the move the search found for the current position
+    fn play_bot_move(&mut self, mv: <Self::Position as MinimaxGame>::Move) -> Option<Self::Played>;
+}
+
+/// Let the bot play while it is to move, searching in place. The server searches off the
+/// rooms lock instead (see `gameroom_handler::play_bot_turns`).
+#[cfg(test)]
+pub fn play_bot_turns<M: BotPlayer>(model: &mut M) -> Vec<M::Played> {
+    let mut played = Vec::new();
+    while let Some((position, depth)) = model.bot_search() {
+        let Some(mv) = best_move(&position, depth) else { break; };
+        let Some(result) = model.play_bot_move(mv) else { break; };
+        played.push(result);
+    }
+    played
+}
+
+/// Score `next` (reached by one move from `game`) from the point of view of the side that moved
+fn score_after<G: MinimaxGame>(game: &G, next: &G, depth: u32, alpha: i32, beta: i32) -> i32 {
+    let moves_again = game.side_to_move().is_some() && next.side_to_move() == game.side_to_move();
+    if moves_again {
+        negamax(next, depth, alpha, beta)
+    } else {
+        -negamax(next, depth, -beta, -alpha)
+    }
+}
+
+/// Best move for the side to move, searching `depth` plies. None if there are no legal moves.
+pub fn best_move<G: MinimaxGame>(game: &G, depth: u32) -> Option<G::Move> {
+    let mut best = None;
+    let mut alpha = -i32::MAX;
+    for mv in game.legal_moves() {
+        let mut next = game.clone();
+        next.apply(mv);
+        let score = score_after(game, &next, depth.saturating_sub(1), alpha, i32::MAX);
+        if best.is_none() || score > alpha {
+            alpha = score;
+            best = Some(mv);
+        }
+    }
+    best
+}
+
+fn negamax<G: MinimaxGame>(game: &G, depth: u32, mut alpha: i32, beta: i32) -> i32 {
+    if depth == 0 || game.is_terminal() {
+        // Leftover depth means the game ended sooner: prefer quicker wins and slower losses
+        let score = game.evaluate();
+        return if score.abs() >= WIN_SCORE { score + score.signum() * depth as i32 } else { score };
+    }
+
+    let mut best = -i32::MAX;
+    for mv in game.legal_moves() {
+        let mut next = game.clone();
+        next.apply(mv);
+        let score = score_after(game, &next, depth - 1, alpha, beta);
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
@@ -1,5 +1,29 @@
+pub mod accounts;
 pub mod appstate;
+pub mod lobby;
+pub mod mancala;
+pub mod cards;
+pub mod battleship;
+pub mod blackjack;
+pub mod checkers;
+pub mod reversi;
+pub mod crazyeights;
+pub mod db;
+pub mod dotsandboxes;
+pub mod gofish;
+pub mod hangman;
+pub mod identity;
+pub mod holdem;
 pub mod gameroom;
 pub mod tictactoe;
+pub mod connectfour;
+pub mod minimax;
+pub mod pictionary;
+pub mod ratings;
+pub mod seats;
+pub mod shedding;
 pub mod rockpaperscissors;
+pub mod series;
+pub mod takeback;
+pub mod trivia;
 pub mod uno;
@@ -0,0 +1,335 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, seq::IndexedRandom, SeedableRng};
+
+use crate::models::hangman::model::word_list;
+
+pub const DEFAULT_DRAW_SECS: u64 = 60;
+pub const MIN_DRAW_SECS: u64 = 15;
+pub const MAX_DRAW_SECS: u64 = 180;
+pub const DEFAULT_ROUNDS: u32 = 2;
+pub const MAX_ROUNDS: u32 = 5;
+/// How long the word stays revealed before the next drawer starts
+pub const TURN_BREAK_SECS: u64 = 4;
+
+/// Limits on what a drawer may send, so one client can't flood the room
+pub const MAX_POINTS_PER_STROKE: usize = 512;
+pub const MAX_CANVAS_STROKES: usize = 4000;
+pub const MAX_STROKE_WIDTH: f32 = 64.0;
+
+/// Guessers earn up to this much for answering quickly, on top of the same again for being right
+const GUESS_POINTS: u32 = 50;
+/// The drawer earns this for each player who guesses their word
+const DRAWER_POINTS: u32 = 25;
+
+/// A batch of pen movement: points in canvas coordinates, drawn as one line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    pub points: Vec<(f32, f32)>,
+    pub color: String, // CSS color, e.g. "#1e90ff"
+    pub width: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PictionaryPhase {
+    Waiting,
+    Drawing,
+    TurnOver,
+    Finished,
+}
+
+impl PictionaryPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PictionaryPhase::Waiting => "waiting",
+            PictionaryPhase::Drawing => "drawing",
+            PictionaryPhase::TurnOver => "turn_over",
+            PictionaryPhase::Finished => "finished",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessResult {
+    Correct,
+    Wrong,
+}
+
+/// Pictionary: players take turns drawing a secret word while everyone else guesses in chat.
+/// The server's timer task moves turns along; the canvas has its own lock so strokes can be
+/// stored and relayed while only holding the rooms map for reading.
+#[derive(Debug, Clone)]
+pub struct PictionaryModel {
+    pub categories: Vec<String>, // Word list categories to draw from; empty means all
+    pub draw_secs: u64,
+    pub rounds: u32, // Times each player draws
+    pub seed: Option<u64>, // Fixed word choice; only tests set it
+    pub players: Vec<String>, // Seat order
+    pub scores: Vec<u32>, // Parallel to `players`
+    pub phase: PictionaryPhase,
+    pub turn: usize, // Turns started so far; the drawer is players[(turn - 1) % players.len()]
+    word: String,
+    pub guessed: Vec<String>, // Players who found this turn's word, in order
+    pub started_at: Option<Instant>,
+    pub canvas: Arc<Mutex<Vec<Stroke>>>,
+    pub run_id: u64, // Bumped on every start and reset, so a stale timer task knows to stop
+}
+
+impl PictionaryModel {
+    pub fn new() -> Self {
+        Self {
+            categories: Vec::new(),
+            draw_secs: DEFAULT_DRAW_SECS,
+            rounds: DEFAULT_ROUNDS,
+            seed: None,
+            players: Vec::new(),
+            scores: Vec::new(),
+            phase: PictionaryPhase::Waiting,
+            turn: 0,
+            word: String::new(),
+            guessed: Vec::new(),
+            started_at: None,
+            canvas: Arc::new(Mutex::new(Vec::new())),
+            run_id: 0,
+        }
+    }
+
+    /// Back to the lobby, keeping the room's settings
+    pub fn reset(&mut self) {
+        self.players.clear();
+        self.scores.clear();
+        self.phase = PictionaryPhase::Waiting;
+        self.turn = 0;
+        self.word.clear();
+        self.guessed.clear();
+        self.started_at = None;
+        self.clear_canvas();
+        self.run_id += 1;
+    }
+
+    /// Seat the players; the timer task then starts the first turn. Returns the new run id.
+    pub fn start(&mut self, players: &[String]) -> u64 {
+        self.reset();
+        self.players = players.to_vec();
+        self.scores = vec![0; players.len()];
+        self.run_id
+    }
+
+    pub fn total_turns(&self) -> usize {
+        self.players.len() * self.rounds as usize
+    }
+
+    pub fn drawer(&self) -> Option<&String> {
+        if self.turn == 0 || self.phase == PictionaryPhase::Finished {
+            return None;
+        }
+        self.players.get((self.turn - 1) % self.players.len())
+    }
+
+    pub fn is_drawer(&self, player: &str) -> bool {
+        self.drawer().is_some_and(|d| d == player)
+    }
+
+    /// The word, for the drawer or once the turn is over
+    pub fn word_for(&self, viewer: Option<&str>) -> Option<&str> {
+        let visible = match self.phase {
+            PictionaryPhase::Drawing => viewer.is_some_and(|v| self.is_drawer(v)),
+            PictionaryPhase::TurnOver | PictionaryPhase::Finished => true,
+            PictionaryPhase::Waiting => false,
+        };
+        (visible && !self.word.is_empty()).then_some(self.word.as_str())
+    }
+
+    /// Underscores for letters, e.g. "_ _ _ _ _"
+    pub fn hint(&self) -> String {
+        self.word.chars().map(|c| if c.is_ascii_alphabetic() { "_" } else { " " }).collect::<Vec<_>>().join(" ")
+    }
+
+    pub fn seconds_left(&self, now: Instant) -> Option<u64> {
+        if self.phase != PictionaryPhase::Drawing {
+            return None;
+        }
+        let elapsed = now.saturating_duration_since(self.started_at?);
+        Some(Duration::from_secs(self.draw_secs).saturating_sub(elapsed).as_secs_f64().ceil() as u64)
+    }
+
+    /// Hand the pen to the next player with a fresh word and a blank canvas.
+    /// Returns false once everyone has had their turns.
+    pub fn next_turn(&mut self, now: Instant) -> bool {
+        self.guessed.clear();
+        self.clear_canvas();
+        if self.turn >= self.total_turns() {
+            self.phase = PictionaryPhase::Finished;
+            self.started_at = None;
+            return false;
+        }
+        self.turn += 1;
+        self.word = self.pick_word();
+        self.started_at = Some(now);
+        self.phase = PictionaryPhase::Drawing;
+        true
+    }
+
+    fn pick_word(&self) -> String {
+        let pool: Vec<&str> = word_list()
+            .iter()
+            .filter(|(name, _)| self.categories.is_empty() || self.categories.contains(name))
+            .flat_map(|(_, words)| words.iter().map(|w| w.as_str()))
+            .collect();
+        let choice = match self.seed {
+            Some(seed) => pool.choose(&mut StdRng::seed_from_u64(seed.wrapping_add(self.turn as u64))),
+            None => pool.choose(&mut rand::rng()),
+        };
+        choice.map(|w| w.to_string()).unwrap_or_default()
+    }
+
+    pub fn turn_is_done(&self, now: Instant) -> bool {
+        let guessers = self.players.len().saturating_sub(1);
+        self.phase == PictionaryPhase::Drawing && (self.guessed.len() >= guessers || self.seconds_left(now) == Some(0))
+    }
+
+    pub fn end_turn(&mut self) {
+        if self.phase == PictionaryPhase::Drawing {
+            self.phase = PictionaryPhase::TurnOver;
+        }
+    }
+
+    /// Check a chat message against the word. Only players still guessing can score.
+    pub fn guess(&mut self, player: &str, text: &str, now: Instant) -> GuessResult {
+        if self.phase != PictionaryPhase::Drawing || self.is_drawer(player) || self.guessed.iter().any(|g| g == player) {
+            return GuessResult::Wrong;
+        }
+        let Some(seat) = self.players.iter().position(|p| p == player) else {
+            return GuessResult::Wrong;
+        };
+        if !text.trim().eq_ignore_ascii_case(&self.word) {
+            return GuessResult::Wrong;
+        }
+
+        let left = self.seconds_left(now).unwrap_or(0) as f64 / self.draw_secs as f64;
+        self.scores[seat] += GUESS_POINTS + (GUESS_POINTS as f64 * left).round() as u32;
+        if let Some(drawer) = self.players.iter().position(|p| self.is_drawer(p)) {
+            self.scores[drawer] += DRAWER_POINTS;
+        }
+        self.guessed.push(player.to_string());
+        GuessResult::Correct
+    }
+
+    /// Whether `player`'s chat could give the word away: the drawer and anyone who already found it
+    pub fn knows_word(&self, player: &str) -> bool {
+        self.phase == PictionaryPhase::Drawing && (self.is_drawer(player) || self.guessed.iter().any(|g| g == player))
+    }
+
+    /// `text` with every occurrence of the word starred out
+    pub fn mask_word(&self, text: &str) -> String {
+        if self.word.is_empty() {
+            return text.to_string();
+        }
+        // ASCII lowercasing keeps byte offsets the same as in `text`
+        let lower = text.to_ascii_lowercase();
+        let mut out = String::new();
+        let mut rest = 0;
+        for (start, _) in lower.match_indices(&self.word) {
+            out.push_str(&text[rest..start]);
+            out.push_str(&"*".repeat(self.word.len()));
+            rest = start + self.word.len();
+        }
+        out.push_str(&text[rest..]);
+        out
+    }
+
+    /// Validate and keep a stroke from the drawer. Takes `&self`: the canvas has its own lock.
+    pub fn add_stroke(&self, player: &str, stroke: Stroke) -> Result<(), &'static str> {
+        if self.phase != PictionaryPhase::Drawing {
+            return Err("not_drawing");
+        }
+        if !self.is_drawer(player) {
+            return Err("not_the_drawer");
+        }
+        if stroke.points.is_empty() || stroke.points.len() > MAX_POINTS_PER_STROKE {
+            return Err("invalid_stroke");
+        }
+        if !(stroke.width > 0.0 && stroke.width <= MAX_STROKE_WIDTH)
+            || stroke.color.len() > 32
+            || stroke.points.iter().any(|(x, y)| !x.is_finite() || !y.is_finite())
+        {
+            return Err("invalid_stroke");
+        }
+        let mut canvas = self.canvas.lock().expect("canvas lock poisoned");
+        if canvas.len() >= MAX_CANVAS_STROKES {
+            return Err("canvas_full");
+        }
+        canvas.push(stroke);
+        Ok(())
+    }
+
+    pub fn clear_canvas(&self) {
+        self.canvas.lock().expect("canvas lock poisoned").clear();
+    }
+
+    pub fn strokes(&self) -> Vec<Stroke> {
+        self.canvas.lock().expect("canvas lock poisoned").clone()
+    }
+
+    /// Top scorers once every turn has been played
+    pub fn winners(&self) -> Vec<String> {
+        if self.phase != PictionaryPhase::Finished {
+            return Vec::new();
+        }
+        let best = self.scores.iter().copied().max().unwrap_or(0);
+        self.players.iter().zip(&self.scores).filter(|(_, s)| **s == best).map(|(p, _)| p.clone()).collect()
+    }
+}
+
+impl Default for PictionaryModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dot() -> Stroke {
+        Stroke { points: vec![(1.0, 2.0)], color: "#000".into(), width: 3.0 }
+    }
+
+    #[test]
+    fn only_the_drawer_draws_and_correct_guesses_score() {
+        let mut game = PictionaryModel::new();
+        game.rounds = 1;
+        game.seed = Some(7);
+        game.start(&["Ada".into(), "Alan".into(), "Grace".into()]);
+        let t0 = Instant::now();
+        assert!(game.next_turn(t0));
+        assert!(game.is_drawer("Ada"));
+
+        assert_eq!(game.add_stroke("Alan", dot()), Err("not_the_drawer"));
+        assert_eq!(game.add_stroke("Ada", Stroke { width: 0.0, ..dot() }), Err("invalid_stroke"));
+        game.add_stroke("Ada", dot()).unwrap();
+        assert_eq!(game.strokes().len(), 1);
+
+        let word = game.word_for(Some("Ada")).unwrap().to_string();
+        assert_eq!(game.word_for(Some("Alan")), None);
+        assert_eq!(game.guess("Ada", &word, t0), GuessResult::Wrong);
+        assert_eq!(game.guess("Alan", "not it", t0), GuessResult::Wrong);
+        assert_eq!(game.guess("Alan", &word.to_uppercase(), t0), GuessResult::Correct);
+        assert_eq!(game.guess("Alan", &word, t0), GuessResult::Wrong);
+        assert_eq!(game.scores, vec![25, 100, 0]);
+        assert!(game.knows_word("Alan"));
+        assert_eq!(game.mask_word(&format!("it was {}!", word)), format!("it was {}!", "*".repeat(word.len())));
+
+        assert_eq!(game.guess("Grace", &word, t0 + Duration::from_secs(30)), GuessResult::Correct);
+        assert!(game.turn_is_done(t0));
+        game.end_turn();
+        assert_eq!(game.word_for(None), Some(word.as_str()));
+
+        assert!(game.next_turn(t0));
+        assert!(game.is_drawer("Alan"));
+        assert!(game.strokes().is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use redb::{Database, ReadableTable, TableDefinition};
+use serde::{Deserialize, Serialize};
+
+use crate::models::db::{self, storage_error};
+
+/// Games whose finished results are rated, by the name rooms are created with
+pub const RATED_GAMES: [&str; 3] = ["tictactoe", "rockpaperscissors", "uno"];
+pub const INITIAL_RATING: f64 = 1200.0;
+/// Most rating a two-player game can move; split across opponents in bigger games
+pub const K_FACTOR: f64 = 32.0;
+pub const DEFAULT_LEADERBOARD_SIZE: usize = 10;
+pub const MAX_LEADERBOARD_SIZE: usize = 100;
+
+/// (game, player name) -> PlayerStats as JSON
+const STATS: TableDefinition<(&str, &str), &str> = TableDefinition::new("stats");
+
+/// One player's record in one game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlayerStats {
+    pub rating: f64,
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub streak: i32, // Current run: +n wins or -n losses in a row; a draw ends it
+    pub best_streak: u32, // Longest run of wins
+}
+
+impl Default for PlayerStats {
+    fn default() -> Self {
+        Self { rating: INITIAL_RATING, games_played: 0, wins: 0, losses: 0, draws: 0, streak: 0, best_streak: 0 }
+    }
+}
+
+impl PlayerStats {
+    fn add_win(&mut self) {
+        self.wins += 1;
+        self.streak = self.streak.max(0) + 1;
+        self.best_streak = self.best_streak.max(self.streak as u32);
+    }
+
+    fn add_loss(&mut self) {
+        self.losses += 1;
+        self.streak = self.streak.min(0) - 1;
+    }
+
+    fn add_draw(&mut self) {
+        self.draws += 1;
+        self.streak = 0;
+    }
+}
+
+/// How a finished game ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameResult {
+    Win { winner: String, losers: Vec<String> },
+    Draw(Vec<String>),
+}
+
+impl GameResult {
+    /// Everyone who took part, winner first
+    pub fn players(&self) -> Vec<&String> {
+        match self {
+            GameResult::Win { winner, losers } => std::iter::once(winner).chain(losers).collect(),
+            GameResult::Draw(players) => players.iter().collect(),
+        }
+    }
+}
+
+/// Elo's expected score for a player rated `a` against one rated `b`
+fn expected(a: f64, b: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((b - a) / 400.0))
+}
+
+/// Rating changes for a result, from the players' ratings before the game. A multiplayer win
+/// counts as the winner beating each loser (losers don't play each other); a draw as every
+/// pair drawing. K is shared out so one game moves a rating by at most `K_FACTOR`.
+fn rating_changes(result: &GameResult, ratings: &BTreeMap<String, f64>) -> BTreeMap<String, f64> {
+    let mut changes: BTreeMap<String, f64> = ratings.keys().map(|name| (name.clone(), 0.0)).collect();
+    match result {
+        GameResult::Win { winner, losers } => {
+            let k = K_FACTOR / losers.len().max(1) as f64;
+            for loser in losers {
+                let gain = k * (1.0 - expected(ratings[winner], ratings[loser]));
+                *changes.get_mut(winner).unwrap() += gain;
+                *changes.get_mut(loser).unwrap() -= gain;
+            }
+        }
+        GameResult::Draw(players) => {
+            let k = K_FACTOR / (players.len().max(2) - 1) as f64;
+            for (i, a) in players.iter().enumerate() {
+                for b in &players[i + 1..] {
+                    let gain = k * (0.5 - expected(ratings[a], ratings[b]));
+                    *changes.get_mut(a).unwrap() += gain;
+                    *changes.get_mut(b).unwrap() -= gain;
+                }
+            }
+        }
+    }
+    changes
+}
+
+/// Per-game Elo ratings and lifetime stats, kept in the server's database
+pub struct RatingStore {
+    db: Arc<Database>,
+}
+
+impl RatingStore {
+    /// Ratings that only live as long as the process, for tests and `AppState::default()`
+    pub fn in_memory() -> Self {
+        Self::new(db::in_memory()).expect("in-memory database")
+    }
+
+    pub fn new(db: Arc<Database>) -> Result<Self, String> {
+        let txn = db.begin_write().map_err(db::describe)?;
+        txn.open_table(STATS).map_err(db::describe)?;
+        txn.commit().map_err(db::describe)?;
+        Ok(Self { db })
+    }
+
+    /// Apply a finished game to everyone who played it
+    pub fn record(&self, game: &str, result: &GameResult) -> Result<(), &'static str> {
+        let players = result.players();
+
+        let txn = self.db.begin_write().map_err(storage_error)?;
+        {
+            let mut table = txn.open_table(STATS).map_err(storage_error)?;
+            let mut stats = BTreeMap::new();
+            for &name in &players {
+                let current = match table.get((game, name.as_str())).map_err(storage_error)? {
+                    Some(json) => serde_json::from_str(json.value()).map_err(|_| "storage_error")?,
+                    None => PlayerStats::default(),
+                };
+                stats.insert(name.clone(), current);
+            }
+
+            let ratings = stats.iter().map(|(name, s)| (name.clone(), s.rating)).collect();
+            for (name, change) in rating_changes(result, &ratings) {
+                let entry = stats.get_mut(&name).unwrap();
+                entry.rating += change;
+                entry.games_played += 1;
+                match result {
+                    GameResult::Win { winner, .. } if *winner == name => entry.add_win(),
+                    GameResult::Win { .. } => entry.add_loss(),
+                    GameResult::Draw(_) => entry.add_draw(),
+                }
+            }
+
+            for (name, entry) in &stats {
+                let json = serde_json::to_string(entry).map_err(|_| "storage_error")?;
+                table.insert((game, name.as_str()), json.as_str()).map_err(storage_error)?;
+            }
+        }
+        txn.commit().map_err(storage_error)?;
+        Ok(())
+    }
+
+    /// A player's record in every rated game they have played
+    pub fn player_stats(&self, name: &str) -> Result<BTreeMap<String, PlayerStats>, &'static str> {
+        let txn = self.db.begin_read().map_err(storage_error)?;
+        let table = txn.open_table(STATS).map_err(storage_error)?;
+        let mut games = BTreeMap::new();
+        for game in RATED_GAMES {
+            if let Some(json) = table.get((game, name)).map_err(storage_error)? {
+                let stats = serde_json::from_str(json.value()).map_err(|_| "storage_error")?;
+                games.insert(game.to_string(), stats);
+            }
+        }
+        Ok(games)
+    }
+
+    /// Best-rated players of one game, highest first
+    pub fn leaderboard(&self, game: &str, limit: usize) -> Result<Vec<(String, PlayerStats)>, &'static str> {
+        let txn = self.db.begin_read().map_err(storage_error)?;
+        let table = txn.open_table(STATS).map_err(storage_error)?;
+        let mut players: Vec<(String, PlayerStats)> = Vec::new();
+        for entry in table.range((game, "")..).map_err(storage_error)? {
+            let (key, json) = entry.map_err(storage_error)?;
+            let (entry_game, name) = key.value();
+            if entry_game != game {
+                break;
+            }
+            let stats = serde_json::from_str(json.value()).map_err(|_| "storage_error")?;
+            players.push((name.to_string(), stats));
+        }
+        players.sort_by(|a, b| b.1.rating.total_cmp(&a.1.rating).then_with(|| a.0.cmp(&b.0)));
+        players.truncate(limit);
+        Ok(players)
+    }
+}
+
+impl Default for RatingStore {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn win(winner: &str, losers: &[&str]) -> GameResult {
+        GameResult::Win { winner: winner.to_string(), losers: losers.iter().map(|s| s.to_string()).collect() }
+    }
+
+    #[test]
+    fn results_update_ratings_streaks_and_the_leaderboard() {
+        let ratings = RatingStore::in_memory();
+        ratings.record("tictactoe", &win("alice", &["bob"])).unwrap();
+        ratings.record("tictactoe", &win("alice", &["bob"])).unwrap();
+        ratings.record("tictactoe", &GameResult::Draw(vec!["alice".into(), "bob".into()])).unwrap();
+
+        let alice = &ratings.player_stats("alice").unwrap()["tictactoe"];
+        let bob = &ratings.player_stats("bob").unwrap()["tictactoe"];
+        assert_eq!((alice.games_played, alice.wins, alice.losses, alice.draws), (3, 2, 0, 1));
+        assert_eq!((alice.streak, alice.best_streak), (0, 2));
+        assert_eq!((bob.streak, bob.losses), (0, 2));
+        // Even players swap 16 points on the first game; ratings are zero-sum
+        assert!(alice.rating > 1216.0 && alice.rating < 1232.0);
+        assert!((alice.rating + bob.rating - 2.0 * INITIAL_RATING).abs() < 1e-9);
+
+        let board = ratings.leaderboard("tictactoe", 10).unwrap();
+        assert_eq!(board.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(), ["alice", "bob"]);
+        assert!(ratings.leaderboard("uno", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn multiplayer_win_splits_k_across_the_losers() {
+        let ratings = RatingStore::in_memory();
+        ratings.record("uno", &win("carol", &["alice", "bob", "dave"])).unwrap();
+
+        let carol = &ratings.player_stats("carol").unwrap()["uno"];
+        let dave = &ratings.player_stats("dave").unwrap()["uno"];
+        assert!((carol.rating - (INITIAL_RATING + K_FACTOR / 2.0)).abs() < 1e-9);
+        assert!((dave.rating - (INITIAL_RATING - K_FACTOR / 6.0)).abs() < 1e-9);
+        assert_eq!(dave.streak, -1);
+        assert!(!ratings.player_stats("alice").unwrap().contains_key("tictactoe"));
+    }
+}
@@ -0,0 +1,353 @@
+use crate::models::{
+    minimax::{BotPlayer, MinimaxGame, WIN_SCORE},
+    seats::TwoSeats,
+    tictactoe::model::{GameWinner, Player},
+};
+
+pub const SIZE: usize = 8;
+
+const DIRECTIONS: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// Square values for the bot: corners are gold, the squares next to them hand corners away
+const WEIGHTS: [[i32; SIZE]; SIZE] = [
+    [100, -20, 10, 5, 5, 10, -20, 100],
+    [-20, -50, -2, -2, -2, -2, -50, -20],
+    [10, -2, 1, 1, 1, 1, -2, 10],
+    [5, -2, 1, 0, 0, 1, -2, 5],
+    [5, -2, 1, 0, 0, 1, -2, 5],
+    [10, -2, 1, 1, 1, 1, -2, 10],
+    [-20, -50, -2, -2, -2, -2, -50, -20],
+    [100, -20, 10, 5, 5, 10, -20, 100],
+];
+
+/// Bot strengths and how many plies each searches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotLevel {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl BotLevel {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "easy" => Some(BotLevel::Easy),
+            "medium" => Some(BotLevel::Medium),
+            "hard" => Some(BotLevel::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn depth(self) -> u32 {
+        match self {
+            BotLevel::Easy => 1,
+            BotLevel::Medium => 3,
+            BotLevel::Hard => 5,
+        }
+    }
+}
+
+/// Board and rules, without seats. Row 0 is the top; player 1 (black, 1) moves first,
+/// player 2 is white (-1).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Board {
+    pub cells: [[i8; SIZE]; SIZE],
+    pub to_move: i8,
+}
+
+impl Board {
+    pub fn new() -> Self {
+        let mut cells = [[0; SIZE]; SIZE];
+        cells[3][3] = -1;
+        cells[4][4] = -1;
+        cells[3][4] = 1;
+        cells[4][3] = 1;
+        Self { cells, to_move: 1 }
+    }
+
+    /// Discs `color` would flip by playing (row, col); empty if the move is illegal
+    pub fn flips(&self, row: usize, col: usize, color: i8) -> Vec<(usize, usize)> {
+        if self.cells[row][col] != 0 {
+            return Vec::new();
+        }
+        let mut flipped = Vec::new();
+        for (dr, dc) in DIRECTIONS {
+            let mut line = Vec::new();
+            let (mut r, mut c) = (row as isize + dr, col as isize + dc);
+            while (0..SIZE as isize).contains(&r) && (0..SIZE as isize).contains(&c) {
+                match self.cells[r as usize][c as usize] {
+                    v if v == -color => line.push((r as usize, c as usize)),
+                    v if v == color => {
+                        flipped.append(&mut line);
+                        break;
+                    }
+                    _ => break,
+                }
+                r += dr;
+                c += dc;
+            }
+        }
+        flipped
+    }
+
+    pub fn legal_moves(&self, color: i8) -> Vec<(usize, usize)> {
+        (0..SIZE)
+            .flat_map(|r| (0..SIZE).map(move |c| (r, c)))
+            .filter(|&(r, c)| !self.flips(r, c, color).is_empty())
+            .collect()
+    }
+
+    /// Place a disc and flip; returns the flipped discs. The caller decides who moves next.
+    pub fn place(&mut self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        let flipped = self.flips(row, col, self.to_move);
+        self.cells[row][col] = self.to_move;
+        for &(r, c) in &flipped {
+            self.cells[r][c] = self.to_move;
+        }
+        flipped
+    }
+
+    /// Disc counts for player 1 and player 2
+    pub fn counts(&self) -> [usize; 2] {
+        let cells = self.cells.iter().flatten();
+        [cells.clone().filter(|&&v| v == 1).count(), cells.filter(|&&v| v == -1).count()]
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.legal_moves(1).is_empty() && self.legal_moves(-1).is_empty()
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reversi (Othello) for two seats, optionally against the server's bot.
+#[derive(Debug, Clone)]
+pub struct ReversiModel {
+    pub board: Board,
+    pub whos_turn: Player,
+    pub winner: GameWinner,
+    pub player1_name: Option<String>,
+    pub player2_name: Option<String>,
+    pub last_move: Option<(usize, usize)>,
+    pub flipped: Vec<(usize, usize)>, // Discs turned over by the last move
+    pub passed: Option<Player>, // Player whose turn was skipped for lack of moves after the last move
+    pub vs_bot: bool, // Player 2 is the server's minimax bot
+    pub bot_level: BotLevel,
+}
+
+impl ReversiModel {
+    pub fn new() -> Self {
+        Self {
+            board: Board::new(),
+            whos_turn: Player::Player1,
+            winner: GameWinner::Pending,
+            player1_name: None,
+            player2_name: None,
+            last_move: None,
+            flipped: Vec::new(),
+            passed: None,
+            vs_bot: false,
+            bot_level: BotLevel::Medium,
+        }
+    }
+
+    /// Fresh board with the same bot settings
+    pub fn reset(&mut self) {
+        *self = Self { vs_bot: self.vs_bot, bot_level: self.bot_level, ..Self::new() };
+    }
+
+    pub fn current_player_name(&self) -> Option<&str> {
+        match self.whos_turn {
+            Player::Player1 => self.player1_name.as_deref(),
+            Player::Player2 => self.player2_name.as_deref(),
+        }
+    }
+
+    pub fn winner_name(&self) -> Option<&str> {
+        match self.winner {
+            GameWinner::Player1 => self.player1_name.as_deref(),
+            GameWinner::Player2 => self.player2_name.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Name of the player skipped after the last move, if any
+    pub fn passed_name(&self) -> Option<&str> {
+        match self.passed {
+            Some(Player::Player1) => self.player1_name.as_deref(),
+            Some(Player::Player2) => self.player2_name.as_deref(),
+            None => None,
+        }
+    }
+
+    /// Squares the player to move may play
+    pub fn legal_moves(&self) -> Vec<(usize, usize)> {
+        if self.winner != GameWinner::Pending {
+            return Vec::new();
+        }
+        self.board.legal_moves(self.board.to_move)
+    }
+
+    /// Play (row, col) for the player whose turn it is. A player left without a move is passed
+    /// automatically; the game ends when neither side can move.
+    pub fn play(&mut self, row: usize, col: usize) -> Result<(), &'static str> {
+        if self.winner != GameWinner::Pending {
+            return Err("game_over");
+        }
+        if row >= SIZE || col >= SIZE || self.board.flips(row, col, self.board.to_move).is_empty() {
+            return Err("illegal_move");
+        }
+
+        self.flipped = self.board.place(row, col);
+        self.last_move = Some((row, col));
+        self.passed = None;
+
+        let next = -self.board.to_move;
+        if !self.board.legal_moves(next).is_empty() {
+            self.board.to_move = next;
+        } else if !self.board.legal_moves(self.board.to_move).is_empty() {
+            self.passed = Some(if next == 1 { Player::Player1 } else { Player::Player2 });
+        } else {
+            let [p1, p2] = self.board.counts();
+            self.winner = match p1.cmp(&p2) {
+                std::cmp::Ordering::Greater => GameWinner::Player1,
+                std::cmp::Ordering::Less => GameWinner::Player2,
+                std::cmp::Ordering::Equal => GameWinner::Tie,
+            };
+        }
+        self.whos_turn = if self.board.to_move == 1 { Player::Player1 } else { Player::Player2 };
+        Ok(())
+    }
+}
+
+/// The bot plays player 2 and reports the squares it played. It may move again after the
+/// human passes; the model passes for it when it has nothing to play.
+impl BotPlayer for ReversiModel {
+    type Position = Board;
+    type Played = (usize, usize);
+
+    fn bot_search(&self) -> Option<(Board, u32)> {
+        if !self.vs_bot || self.winner != GameWinner::Pending || self.whos_turn != Player::Player2 {
+            return None;
+        }
+        Some((self.board, self.bot_level.depth()))
+    }
+
+    fn play_bot_move(&mut self, mv: Option<(usize, usize)>) -> Option<(usize, usize)> {
+        let (row, col) = mv?;
+        self.play(row, col).ok()?;
+        Some((row, col))
+    }
+}
+
+impl Default for ReversiModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TwoSeats for ReversiModel {
+    fn seat_names(&self) -> [&Option<String>; 2] {
+        [&self.player1_name, &self.player2_name]
+    }
+
+    fn seat_names_mut(&mut self) -> [&mut Option<String>; 2] {
+        [&mut self.player1_name, &mut self.player2_name]
+    }
+}
+
+/// A move is a square, or None to pass when the side to move has nothing to play
+impl MinimaxGame for Board {
+    type Move = Option<(usize, usize)>;
+
+    fn legal_moves(&self) -> Vec<Self::Move> {
+        let moves = Board::legal_moves(self, self.to_move);
+        if moves.is_empty() { vec![None] } else { moves.into_iter().map(Some).collect() }
+    }
+
+    fn apply(&mut self, mv: Self::Move) {
+        if let Some((row, col)) = mv {
+            self.place(row, col);
+        }
+        self.to_move = -self.to_move;
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.is_over()
+    }
+
+    fn evaluate(&self) -> i32 {
+        let [p1, p2] = self.counts();
+        let mine = if self.to_move == 1 { p1 as i32 - p2 as i32 } else { p2 as i32 - p1 as i32 };
+        if self.is_over() {
+            return match mine.signum() {
+                1 => WIN_SCORE,
+                -1 => -WIN_SCORE,
+                _ => 0,
+            };
+        }
+        let mut score = 0;
+        for (r, row) in self.cells.iter().enumerate() {
+            for (c, &v) in row.iter().enumerate() {
+                score += WEIGHTS[r][c] * (v * self.to_move) as i32;
+            }
+        }
+        let mobility = Board::legal_moves(self, self.to_move).len() as i32
+            - Board::legal_moves(self, -self.to_move).len() as i32;
+        score + 5 * mobility
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::minimax;
+
+    #[test]
+    fn opening_moves_flip_and_count() {
+        let mut game = ReversiModel::new();
+        assert_eq!(game.legal_moves(), vec![(2, 3), (3, 2), (4, 5), (5, 4)]);
+        assert_eq!(game.play(0, 0), Err("illegal_move"));
+
+        game.play(2, 3).unwrap();
+        assert_eq!(game.flipped, vec![(3, 3)]);
+        assert_eq!(game.board.counts(), [4, 1]);
+        assert_eq!(game.whos_turn, Player::Player2);
+    }
+
+    #[test]
+    fn player_without_moves_is_passed_and_the_bot_takes_the_corner() {
+        let mut game = ReversiModel::new();
+        let mut cells = [[0; SIZE]; SIZE];
+        // After black takes A2, white's only disc can't outflank anything; black moves again
+        cells[0][0] = 1;
+        cells[0][1] = -1;
+        cells[7][7] = 1;
+        cells[6][6] = -1;
+        game.board = Board { cells, to_move: 1 };
+        game.play(0, 2).unwrap();
+        assert_eq!(game.passed, Some(Player::Player2));
+        assert_eq!(game.whos_turn, Player::Player1);
+        assert_eq!(game.legal_moves(), vec![(5, 5)]);
+
+        // Bot (white) can take the H8 corner or a plain edge square; it picks the corner
+        let mut cells = [[0; SIZE]; SIZE];
+        cells[7][6] = 1;
+        cells[7][5] = -1;
+        cells[5][7] = 1;
+        cells[4][7] = -1;
+        cells[2][2] = 1;
+        cells[2][3] = 1;
+        cells[2][4] = -1;
+        game.board = Board { cells, to_move: -1 };
+        game.whos_turn = Player::Player2;
+        game.vs_bot = true;
+        game.bot_level = BotLevel::Easy;
+        let played = minimax::play_bot_turns(&mut game);
+        assert_eq!(played.first(), Some(&(7, 7)));
+    }
+}
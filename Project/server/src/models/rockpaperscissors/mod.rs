@@ -1 +1,3 @@
 pub mod model;
+pub mod rules;
+pub mod tournament;
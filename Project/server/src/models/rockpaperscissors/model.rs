@@ -1,28 +1,17 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum RpsChoice {
-    Rock,
-    Paper,
-    Scissors,
-}
+use std::time::{Duration, Instant};
 
-impl RpsChoice {
-    pub fn from_str(choice: &str) -> Option<Self> {
-        match choice.trim().to_lowercase().as_str() {
-            "rock" | "r" => Some(Self::Rock),
-            "paper" | "p" => Some(Self::Paper),
-            "scissors" | "s" => Some(Self::Scissors),
-            _ => None,
-        }
-    }
+use sha2::{Digest, Sha256};
 
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::Rock => "rock",
-            Self::Paper => "paper",
-            Self::Scissors => "scissors",
-        }
-    }
-}
+use crate::models::{
+    rockpaperscissors::rules::{RpsChoice, RuleSet},
+    seats::TwoSeats,
+    series::Series,
+};
+
+/// Time to reveal once both commitments are in; whoever hasn't revealed by then forfeits the round
+pub const REVEAL_TIMEOUT: Duration = Duration::from_secs(30);
+/// Shortest nonce accepted in a reveal. Short nonces make a commitment easy to brute-force.
+pub const MIN_NONCE_LEN: usize = 8;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RpsRoundResult {
@@ -45,6 +34,12 @@ pub struct RockPaperScissorsModel {
     pub player1_choice: Option<RpsChoice>,
     pub player2_choice: Option<RpsChoice>,
     pub winner: RpsRoundResult,
+    pub series: Series, // Score across rounds in a best-of-N series
+    pub rules: RuleSet, // Weapons and "beats" graph chosen for the room
+    pub commit_reveal: bool, // Players commit to sha256("<choice>:<nonce>") before revealing
+    pub commitments: [Option<String>; 2], // Hex digests, by seat
+    pub nonces: [Option<String>; 2], // Nonces from verified reveals, by seat
+    pub reveal_deadline: Option<Instant>, // Set once both players have committed
 }
 
 impl RockPaperScissorsModel {
@@ -55,13 +50,39 @@ impl RockPaperScissorsModel {
             player1_choice: None,
             player2_choice: None,
             winner: RpsRoundResult::Pending,
+            series: Series::default(),
+            rules: RuleSet::default(),
+            commit_reveal: false,
+            commitments: [None, None],
+            nonces: [None, None],
+            reveal_deadline: None,
         }
     }
 
+    /// Clear players, choices and score for a new series, keeping the room's series length and rule set
+    pub fn new_series(&mut self) {
+        let series = Series::new(self.series.best_of);
+        let rules = std::mem::take(&mut self.rules);
+        let commit_reveal = self.commit_reveal;
+        *self = Self { series, rules, commit_reveal, ..Self::new() };
+    }
+
+    /// Start the next round of the series. Returns false once the series is decided.
+    pub fn next_game(&mut self) -> bool {
+        if !self.series.next_game() {
+            return false;
+        }
+        self.reset_round();
+        true
+    }
+
     pub fn reset_round(&mut self) {
         self.player1_choice = None;
         self.player2_choice = None;
         self.winner = RpsRoundResult::Pending;
+        self.commitments = [None, None];
+        self.nonces = [None, None];
+        self.reveal_deadline = None;
     }
 
     pub fn both_players_joined(&self) -> bool {
@@ -73,9 +94,12 @@ impl RockPaperScissorsModel {
     }
 
     pub fn submit_choice(&mut self, player_name: &str, choice: RpsChoice) -> Result<(), &'static str> {
-        if self.winner != RpsRoundResult::Pending && self.both_choices_made() {
-            // Automatically start the next round if both players already finished
-            self.reset_round();
+        if self.winner != RpsRoundResult::Pending {
+            // Keep the finished round on display until the players move on to the next one
+            return Err("round_complete");
+        }
+        if self.commit_reveal {
+            return Err("commitment_required");
         }
 
         match self.player_slot(player_name) {
@@ -91,31 +115,124 @@ impl RockPaperScissorsModel {
         }
     }
 
+    /// Commit-reveal mode: store a player's commitment (hex sha256 of "<choice>:<nonce>").
+    /// The second commitment starts the reveal clock.
+    pub fn submit_commitment(&mut self, player_name: &str, commitment: &str, now: Instant) -> Result<(), &'static str> {
+        if self.winner != RpsRoundResult::Pending {
+            return Err("round_complete");
+        }
+        if !self.commit_reveal {
+            return Err("commit_reveal_disabled");
+        }
+        let seat = self.seat_of(player_name).ok_or("unknown_player")?;
+        if self.commitments[seat].is_some() {
+            return Err("already_committed");
+        }
+
+        let commitment = commitment.trim().to_lowercase();
+        if commitment.len() != 64 || !commitment.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("invalid_commitment");
+        }
+        self.commitments[seat] = Some(commitment);
+        if self.both_committed() {
+            self.reveal_deadline = Some(now + REVEAL_TIMEOUT);
+        }
+        Ok(())
+    }
+
+    pub fn both_committed(&self) -> bool {
+        self.commitments.iter().all(|c| c.is_some())
+    }
+
+    /// Commit-reveal mode: once both commitments are in, check the reveal against the
+    /// player's commitment and lock in the choice.
+    pub fn reveal(&mut self, player_name: &str, choice: &str, nonce: &str) -> Result<(), &'static str> {
+        if self.winner != RpsRoundResult::Pending {
+            return Err("round_complete");
+        }
+        let seat = self.seat_of(player_name).ok_or("unknown_player")?;
+        if !self.both_committed() {
+            return Err("waiting_for_commitments");
+        }
+        if self.nonces[seat].is_some() {
+            return Err("already_revealed");
+        }
+        if nonce.chars().count() < MIN_NONCE_LEN {
+            return Err("nonce_too_short");
+        }
+        if self.commitments[seat].as_deref() != Some(commitment_hash(choice, nonce).as_str()) {
+            return Err("commitment_mismatch");
+        }
+        let parsed = self.rules.parse_choice(choice).ok_or("invalid_choice")?;
+
+        match seat {
+            0 => self.player1_choice = Some(parsed),
+            _ => self.player2_choice = Some(parsed),
+        }
+        self.nonces[seat] = Some(nonce.to_string());
+        Ok(())
+    }
+
+    /// Decide the round once both choices are in and add it to the series score.
     pub fn resolve_round(&mut self) -> RpsRoundResult {
-        if !self.both_choices_made() {
-            self.winner = RpsRoundResult::Pending;
+        if !self.both_choices_made() || self.winner != RpsRoundResult::Pending {
             return self.winner;
         }
 
-        use RpsChoice::*;
-
         let p1 = self.player1_choice.unwrap();
         let p2 = self.player2_choice.unwrap();
 
         self.winner = if p1 == p2 {
             RpsRoundResult::Tie
-        } else if (p1 == Rock && p2 == Scissors)
-            || (p1 == Paper && p2 == Rock)
-            || (p1 == Scissors && p2 == Paper)
-        {
+        } else if self.rules.beats(p1, p2) {
             RpsRoundResult::Player1
         } else {
             RpsRoundResult::Player2
         };
 
+        match self.winner {
+            RpsRoundResult::Player1 => self.series.record(Some(0)),
+            RpsRoundResult::Player2 => self.series.record(Some(1)),
+            _ => self.series.record(None),
+        }
+
         self.winner
     }
 
+    /// Once the reveal deadline has passed, the player who revealed wins the round. If neither
+    /// revealed it counts as a tie. Returns true if this decided the round.
+    pub fn forfeit_unrevealed(&mut self, now: Instant) -> bool {
+        if self.winner != RpsRoundResult::Pending || self.reveal_deadline.is_none_or(|deadline| now < deadline) {
+            return false;
+        }
+        self.winner = match (self.nonces[0].is_some(), self.nonces[1].is_some()) {
+            (true, false) => RpsRoundResult::Player1,
+            (false, true) => RpsRoundResult::Player2,
+            _ => RpsRoundResult::Tie,
+        };
+        match self.winner {
+            RpsRoundResult::Player1 => self.series.record(Some(0)),
+            RpsRoundResult::Player2 => self.series.record(Some(1)),
+            _ => self.series.record(None),
+        }
+        true
+    }
+
+    /// The round ended at the reveal deadline rather than on two reveals
+    pub fn decided_by_forfeit(&self) -> bool {
+        self.winner != RpsRoundResult::Pending && !self.both_choices_made()
+    }
+
+    /// "Spock vaporizes Rock" for a decided round
+    pub fn round_description(&self) -> Option<String> {
+        let (p1, p2) = (self.player1_choice?, self.player2_choice?);
+        match self.winner {
+            RpsRoundResult::Player1 => Some(self.rules.describe(p1, p2)),
+            RpsRoundResult::Player2 => Some(self.rules.describe(p2, p1)),
+            _ => None,
+        }
+    }
+
     pub fn winner_name(&self) -> Option<&str> {
         match self.winner {
             RpsRoundResult::Player1 => self.player1_name.as_deref(),
@@ -134,3 +251,63 @@ impl RockPaperScissorsModel {
         }
     }
 }
+
+impl TwoSeats for RockPaperScissorsModel {
+    fn seat_names(&self) -> [&Option<String>; 2] {
+        [&self.player1_name, &self.player2_name]
+    }
+
+    fn seat_names_mut(&mut self) -> [&mut Option<String>; 2] {
+        [&mut self.player1_name, &mut self.player2_name]
+    }
+}
+
+/// Hex sha256 of "<choice>:<nonce>", the value clients commit to
+pub fn commitment_hash(choice: &str, nonce: &str) -> String {
+    hex::encode(Sha256::digest(format!("{}:{}", choice, nonce)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_reveal_checks_the_hash_before_accepting_a_choice() {
+        let mut game = RockPaperScissorsModel::new();
+        game.commit_reveal = true;
+        game.player1_name = Some("Ada".to_string());
+        game.player2_name = Some("Alan".to_string());
+        let now = Instant::now();
+
+        game.submit_commitment("Ada", &commitment_hash("rock", "nonce-one"), now).unwrap();
+        assert_eq!(game.reveal("Ada", "rock", "nonce-one"), Err("waiting_for_commitments"));
+        game.submit_commitment("Alan", &commitment_hash("scissors", "nonce-two"), now).unwrap();
+
+        assert_eq!(game.reveal("Alan", "paper", "nonce-two"), Err("commitment_mismatch"));
+        game.reveal("Ada", "rock", "nonce-one").unwrap();
+        game.reveal("Alan", "scissors", "nonce-two").unwrap();
+        game.resolve_round();
+        assert_eq!(game.winner, RpsRoundResult::Player1);
+    }
+
+    #[test]
+    fn missing_the_reveal_deadline_forfeits_the_round() {
+        let mut game = RockPaperScissorsModel::new();
+        game.commit_reveal = true;
+        game.player1_name = Some("Ada".to_string());
+        game.player2_name = Some("Alan".to_string());
+        let now = Instant::now();
+
+        game.submit_commitment("Ada", &commitment_hash("rock", "n1"), now).unwrap();
+        game.submit_commitment("Alan", &commitment_hash("paper", "nonce-two"), now).unwrap();
+        assert_eq!(game.reveal("Ada", "rock", "n1"), Err("nonce_too_short"));
+        game.reveal("Alan", "paper", "nonce-two").unwrap();
+
+        assert!(!game.forfeit_unrevealed(now + REVEAL_TIMEOUT - Duration::from_secs(1)));
+        assert!(game.forfeit_unrevealed(now + REVEAL_TIMEOUT));
+        assert_eq!(game.winner, RpsRoundResult::Player2);
+        assert_eq!(game.series.wins, [0, 1]);
+        assert!(game.decided_by_forfeit());
+        assert!(!game.forfeit_unrevealed(now + REVEAL_TIMEOUT));
+    }
+}
@@ -0,0 +1,243 @@
+// Rule sets for Rock-Paper-Scissors and its bigger cousins, defined as data.
+//
+// A rule set is a "beats" graph over an odd number of weapons where every weapon
+// beats exactly half of the others. Win messages ("Spock vaporizes Rock") come
+// from the verb stored on each edge.
+
+use std::collections::HashMap;
+
+/// Most edges a custom rule set may list (RPS-15 needs 105)
+pub const MAX_EDGES: usize = 128;
+/// Most distinct weapons a custom rule set may name
+pub const MAX_WEAPONS: usize = 25;
+/// Longest weapon name, in characters
+pub const MAX_WEAPON_NAME: usize = 24;
+
+/// (winner, verb, loser)
+pub type RuleEdge<'a> = (&'a str, &'a str, &'a str);
+
+pub const CLASSIC: &[RuleEdge] = &[
+    ("rock", "crushes", "scissors"),
+    ("paper", "covers", "rock"),
+    ("scissors", "cuts", "paper"),
+];
+
+pub const RPSLS: &[RuleEdge] = &[
+    ("rock", "crushes", "scissors"),
+    ("paper", "covers", "rock"),
+    ("scissors", "cuts", "paper"),
+    ("rock", "crushes", "lizard"),
+    ("lizard", "poisons", "spock"),
+    ("spock", "smashes", "scissors"),
+    ("scissors", "decapitates", "lizard"),
+    ("lizard", "eats", "paper"),
+    ("paper", "disproves", "spock"),
+    ("spock", "vaporizes", "rock"),
+];
+
+/// RPS-7: each weapon beats the three that follow it (wrapping around)
+pub const RPS7: &[&str] = &["rock", "fire", "scissors", "sponge", "paper", "air", "water"];
+
+/// RPS-15: each weapon beats the seven that follow it (wrapping around)
+pub const RPS15: &[&str] = &[
+    "rock", "fire", "scissors", "snake", "human", "tree", "wolf", "sponge",
+    "paper", "air", "water", "dragon", "devil", "lightning", "gun",
+];
+
+/// Index of a weapon in its rule set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpsChoice(pub usize);
+
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    pub name: String,
+    pub choices: Vec<String>,
+    beats: Vec<Vec<Option<String>>>, // beats[a][b] = verb if a beats b
+}
+
+impl RuleSet {
+    /// Build a rule set from (winner, verb, loser) edges. Weapons are listed in order of first appearance.
+    pub fn from_edges(name: &str, edges: &[RuleEdge]) -> Result<Self, String> {
+        // Custom sets come straight from room options, so bound them before allocating the table
+        if edges.len() > MAX_EDGES {
+            return Err(format!("rule set has {} edges, at most {} allowed", edges.len(), MAX_EDGES));
+        }
+
+        let mut choices: Vec<String> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut resolved = Vec::with_capacity(edges.len());
+        for (winner, verb, loser) in edges {
+            let mut ends = [0; 2];
+            for (end, weapon) in ends.iter_mut().zip([winner, loser]) {
+                let weapon = weapon.trim().to_lowercase();
+                if weapon.is_empty() || weapon.chars().count() > MAX_WEAPON_NAME {
+                    return Err(format!("weapon names must be 1-{} characters", MAX_WEAPON_NAME));
+                }
+                *end = match index.get(&weapon) {
+                    Some(&idx) => idx,
+                    None => {
+                        if choices.len() == MAX_WEAPONS {
+                            return Err(format!("rule set has more than {} weapons", MAX_WEAPONS));
+                        }
+                        index.insert(weapon.clone(), choices.len());
+                        choices.push(weapon);
+                        choices.len() - 1
+                    }
+                };
+            }
+            resolved.push((ends[0], verb.trim().to_string(), ends[1]));
+        }
+
+        let mut rules = Self {
+            name: name.to_string(),
+            beats: vec![vec![None; choices.len()]; choices.len()],
+            choices,
+        };
+        for (w, verb, l) in resolved {
+            rules.beats[w][l] = Some(verb);
+        }
+
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    /// Build a rule set where each weapon beats the next half of the list, wrapping around.
+    pub fn cyclic(name: &str, weapons: &[&str]) -> Result<Self, String> {
+        let n = weapons.len();
+        let edges: Vec<RuleEdge> = (0..n)
+            .flat_map(|i| (1..=n / 2).map(move |k| (weapons[i], "defeats", weapons[(i + k) % n])))
+            .collect();
+        Self::from_edges(name, &edges)
+    }
+
+    /// One of the bundled rule sets: "classic", "rpsls", "rps7", "rps15"
+    pub fn by_name(name: &str) -> Option<Self> {
+        let rules = match name.trim().to_lowercase().as_str() {
+            "classic" | "rps" => Self::from_edges("classic", CLASSIC),
+            "rpsls" => Self::from_edges("rpsls", RPSLS),
+            "rps7" => Self::cyclic("rps7", RPS7),
+            "rps15" => Self::cyclic("rps15", RPS15),
+            _ => return None,
+        };
+        rules.ok()
+    }
+
+    /// Every weapon must beat exactly half of the others and every pair must have exactly one winner.
+    fn validate(&self) -> Result<(), String> {
+        let n = self.choices.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(format!("rule set needs an odd number (3+) of weapons, got {}", n));
+        }
+        for a in 0..n {
+            if self.beats[a][a].is_some() {
+                return Err(format!("{} cannot beat itself", self.choices[a]));
+            }
+            for b in (a + 1)..n {
+                if self.beats[a][b].is_some() == self.beats[b][a].is_some() {
+                    return Err(format!(
+                        "exactly one of {} and {} must beat the other",
+                        self.choices[a], self.choices[b]
+                    ));
+                }
+            }
+            let wins = self.beats[a].iter().filter(|v| v.is_some()).count();
+            if wins != n / 2 {
+                return Err(format!("{} beats {} weapons, expected {}", self.choices[a], wins, n / 2));
+            }
+        }
+        Ok(())
+    }
+
+    fn index_of(&self, weapon: &str) -> Option<usize> {
+        let weapon = weapon.trim().to_lowercase();
+        self.choices.iter().position(|c| *c == weapon)
+    }
+
+    /// Accepts the full weapon name or its first letter when that letter is unambiguous.
+    pub fn parse_choice(&self, choice: &str) -> Option<RpsChoice> {
+        if let Some(idx) = self.index_of(choice) {
+            return Some(RpsChoice(idx));
+        }
+
+        let choice = choice.trim().to_lowercase();
+        if choice.chars().count() != 1 {
+            return None;
+        }
+        let mut matches = self.choices.iter().enumerate().filter(|(_, c)| c.starts_with(&choice));
+        match (matches.next(), matches.next()) {
+            (Some((idx, _)), None) => Some(RpsChoice(idx)),
+            _ => None,
+        }
+    }
+
+    pub fn name_of(&self, choice: RpsChoice) -> &str {
+        &self.choices[choice.0]
+    }
+
+    pub fn beats(&self, a: RpsChoice, b: RpsChoice) -> bool {
+        self.beats[a.0][b.0].is_some()
+    }
+
+    /// "Spock vaporizes Rock"
+    pub fn describe(&self, winner: RpsChoice, loser: RpsChoice) -> String {
+        let verb = self.beats[winner.0][loser.0].as_deref().unwrap_or("beats");
+        format!("{} {} {}", capitalize(self.name_of(winner)), verb, capitalize(self.name_of(loser)))
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::from_edges("classic", CLASSIC).unwrap()
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_rule_sets_are_valid() {
+        for name in ["classic", "rpsls", "rps7", "rps15"] {
+            assert!(RuleSet::by_name(name).is_some(), "{} failed validation", name);
+        }
+    }
+
+    #[test]
+    fn rpsls_messages_come_from_edges() {
+        let rules = RuleSet::by_name("rpsls").unwrap();
+        let spock = rules.parse_choice("Spock").unwrap();
+        let rock = rules.parse_choice("rock").unwrap();
+        assert!(rules.beats(spock, rock));
+        assert_eq!(rules.describe(spock, rock), "Spock vaporizes Rock");
+        // "s" is ambiguous between scissors and spock
+        assert_eq!(rules.parse_choice("s"), None);
+    }
+
+    #[test]
+    fn rejects_unbalanced_graph() {
+        let edges = [("a", "x", "b"), ("a", "x", "c"), ("b", "x", "c")];
+        assert!(RuleSet::from_edges("bad", &edges).is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_rule_sets() {
+        let edges: Vec<RuleEdge> = (0..=MAX_EDGES).map(|_| ("a", "x", "b")).collect();
+        assert!(RuleSet::from_edges("big", &edges).unwrap_err().contains("edges"));
+
+        let names: Vec<String> = (0..=MAX_WEAPONS).map(|i| format!("w{}", i)).collect();
+        let edges: Vec<RuleEdge> = names.windows(2).map(|w| (w[0].as_str(), "x", w[1].as_str())).collect();
+        assert!(RuleSet::from_edges("wide", &edges).unwrap_err().contains("weapons"));
+
+        let long = "x".repeat(MAX_WEAPON_NAME + 1);
+        assert!(RuleSet::from_edges("long", &[(long.as_str(), "x", "b")]).is_err());
+        assert!(RuleSet::from_edges("blank", &[(" ", "x", "b")]).is_err());
+    }
+}
@@ -0,0 +1,343 @@
+use std::time::Instant;
+
+use rand::seq::SliceRandom;
+
+use crate::models::{
+    rockpaperscissors::{
+        model::{RockPaperScissorsModel, RpsRoundResult},
+        rules::RuleSet,
+    },
+    series::Series,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketFormat {
+    SingleElimination,
+    RoundRobin,
+}
+
+impl BracketFormat {
+    pub fn from_str(format: &str) -> Option<Self> {
+        match format.trim().to_lowercase().as_str() {
+            "single_elimination" | "knockout" => Some(Self::SingleElimination),
+            "round_robin" => Some(Self::RoundRobin),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SingleElimination => "single_elimination",
+            Self::RoundRobin => "round_robin",
+        }
+    }
+}
+
+/// One pairing in the bracket. Each match is its own RPS series; a bye has no second player.
+#[derive(Debug, Clone)]
+pub struct TournamentMatch {
+    pub game: RockPaperScissorsModel,
+    pub bye: bool,
+}
+
+impl TournamentMatch {
+    fn new(player1: &str, player2: Option<&str>, tournament: &TournamentModel) -> Self {
+        let mut game = RockPaperScissorsModel::new();
+        game.series = Series::new(tournament.best_of);
+        game.rules = tournament.rules.clone();
+        game.commit_reveal = tournament.commit_reveal;
+        game.player1_name = Some(player1.to_string());
+        game.player2_name = player2.map(|p| p.to_string());
+        Self { bye: player2.is_none(), game }
+    }
+
+    pub fn winner(&self) -> Option<&String> {
+        if self.bye {
+            return self.game.player1_name.as_ref();
+        }
+        match self.game.series.winner {
+            Some(0) => self.game.player1_name.as_ref(),
+            Some(1) => self.game.player2_name.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn is_decided(&self) -> bool {
+        self.winner().is_some()
+    }
+
+    pub fn involves(&self, player: &str) -> bool {
+        self.game.player1_name.as_deref() == Some(player)
+            || self.game.player2_name.as_deref() == Some(player)
+    }
+}
+
+/// Multi-player RPS: everyone seated in the room is paired into a bracket of RPS series.
+#[derive(Debug, Clone)]
+pub struct TournamentModel {
+    pub format: BracketFormat,
+    pub best_of: u32,
+    pub rules: RuleSet,
+    pub commit_reveal: bool, // Copied into every match
+    pub players: Vec<String>,
+    pub rounds: Vec<Vec<TournamentMatch>>, // single elimination grows a round at a time
+    pub current_round: usize,
+    pub champion: Option<String>,
+}
+
+impl TournamentModel {
+    pub fn new() -> Self {
+        Self {
+            format: BracketFormat::SingleElimination,
+            best_of: 1,
+            rules: RuleSet::default(),
+            commit_reveal: false,
+            players: Vec::new(),
+            rounds: Vec::new(),
+            current_round: 0,
+            champion: None,
+        }
+    }
+
+    /// Clear the bracket, keeping format, series length, rule set and commit-reveal mode
+    pub fn reset(&mut self) {
+        self.players.clear();
+        self.rounds.clear();
+        self.current_round = 0;
+        self.champion = None;
+    }
+
+    /// Seed the players (random order) and build the first round, or the full round-robin schedule.
+    pub fn start(&mut self, players: &[String]) {
+        self.reset();
+        self.players = players.to_vec();
+        self.players.shuffle(&mut rand::rng());
+
+        match self.format {
+            BracketFormat::SingleElimination => {
+                let first_round = self.first_round();
+                self.rounds.push(first_round);
+            }
+            BracketFormat::RoundRobin => self.rounds = self.round_robin_schedule(),
+        }
+        self.advance_if_round_done();
+    }
+
+    /// Pads the bracket to the next power of two, so every bye falls in the first round and each
+    /// player gets at most one. Byes alternate with real matches to keep bye players apart in round two.
+    fn first_round(&self) -> Vec<TournamentMatch> {
+        let bye_count = self.players.len().next_power_of_two() - self.players.len();
+        let (bye_players, paired) = self.players.split_at(bye_count);
+        let mut byes = bye_players.iter().map(|p| TournamentMatch::new(p, None, self));
+        let mut matches = self.pair_up(paired).into_iter();
+
+        let mut round = Vec::new();
+        loop {
+            match (byes.next(), matches.next()) {
+                (None, None) => return round,
+                (bye, real) => round.extend(bye.into_iter().chain(real)),
+            }
+        }
+    }
+
+    fn pair_up(&self, players: &[String]) -> Vec<TournamentMatch> {
+        players
+            .chunks(2)
+            .map(|pair| {
+                TournamentMatch::new(&pair[0], pair.get(1).map(|p| p.as_str()), self)
+            })
+            .collect()
+    }
+
+    /// Circle method: every player meets every other player once; odd counts sit out one round each.
+    fn round_robin_schedule(&self) -> Vec<Vec<TournamentMatch>> {
+        let mut ring: Vec<Option<&String>> = self.players.iter().map(Some).collect();
+        if ring.len() % 2 == 1 {
+            ring.push(None);
+        }
+        let n = ring.len();
+        let mut rounds = Vec::with_capacity(n - 1);
+
+        for _ in 0..n - 1 {
+            let mut round = Vec::new();
+            for i in 0..n / 2 {
+                match (ring[i], ring[n - 1 - i]) {
+                    (Some(a), Some(b)) => round.push(TournamentMatch::new(a, Some(b), self)),
+                    (Some(a), None) | (None, Some(a)) => round.push(TournamentMatch::new(a, None, self)),
+                    (None, None) => {}
+                }
+            }
+            rounds.push(round);
+            // Keep the first entry fixed and rotate the rest
+            ring[1..].rotate_right(1);
+        }
+        rounds
+    }
+
+    /// The live (undecided) match this player is in, if any
+    pub fn match_for_mut(&mut self, player: &str) -> Option<&mut TournamentMatch> {
+        self.rounds
+            .get_mut(self.current_round)?
+            .iter_mut()
+            .find(|m| !m.is_decided() && m.involves(player))
+    }
+
+    /// Apply a move (choice, commitment or reveal) to the player's current match. A finished
+    /// round of a still-undecided series rolls over to the next round first.
+    pub fn play(
+        &mut self,
+        player: &str,
+        make_move: impl FnOnce(&mut RockPaperScissorsModel) -> Result<(), &'static str>,
+    ) -> Result<(), &'static str> {
+        if self.champion.is_some() {
+            return Err("tournament_over");
+        }
+        let Some(m) = self.match_for_mut(player) else {
+            return Err("no_active_match");
+        };
+
+        if m.game.winner != RpsRoundResult::Pending {
+            m.game.next_game();
+        }
+        make_move(&mut m.game)?;
+        if m.game.both_choices_made() {
+            m.game.resolve_round();
+        }
+
+        self.advance_if_round_done();
+        Ok(())
+    }
+
+    /// Forfeit every live match whose reveal deadline has passed. Returns true if any was decided.
+    pub fn forfeit_unrevealed(&mut self, now: Instant) -> bool {
+        let Some(round) = self.rounds.get_mut(self.current_round) else { return false };
+        let mut forfeited = false;
+        for m in round.iter_mut().filter(|m| !m.bye) {
+            forfeited |= m.game.forfeit_unrevealed(now);
+        }
+        self.advance_if_round_done();
+        forfeited
+    }
+
+    /// Move to the next round (or crown a champion) once every match in the current round is decided.
+    fn advance_if_round_done(&mut self) {
+        while let Some(round) = self.rounds.get(self.current_round) {
+            if !round.iter().all(|m| m.is_decided()) {
+                return;
+            }
+
+            match self.format {
+                BracketFormat::SingleElimination => {
+                    let winners: Vec<String> = round.iter().filter_map(|m| m.winner().cloned()).collect();
+                    if winners.len() <= 1 {
+                        self.champion = winners.into_iter().next();
+                        return;
+                    }
+                    let next_round = self.pair_up(&winners);
+                    self.rounds.push(next_round);
+                    self.current_round += 1;
+                }
+                BracketFormat::RoundRobin => {
+                    if self.current_round + 1 >= self.rounds.len() {
+                        self.champion = self.standings().first().map(|(name, _, _)| name.clone());
+                        return;
+                    }
+                    self.current_round += 1;
+                }
+            }
+        }
+    }
+
+    /// (player, match wins, game wins) sorted best first. Byes don't count as wins.
+    pub fn standings(&self) -> Vec<(String, u32, u32)> {
+        let mut table: Vec<(String, u32, u32)> = self.players.iter().map(|p| (p.clone(), 0, 0)).collect();
+
+        for m in self.rounds.iter().flatten().filter(|m| !m.bye) {
+            let names = [m.game.player1_name.as_ref(), m.game.player2_name.as_ref()];
+            for (seat, name) in names.iter().enumerate() {
+                let Some(entry) = table.iter_mut().find(|entry| Some(&entry.0) == *name) else {
+                    continue;
+                };
+                entry.2 += m.game.series.wins[seat];
+                if m.game.series.winner == Some(seat) {
+                    entry.1 += 1;
+                }
+            }
+        }
+
+        table.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)));
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("P{}", i)).collect()
+    }
+
+    /// Play every live match so that player1 wins with rock over scissors
+    fn play_round(t: &mut TournamentModel) {
+        let rock = t.rules.parse_choice("rock").unwrap();
+        let scissors = t.rules.parse_choice("scissors").unwrap();
+        let pairs: Vec<(String, String)> = t.rounds[t.current_round]
+            .iter()
+            .filter(|m| !m.is_decided())
+            .map(|m| (m.game.player1_name.clone().unwrap(), m.game.player2_name.clone().unwrap()))
+            .collect();
+        for (p1, p2) in pairs {
+            t.play(&p1, |g| g.submit_choice(&p1, rock)).unwrap();
+            t.play(&p2, |g| g.submit_choice(&p2, scissors)).unwrap();
+        }
+    }
+
+    #[test]
+    fn single_elimination_with_bye_crowns_one_champion() {
+        let mut t = TournamentModel::new();
+        t.start(&names(5));
+        while t.champion.is_none() {
+            play_round(&mut t);
+        }
+        assert_eq!(t.rounds.len(), 3);
+    }
+
+    #[test]
+    fn byes_only_in_first_round_and_at_most_one_each() {
+        for n in 2..=12 {
+            let mut t = TournamentModel::new();
+            t.start(&names(n));
+            while t.champion.is_none() {
+                play_round(&mut t);
+            }
+
+            for player in &t.players {
+                let byes = t.rounds.iter().flatten().filter(|m| m.bye && m.involves(player)).count();
+                assert!(byes <= 1, "{} got {} byes with {} players", player, byes, n);
+            }
+            assert!(t.rounds[1..].iter().flatten().all(|m| !m.bye), "late bye with {} players", n);
+
+            let last = t.rounds.last().unwrap();
+            assert_eq!(last.len(), 1);
+            let finalists = [&last[0].game.player1_name, &last[0].game.player2_name].map(|p| p.clone().unwrap());
+            for finalist in finalists {
+                let played = t.rounds.iter().flatten().any(|m| !m.bye && m.involves(&finalist));
+                assert!(played, "{} reached the final without a match", finalist);
+            }
+        }
+    }
+
+    #[test]
+    fn round_robin_pairs_everyone_once() {
+        let mut t = TournamentModel::new();
+        t.format = BracketFormat::RoundRobin;
+        t.start(&names(4));
+        assert_eq!(t.rounds.len(), 3);
+        while t.champion.is_none() {
+            play_round(&mut t);
+        }
+        let played: u32 = t.standings().iter().map(|(_, wins, _)| wins).sum();
+        assert_eq!(played, 6);
+    }
+}
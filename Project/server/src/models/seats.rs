@@ -0,0 +1,18 @@
+/// Games played by exactly two seated players, player 1 and player 2.
+/// Shared by TicTacToe, RockPaperScissors and Connect Four so seating works the same everywhere.
+pub trait TwoSeats {
+    fn seat_names(&self) -> [&Option<String>; 2];
+    fn seat_names_mut(&mut self) -> [&mut Option<String>; 2];
+
+    /// Put the first two lobby seats in player 1 / player 2, in order
+    fn seat_players(&mut self, seats: &[String]) {
+        for (slot, name) in self.seat_names_mut().into_iter().zip(seats) {
+            *slot = Some(name.clone());
+        }
+    }
+
+    /// 0 for player 1, 1 for player 2
+    fn seat_of(&self, player_name: &str) -> Option<usize> {
+        self.seat_names().iter().position(|name| name.as_deref() == Some(player_name))
+    }
+}
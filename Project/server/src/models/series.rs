@@ -0,0 +1,111 @@
+use crate::types::SeriesPayload;
+
+/// Best-of-N series between the two seated players of a two-player game.
+/// Seat 0 is player 1, seat 1 is player 2.
+#[derive(Debug, Clone)]
+pub struct Series {
+    pub best_of: u32,
+    pub wins: [u32; 2],
+    pub ties: u32,
+    pub game_number: u32, // 1-based
+    pub winner: Option<usize>, // seat that took the series
+    pub rematch_votes: Vec<String>,
+}
+
+impl Series {
+    /// `best_of` is rounded up to an odd number (1, 3, 5, 7 ...)
+    pub fn new(best_of: u32) -> Self {
+        Self {
+            best_of: best_of.max(1) | 1,
+            wins: [0, 0],
+            ties: 0,
+            game_number: 1,
+            winner: None,
+            rematch_votes: Vec::new(),
+        }
+    }
+
+    pub fn wins_needed(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some()
+    }
+
+    /// Record the result of the current game. `None` is a tie, which doesn't count toward N.
+    pub fn record(&mut self, winning_seat: Option<usize>) {
+        match winning_seat {
+            Some(seat) => {
+                self.wins[seat] += 1;
+                if self.wins[seat] >= self.wins_needed() {
+                    self.winner = Some(seat);
+                }
+            }
+            None => self.ties += 1,
+        }
+    }
+
+    /// Move on to the next game. Returns false once the series is decided.
+    pub fn next_game(&mut self) -> bool {
+        if self.is_over() {
+            return false;
+        }
+        self.game_number += 1;
+        true
+    }
+
+    /// Seat that moves first in the current game; alternates every game.
+    pub fn first_seat(&self) -> usize {
+        ((self.game_number - 1) % 2) as usize
+    }
+
+    /// Register a rematch vote. Returns true once every listed player has voted.
+    pub fn vote_rematch(&mut self, name: &str, players: &[String]) -> bool {
+        if !self.rematch_votes.iter().any(|v| v == name) {
+            self.rematch_votes.push(name.to_string());
+        }
+        players.iter().all(|p| self.rematch_votes.contains(p))
+    }
+
+    pub fn payload(&self, player1: Option<&String>, player2: Option<&String>) -> SeriesPayload {
+        let names = [player1, player2];
+        SeriesPayload {
+            best_of: self.best_of,
+            game_number: self.game_number,
+            wins: self.wins.to_vec(),
+            ties: self.ties,
+            winner: self.winner.and_then(|seat| names[seat].cloned()),
+            rematch_votes: self.rematch_votes.clone(),
+        }
+    }
+}
+
+impl Default for Series {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_of_three_ends_after_two_wins_and_ignores_ties() {
+        let mut series = Series::new(3);
+        series.record(Some(0));
+        assert!(series.next_game());
+        assert_eq!(series.first_seat(), 1);
+
+        series.record(None);
+        assert!(series.next_game());
+        series.record(Some(0));
+        assert_eq!(series.winner, Some(0));
+        assert!(!series.next_game());
+
+        let players = vec!["Ada".to_string(), "Alan".to_string()];
+        assert!(!series.vote_rematch("Ada", &players));
+        assert!(series.vote_rematch("Alan", &players));
+    }
+}
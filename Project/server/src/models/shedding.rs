@@ -0,0 +1,166 @@
+//! Turn engine shared by the hand-based card games (Uno, Crazy Eights, Go Fish):
+//! seat order and turn rotation, hidden hands and a face-down draw pile.
+
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+pub type PlayerId = String;
+
+#[derive(Clone, Debug)]
+pub struct SheddingTable<C> {
+    pub players: Vec<PlayerId>,                 // seat order (names)
+    pub current_idx: usize,                     // whose turn (index into players)
+    pub direction: i8,                          // 1 or -1
+    pub deck: Vec<C>,                           // face-down draw pile (top = last)
+    pub hands: HashMap<PlayerId, Vec<C>>,       // hidden state per player
+    pub winner: Option<PlayerId>,
+    pub started: bool,
+    pub seed: Option<u64>,                      // Fixed shuffle order; only tests set it
+}
+
+impl<C: Clone + PartialEq> SheddingTable<C> {
+    pub fn new() -> Self {
+        Self {
+            players: Vec::new(),
+            current_idx: 0,
+            direction: 1,
+            deck: Vec::new(),
+            hands: HashMap::new(),
+            winner: None,
+            started: false,
+            seed: None,
+        }
+    }
+
+    /// Clear the table for a new game, keeping the shuffle seed
+    pub fn reset(&mut self) {
+        *self = Self { seed: self.seed, ..Self::new() };
+    }
+
+    pub fn add_player(&mut self, name: &str) {
+        if !self.players.contains(&name.to_string()) {
+            self.players.push(name.to_string());
+            self.hands.entry(name.to_string()).or_default();
+        }
+    }
+
+    /// Shuffle `deck` into the draw pile and deal `hand_size` cards to every player
+    pub fn start(&mut self, mut deck: Vec<C>, hand_size: usize) {
+        match self.seed {
+            Some(seed) => deck.shuffle(&mut StdRng::seed_from_u64(seed)),
+            None => deck.shuffle(&mut rand::rng()),
+        }
+
+        self.deck = deck;
+        self.started = true;
+        self.current_idx = 0;
+        self.direction = 1;
+        self.winner = None;
+
+        for p in self.players.clone() {
+            let mut hand = Vec::with_capacity(hand_size);
+            for _ in 0..hand_size { if let Some(c) = self.deck.pop() { hand.push(c); } }
+            self.hands.insert(p, hand);
+        }
+    }
+
+    pub fn current_player(&self) -> Option<&PlayerId> {
+        self.players.get(self.current_idx)
+    }
+
+    /// True if it's `name`'s turn.
+    pub fn is_players_turn(&self, name: &str) -> bool {
+        matches!(self.current_player(), Some(p) if p == name)
+    }
+
+    pub fn hand(&self, player: &str) -> &[C] {
+        self.hands.get(player).map(|h| h.as_slice()).unwrap_or_default()
+    }
+
+    pub fn hand_is_empty(&self, player: &str) -> bool {
+        self.hand(player).is_empty()
+    }
+
+    /// Remove exactly one card equal to `target` from player's hand. Returns true if removed.
+    pub fn remove_one_card(&mut self, player: &str, target: &C) -> bool {
+        if let Some(hand) = self.hands.get_mut(player)
+            && let Some(pos) = hand.iter().position(|c| c == target)
+        {
+            hand.remove(pos);
+            return true;
+        }
+        false
+    }
+
+    pub fn public_counts(&self) -> Vec<u8> {
+        self.players.iter()
+            .map(|p| self.hands.get(p).map(|h| h.len() as u8).unwrap_or(0))
+            .collect()
+    }
+
+    /// Draw a single card into player's hand. Returns the card drawn, if any.
+    pub fn draw_one(&mut self, player: &str) -> Option<C> {
+        let hand = self.hands.get_mut(player)?;
+        let card = self.deck.pop()?;
+        hand.push(card.clone());
+        Some(card)
+    }
+
+    /// Draw up to n cards; returns number actually drawn (deck may deplete).
+    pub fn draw_n(&mut self, player: &str, n: usize) -> usize {
+        let mut k = 0;
+        for _ in 0..n {
+            if self.draw_one(player).is_some() { k += 1; } else { break; }
+        }
+        k
+    }
+
+    pub fn advance_turn(&mut self, steps: usize) {
+        let n = self.players.len();
+        if n == 0 { return; }
+        let dir = if self.direction >= 0 { 1isize } else { -1isize };
+        let mut idx = self.current_idx as isize;
+        for _ in 0..steps {
+            idx = (idx + dir).rem_euclid(n as isize);
+        }
+        self.current_idx = idx as usize;
+    }
+
+    pub fn reverse(&mut self) {
+        self.direction *= -1;
+    }
+}
+
+impl<C: Clone + PartialEq> Default for SheddingTable<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_deal_is_repeatable_and_turns_wrap() {
+        let deal = || {
+            let mut table = SheddingTable::new();
+            table.seed = Some(7);
+            for name in ["Ada", "Alan", "Grace"] {
+                table.add_player(name);
+            }
+            table.start((0..20).collect::<Vec<u8>>(), 5);
+            table
+        };
+
+        let mut table = deal();
+        assert_eq!(table.hand("Ada"), deal().hand("Ada"));
+        assert_eq!(table.public_counts(), vec![5, 5, 5]);
+        assert_eq!(table.deck.len(), 5);
+
+        table.reverse();
+        table.advance_turn(1);
+        assert!(table.is_players_turn("Grace"));
+    }
+}
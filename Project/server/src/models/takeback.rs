@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use crate::models::gameroom::GameType;
+use crate::types::TakebackPayload;
+
+pub const DEFAULT_TAKEBACK_LIMIT: u32 = 3;
+
+/// An open takeback request and who has agreed to it so far
+#[derive(Debug, Clone)]
+pub struct TakebackRequest {
+    pub requester: String,
+    pub accepted: Vec<String>,
+}
+
+/// Undo support for a room: a snapshot of the game before each action of the current game.
+/// Undoing needs every other seated player to accept.
+#[derive(Debug)]
+pub struct Takebacks {
+    pub enabled: bool, // Off in ranked rooms
+    pub limit: u32, // Takebacks per player per game
+    history: Vec<(String, GameType)>, // (player who acted, game before the action)
+    pub used: HashMap<String, u32>,
+    pub pending: Option<TakebackRequest>,
+}
+
+impl Takebacks {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            limit: DEFAULT_TAKEBACK_LIMIT,
+            history: Vec::new(),
+            used: HashMap::new(),
+            pending: None,
+        }
+    }
+
+    /// Remember the game as it was before `player`'s action. A new action cancels an open request.
+    pub fn record(&mut self, player: &str, before: GameType) {
+        if !self.enabled {
+            return;
+        }
+        self.history.push((player.to_string(), before));
+        self.pending = None;
+    }
+
+    /// Drop the undo history but keep everyone's count. Called once cards leave the deck: undoing
+    /// anything from before would put the drawer's cards back where they know to find them.
+    pub fn seal(&mut self) {
+        self.history.clear();
+        self.pending = None;
+    }
+
+    /// Forget everything for a new game
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.used.clear();
+        self.pending = None;
+    }
+
+    pub fn remaining(&self, player: &str) -> u32 {
+        self.limit.saturating_sub(self.used.get(player).copied().unwrap_or(0))
+    }
+
+    /// Ask to undo the last action, which must be the requester's own.
+    pub fn request(&mut self, player: &str, game_over: bool) -> Result<(), &'static str> {
+        if !self.enabled {
+            return Err("takebacks_disabled");
+        }
+        if game_over {
+            return Err("game_over");
+        }
+        if self.pending.is_some() {
+            return Err("takeback_pending");
+        }
+        match self.history.last() {
+            None => return Err("nothing_to_undo"),
+            Some((mover, _)) if mover != player => return Err("not_your_move"),
+            Some(_) => {}
+        }
+        if self.remaining(player) == 0 {
+            return Err("takeback_limit_reached");
+        }
+
+        self.pending = Some(TakebackRequest { requester: player.to_string(), accepted: Vec::new() });
+        Ok(())
+    }
+
+    /// Accept or decline the open request. Returns the game to restore once every
+    /// other seated player has accepted.
+    pub fn respond(&mut self, player: &str, accept: bool, seats: &[String]) -> Result<Option<GameType>, &'static str> {
+        let Some(request) = self.pending.as_mut() else {
+            return Err("no_takeback_pending");
+        };
+        if request.requester == player {
+            return Err("own_request");
+        }
+        if !accept {
+            self.pending = None;
+            return Ok(None);
+        }
+
+        if !request.accepted.iter().any(|p| p == player) {
+            request.accepted.push(player.to_string());
+        }
+        let requester = request.requester.clone();
+        let everyone_agreed = seats.iter().filter(|s| **s != requester).all(|s| request.accepted.contains(s));
+        if !everyone_agreed {
+            return Ok(None);
+        }
+
+        self.pending = None;
+        *self.used.entry(requester).or_insert(0) += 1;
+        Ok(self.history.pop().map(|(_, before)| before))
+    }
+
+    pub fn payload(&self) -> TakebackPayload {
+        TakebackPayload {
+            enabled: self.enabled,
+            limit: self.limit,
+            used: self.used.clone(),
+            requested_by: self.pending.as_ref().map(|r| r.requester.clone()),
+            accepted_by: self.pending.as_ref().map(|r| r.accepted.clone()).unwrap_or_default(),
+            error: None,
+        }
+    }
+}
+
+impl Default for Takebacks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tictactoe::model::TicTacToeModel;
+
+    #[test]
+    fn takeback_needs_every_opponent_and_respects_the_limit() {
+        let seats = vec!["Ada".to_string(), "Alan".to_string(), "Grace".to_string()];
+        let mut takebacks = Takebacks::new();
+        takebacks.limit = 1;
+
+        assert_eq!(takebacks.request("Ada", false), Err("nothing_to_undo"));
+        takebacks.record("Ada", GameType::TicTacToe(TicTacToeModel::new()));
+        assert_eq!(takebacks.request("Alan", false), Err("not_your_move"));
+        takebacks.request("Ada", false).unwrap();
+
+        assert!(takebacks.respond("Alan", true, &seats).unwrap().is_none());
+        assert!(takebacks.respond("Grace", true, &seats).unwrap().is_some());
+
+        takebacks.record("Ada", GameType::TicTacToe(TicTacToeModel::new()));
+        assert_eq!(takebacks.request("Ada", false), Err("takeback_limit_reached"));
+    }
+}
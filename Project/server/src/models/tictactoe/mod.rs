@@ -1 +1 @@
-pub mod model;
\ No newline at end of file
+pub mod model;pub mod ultimate;
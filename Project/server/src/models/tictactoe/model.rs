@@ -1,3 +1,8 @@
+use crate::models::{seats::TwoSeats, series::Series};
+
+pub const MIN_BOARD_SIZE: usize = 3;
+pub const MAX_BOARD_SIZE: usize = 26; // Rows are lettered A-Z
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Player {
     Player1,
@@ -12,35 +17,144 @@ pub enum GameWinner {
     Tie,
 }
 
+/// Rule variants; all play on the configured board except Order and Chaos (always 6x6, five in a row)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TicTacToeVariant {
+    Standard,
+    Misere, // Completing a line loses
+    Wild, // Either player may place X or O; completing a line of either wins
+    OrderAndChaos, // Either mark; Order (player 1) wants five in a row, Chaos (player 2) wants a full board without one
+}
+
+impl TicTacToeVariant {
+    pub fn from_str(variant: &str) -> Option<Self> {
+        match variant.trim().to_lowercase().as_str() {
+            "standard" | "classic" => Some(Self::Standard),
+            "misere" | "misère" => Some(Self::Misere),
+            "wild" => Some(Self::Wild),
+            "order_and_chaos" => Some(Self::OrderAndChaos),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Misere => "misere",
+            Self::Wild => "wild",
+            Self::OrderAndChaos => "order_and_chaos",
+        }
+    }
+
+    /// Whether the mover chooses X or O instead of always placing their own mark
+    pub fn free_marks(&self) -> bool {
+        matches!(self, Self::Wild | Self::OrderAndChaos)
+    }
+}
+
+/// One placed mark, in play order
+#[derive(Debug, Clone, PartialEq)]
+pub struct TicTacToeMove {
+    pub player: Player,
+    pub row: usize,
+    pub col: usize,
+    pub mark: i8,
+    pub turn: usize, // 1-based
+}
+
 #[derive(Debug, Clone)]
 pub struct TicTacToeModel {
-    pub board: [[i8; 3]; 3],
+    pub board: Vec<Vec<i8>>, // rows x cols; 1 = player 1, -1 = player 2, 0 = empty
+    pub rows: usize,
+    pub cols: usize,
+    pub win_length: usize, // Marks in a row needed to win (3 classic, 5 Gomoku)
+    pub moves: Vec<TicTacToeMove>, // This game's moves; its length is the number of occupied cells
+    pub winning_line: Vec<(usize, usize)>, // Cells of the completed line once the game is won
+    pub variant: TicTacToeVariant,
     pub whos_turn: Player,
     pub winner: GameWinner,
     pub player1_name: Option<String>, // ✅ ADDED: Track player 1's actual name
     pub player2_name: Option<String>, // ✅ ADDED: Track player 2's actual name
+    pub series: Series, // Score across games in a best-of-N series
 }
 
 impl TicTacToeModel {
     pub fn new() -> Self {
         Self {
-            board: [[0; 3]; 3],
+            board: vec![vec![0; 3]; 3],
+            rows: 3,
+            cols: 3,
+            win_length: 3,
+            moves: Vec::new(),
+            winning_line: Vec::new(),
+            variant: TicTacToeVariant::Standard,
             whos_turn: Player::Player1,
             winner: GameWinner::Pending,
             player1_name: None, // ✅ ADDED
             player2_name: None, // ✅ ADDED
+            series: Series::default(),
         }
     }
 
-pub fn assign_player(&mut self, player_name: String) -> Option<Player> {
-        if self.player1_name.is_none() {
-            self.player1_name = Some(player_name);
-            Some(Player::Player1)
-        } else if self.player2_name.is_none() {
-            self.player2_name = Some(player_name);
-            Some(Player::Player2)
-        } else {
-            None // Room is full
+    /// Set an m,n,k board: `rows` x `cols`, `win_length` in a row wins. Clears the board.
+    pub fn configure(&mut self, rows: usize, cols: usize, win_length: usize) -> Result<(), String> {
+        let size = MIN_BOARD_SIZE..=MAX_BOARD_SIZE;
+        if !size.contains(&rows) || !size.contains(&cols) {
+            return Err(format!("board must be between {0}x{0} and {1}x{1}", MIN_BOARD_SIZE, MAX_BOARD_SIZE));
+        }
+        if win_length < MIN_BOARD_SIZE || win_length > rows.max(cols) {
+            return Err(format!("win length must be between {} and {}", MIN_BOARD_SIZE, rows.max(cols)));
+        }
+        self.rows = rows;
+        self.cols = cols;
+        self.win_length = win_length;
+        self.clear_board();
+        Ok(())
+    }
+
+    /// Switch rule variant. Order and Chaos always plays five in a row on 6x6.
+    pub fn set_variant(&mut self, variant: TicTacToeVariant) {
+        self.variant = variant;
+        if variant == TicTacToeVariant::OrderAndChaos {
+            self.rows = 6;
+            self.cols = 6;
+            self.win_length = 5;
+            self.clear_board();
+        }
+    }
+
+    /// Fresh series with the same length, board settings and variant
+    pub fn new_series(&mut self) {
+        let (rows, cols, win_length, variant) = (self.rows, self.cols, self.win_length, self.variant);
+        let series = Series::new(self.series.best_of);
+        *self = Self { rows, cols, win_length, variant, series, ..Self::new() };
+        self.clear_board();
+    }
+
+    fn clear_board(&mut self) {
+        self.board = vec![vec![0; self.cols]; self.rows];
+        self.moves.clear();
+        self.winning_line.clear();
+    }
+
+    /// Clear the board for the next game of the series; the first move alternates each game.
+    pub fn next_game(&mut self) -> bool {
+        if !self.series.next_game() {
+            return false;
+        }
+        self.clear_board();
+        self.winner = GameWinner::Pending;
+        self.whos_turn = if self.series.first_seat() == 0 { Player::Player1 } else { Player::Player2 };
+        true
+    }
+
+    /// Add the finished game to the series score
+    pub fn record_result(&mut self) {
+        match self.winner {
+            GameWinner::Player1 => self.series.record(Some(0)),
+            GameWinner::Player2 => self.series.record(Some(1)),
+            GameWinner::Tie => self.series.record(None),
+            GameWinner::Pending => {}
         }
     }
 
@@ -73,15 +187,48 @@ pub fn assign_player(&mut self, player_name: String) -> Option<Player> {
     }
 
 
+    /// Parse a cell like "A1" or "o15" on this board
+    pub fn parse_cell(&self, choice: &str) -> Option<(usize, usize)> {
+        parse_coordinate(choice, self.rows, self.cols)
+    }
+
     // Validate that the move is allowed
     pub fn validate_choice(&self, row: usize, col: usize) -> bool {
-        self.board[row][col] == 0
+        row < self.rows && col < self.cols && self.board[row][col] == 0
     }
 
-    // Place mark on the board
-    pub fn mark_spot(&mut self, row: usize, col: usize) {
-        let val = if self.whos_turn == Player::Player1 { 1 } else { -1 };
-        self.board[row][col] = val;
+    /// Mark of the player whose turn it is (1 = X, -1 = O)
+    pub fn own_mark(&self) -> i8 {
+        if self.whos_turn == Player::Player1 { 1 } else { -1 }
+    }
+
+    /// Mark to place for a move. Wild and Order and Chaos need an explicit "X" or "O";
+    /// the other variants always place the mover's own mark.
+    pub fn resolve_mark(&self, mark: Option<&str>) -> Result<i8, &'static str> {
+        let requested = match mark.map(|m| m.trim().to_uppercase()) {
+            None => None,
+            Some(m) if m == "X" => Some(1),
+            Some(m) if m == "O" => Some(-1),
+            Some(_) => return Err("invalid_mark"),
+        };
+        match (self.variant.free_marks(), requested) {
+            (true, Some(mark)) => Ok(mark),
+            (true, None) => Err("mark_required"),
+            (false, Some(mark)) if mark != self.own_mark() => Err("invalid_mark"),
+            (false, _) => Ok(self.own_mark()),
+        }
+    }
+
+    // Place mark on the board and record the move for `whos_turn`
+    pub fn mark_spot(&mut self, row: usize, col: usize, mark: i8) {
+        self.board[row][col] = mark;
+        self.moves.push(TicTacToeMove {
+            player: self.whos_turn.clone(),
+            row,
+            col,
+            mark,
+            turn: self.moves.len() + 1,
+        });
     }
 
     // Switch to next turn
@@ -92,35 +239,154 @@ pub fn assign_player(&mut self, player_name: String) -> Option<Player> {
         };
     }
 
-    // Check for winner or tie
-    pub fn check_winner(&mut self) {
-        let lines = [
-            // rows
-            [(0, 0), (0, 1), (0, 2)],
-            [(1, 0), (1, 1), (1, 2)],
-            [(2, 0), (2, 1), (2, 2)],
-            // columns
-            [(0, 0), (1, 0), (2, 0)],
-            [(0, 1), (1, 1), (2, 1)],
-            [(0, 2), (1, 2), (2, 2)],
-            // diagonals
-            [(0, 0), (1, 1), (2, 2)],
-            [(0, 2), (1, 1), (2, 0)],
-        ];
-
-        for line in lines {
-            let sum: i8 = line.iter().map(|&(r, c)| self.board[r][c]).sum();
-            if sum == 3 {
-                self.winner = GameWinner::Player1;
-                return;
-            } else if sum == -3 {
-                self.winner = GameWinner::Player2;
-                return;
+    /// Check for a winner through the last move (made by `whos_turn`), or the end of a full board.
+    /// Only the four lines through (row, col) can have changed, so only those are scanned.
+    pub fn check_winner(&mut self, row: usize, col: usize) {
+        let mark = self.board[row][col];
+        if mark != 0 {
+            for (dr, dc) in [(0, 1), (1, 0), (1, 1), (1, -1)] {
+                let back = self.count_run(row, col, -dr, -dc, mark);
+                let run = back + 1 + self.count_run(row, col, dr, dc, mark);
+                // Order needs exactly five; six in a row is an overline and doesn't count
+                let completes = match self.variant {
+                    TicTacToeVariant::OrderAndChaos => run == self.win_length,
+                    _ => run >= self.win_length,
+                };
+                if completes {
+                    let (start_r, start_c) = (row as isize - back as isize * dr, col as isize - back as isize * dc);
+                    self.winning_line = (0..run as isize)
+                        .map(|i| ((start_r + i * dr) as usize, (start_c + i * dc) as usize))
+                        .collect();
+                    break;
+                }
             }
         }
+        let completed_line = !self.winning_line.is_empty();
+        let (mover, opponent) = match self.whos_turn {
+            Player::Player1 => (GameWinner::Player1, GameWinner::Player2),
+            Player::Player2 => (GameWinner::Player2, GameWinner::Player1),
+        };
+
+        if completed_line {
+            self.winner = match self.variant {
+                TicTacToeVariant::Standard => {
+                    if mark == 1 { GameWinner::Player1 } else { GameWinner::Player2 }
+                }
+                TicTacToeVariant::Misere => opponent,
+                TicTacToeVariant::Wild => mover,
+                TicTacToeVariant::OrderAndChaos => GameWinner::Player1,
+            };
+        } else if self.moves.len() == self.rows * self.cols {
+            self.winner = match self.variant {
+                TicTacToeVariant::OrderAndChaos => GameWinner::Player2,
+                _ => GameWinner::Tie,
+            };
+        }
+    }
+
+    /// Consecutive `mark`s starting next to (row, col) in direction (dr, dc)
+    fn count_run(&self, row: usize, col: usize, dr: isize, dc: isize, mark: i8) -> usize {
+        let mut count = 0;
+        let (mut r, mut c) = (row as isize + dr, col as isize + dc);
+        while r >= 0 && c >= 0 && (r as usize) < self.rows && (c as usize) < self.cols
+            && self.board[r as usize][c as usize] == mark
+        {
+            count += 1;
+            r += dr;
+            c += dc;
+        }
+        count
+    }
+}
+
+impl TwoSeats for TicTacToeModel {
+    fn seat_names(&self) -> [&Option<String>; 2] {
+        [&self.player1_name, &self.player2_name]
+    }
+
+    fn seat_names_mut(&mut self) -> [&mut Option<String>; 2] {
+        [&mut self.player1_name, &mut self.player2_name]
+    }
+}
+
+/// Name of a cell: (0, 0) -> "A1"
+pub fn format_coordinate(row: usize, col: usize) -> String {
+    format!("{}{}", (b'A' + row as u8) as char, col + 1)
+}
+
+/// Parse a cell like "A1" or "o15" on a `rows` x `cols` grid: letter = row (A is the top row),
+/// number = column (1-based)
+pub fn parse_coordinate(choice: &str, rows: usize, cols: usize) -> Option<(usize, usize)> {
+    let choice = choice.trim().to_uppercase();
+    let mut chars = choice.chars();
+    let letter = chars.next().filter(|c| c.is_ascii_uppercase())?;
+    let col: usize = chars.as_str().parse().ok()?;
+
+    let row = (letter as u8 - b'A') as usize;
+    if row >= rows || col == 0 || col > cols {
+        return None;
+    }
+    Some((row, col - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gomoku_needs_five_in_a_row() {
+        let mut game = TicTacToeModel::new();
+        game.configure(15, 15, 5).unwrap();
+        assert_eq!(game.parse_cell("O15"), Some((14, 14)));
+        assert_eq!(game.parse_cell("P1"), None);
+
+        // Player 1 builds a diagonal, filling the middle last
+        for cell in ["C3", "D4", "F6", "G7"] {
+            let (r, c) = game.parse_cell(cell).unwrap();
+            game.mark_spot(r, c, 1);
+            game.check_winner(r, c);
+            assert_eq!(game.winner, GameWinner::Pending);
+        }
+        let (r, c) = game.parse_cell("E5").unwrap();
+        game.mark_spot(r, c, 1);
+        game.check_winner(r, c);
+        assert_eq!(game.winner, GameWinner::Player1);
+        assert_eq!(game.winning_line, vec![(2, 2), (3, 3), (4, 4), (5, 5), (6, 6)]);
+        assert_eq!(game.moves.last().map(|m| m.turn), Some(5));
+    }
+
+    #[test]
+    fn misere_line_loses_and_order_and_chaos_is_six_by_six() {
+        let mut game = TicTacToeModel::new();
+        game.set_variant(TicTacToeVariant::Misere);
+        for col in 0..3 {
+            game.mark_spot(0, col, 1);
+        }
+        game.check_winner(0, 2);
+        assert_eq!(game.winner, GameWinner::Player2);
+
+        let mut game = TicTacToeModel::new();
+        game.set_variant(TicTacToeVariant::OrderAndChaos);
+        assert_eq!((game.rows, game.cols, game.win_length), (6, 6, 5));
+        assert_eq!(game.resolve_mark(None), Err("mark_required"));
+        assert_eq!(game.resolve_mark(Some("o")), Ok(-1));
+    }
+
+    #[test]
+    fn order_and_chaos_overline_of_six_does_not_win() {
+        let mut game = TicTacToeModel::new();
+        game.set_variant(TicTacToeVariant::OrderAndChaos);
+        for col in [0, 1, 2, 4, 5, 3] {
+            game.mark_spot(0, col, 1);
+            game.check_winner(0, col);
+            assert_eq!(game.winner, GameWinner::Pending);
+        }
 
-        if self.board.iter().all(|row| row.iter().all(|&v| v != 0)) {
-            self.winner = GameWinner::Tie;
+        for col in [0, 1, 2, 3, 4] {
+            game.mark_spot(2, col, -1);
+            game.check_winner(2, col);
         }
+        assert_eq!(game.winner, GameWinner::Player1);
+        assert_eq!(game.winning_line.len(), 5);
     }
 }
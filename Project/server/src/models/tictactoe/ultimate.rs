@@ -0,0 +1,132 @@
+use crate::models::tictactoe::model::{GameWinner, TicTacToeModel};
+
+/// Ultimate TicTacToe: a 3x3 meta-board of 3x3 sub-boards, 9x9 cells in all.
+/// The cell you play picks the sub-board your opponent must play in next.
+#[derive(Debug, Clone)]
+pub struct UltimateTicTacToeModel {
+    pub meta: TicTacToeModel, // Players, turn, series and overall winner; a won sub-board claims its cell
+    pub boards: Vec<TicTacToeModel>, // Sub-boards in row-major order (0 = top-left, 8 = bottom-right)
+    pub forced_board: Option<usize>, // Sub-board the next move must go in; None = anywhere open
+}
+
+impl UltimateTicTacToeModel {
+    pub fn new() -> Self {
+        Self {
+            meta: TicTacToeModel::new(),
+            boards: vec![TicTacToeModel::new(); 9],
+            forced_board: None,
+        }
+    }
+
+    /// Fresh series with the same length
+    pub fn new_series(&mut self) {
+        self.meta.new_series();
+        self.clear_boards();
+    }
+
+    /// Clear every board for the next game of the series
+    pub fn next_game(&mut self) -> bool {
+        if !self.meta.next_game() {
+            return false;
+        }
+        self.clear_boards();
+        true
+    }
+
+    fn clear_boards(&mut self) {
+        self.boards = vec![TicTacToeModel::new(); 9];
+        self.forced_board = None;
+    }
+
+    /// Sub-board index and the cell inside it for a position on the 9x9 grid
+    fn locate(row: usize, col: usize) -> (usize, usize, usize) {
+        ((row / 3) * 3 + col / 3, row % 3, col % 3)
+    }
+
+    /// Play for the player whose turn it is at (row, col) on the 9x9 grid.
+    pub fn play(&mut self, row: usize, col: usize) -> Result<(), &'static str> {
+        if self.meta.winner != GameWinner::Pending {
+            return Err("game_over");
+        }
+        let (board_idx, sub_row, sub_col) = Self::locate(row, col);
+        if self.forced_board.is_some_and(|forced| forced != board_idx) {
+            return Err("wrong_board");
+        }
+
+        let board = &mut self.boards[board_idx];
+        if board.winner != GameWinner::Pending {
+            return Err("board_closed");
+        }
+        if !board.validate_choice(sub_row, sub_col) {
+            return Err("invalid_move");
+        }
+        board.whos_turn = self.meta.whos_turn.clone();
+        board.mark_spot(sub_row, sub_col, self.meta.own_mark());
+        board.check_winner(sub_row, sub_col);
+
+        // A won sub-board claims its cell on the meta-board for the mover
+        if matches!(board.winner, GameWinner::Player1 | GameWinner::Player2) {
+            let (meta_row, meta_col) = (board_idx / 3, board_idx % 3);
+            self.meta.mark_spot(meta_row, meta_col, self.meta.own_mark());
+            self.meta.check_winner(meta_row, meta_col);
+        }
+        if self.meta.winner == GameWinner::Pending && self.boards.iter().all(|b| b.winner != GameWinner::Pending) {
+            self.meta.winner = GameWinner::Tie;
+        }
+
+        // The opponent is sent to the sub-board matching the cell just played, unless it is closed
+        let target = sub_row * 3 + sub_col;
+        self.forced_board = (self.boards[target].winner == GameWinner::Pending).then_some(target);
+
+        if self.meta.winner == GameWinner::Pending {
+            self.meta.next_turn();
+        } else {
+            self.meta.record_result();
+        }
+        Ok(())
+    }
+
+    /// All 81 cells as a 9x9 grid (1 = player 1, -1 = player 2, 0 = empty)
+    pub fn full_board(&self) -> Vec<Vec<i8>> {
+        (0..9)
+            .map(|row| {
+                (0..9)
+                    .map(|col| {
+                        let (board_idx, sub_row, sub_col) = Self::locate(row, col);
+                        self.boards[board_idx].board[sub_row][sub_col]
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_sends_opponent_to_matching_board() {
+        let mut game = UltimateTicTacToeModel::new();
+        // Centre cell of the top-left board sends the opponent to the centre board
+        game.play(1, 1).unwrap();
+        assert_eq!(game.forced_board, Some(4));
+        assert_eq!(game.play(0, 0), Err("wrong_board"));
+        game.play(3, 3).unwrap();
+        assert_eq!(game.forced_board, Some(0));
+        assert_eq!(game.full_board()[3][3], -1);
+    }
+
+    #[test]
+    fn winning_a_sub_board_claims_the_meta_cell() {
+        let mut game = UltimateTicTacToeModel::new();
+        // Player 1 takes the top row of board 0; player 2 keeps sending them back there
+        for (row, col) in [(0, 1), (0, 3), (0, 2), (0, 6), (0, 0)] {
+            game.play(row, col).unwrap();
+        }
+        assert_eq!(game.boards[0].winner, GameWinner::Player1);
+        assert_eq!(game.meta.board[0][0], 1);
+        // The last move points at board 0, which is closed, so player 2 may play anywhere
+        assert_eq!(game.forced_board, None);
+    }
+}
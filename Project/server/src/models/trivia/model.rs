@@ -0,0 +1,331 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+
+/// Folder the server loads question packs from, relative to where it runs.
+/// Set `TRIVIA_PACKS_DIR` to use another one.
+pub const DEFAULT_PACKS_DIR: &str = "question_packs";
+pub const DEFAULT_PACK: &str = "general";
+pub const DEFAULT_QUESTION_SECS: u64 = 20;
+pub const MAX_QUESTION_SECS: u64 = 120;
+/// How long the answer and leaderboard stay up before the next question
+pub const REVEAL_SECS: u64 = 5;
+
+/// Points for a right answer, plus up to as much again for answering quickly
+const BASE_POINTS: u32 = 500;
+const SPEED_POINTS: u32 = 500;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TriviaQuestion {
+    pub prompt: String,
+    pub choices: Vec<String>,
+    pub answer: usize, // Index into `choices`
+    #[serde(default)]
+    pub seconds: Option<u64>, // Overrides the room's time limit
+}
+
+/// A question pack file: `<pack>.toml` or `<pack>.json` in the packs folder
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct QuestionPack {
+    pub name: String,
+    pub questions: Vec<TriviaQuestion>,
+}
+
+impl QuestionPack {
+    pub fn parse_toml(text: &str) -> Result<Self, String> {
+        toml::from_str::<Self>(text).map_err(|e| e.to_string())?.validated()
+    }
+
+    pub fn parse_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str::<Self>(text).map_err(|e| e.to_string())?.validated()
+    }
+
+    fn validated(self) -> Result<Self, String> {
+        if self.questions.is_empty() {
+            return Err("pack has no questions".into());
+        }
+        if let Some(i) = self.questions.iter().position(|q| q.choices.len() < 2 || q.answer >= q.choices.len()) {
+            return Err(format!("question {} needs at least two choices and an answer among them", i + 1));
+        }
+        Ok(self)
+    }
+
+    /// Load pack `id` from the packs folder, trying TOML then JSON
+    pub fn load(id: &str) -> Result<Self, String> {
+        Self::load_from(&packs_dir(), id)
+    }
+
+    pub fn load_from(dir: &Path, id: &str) -> Result<Self, String> {
+        // Pack ids are bare file names; anything else could walk out of the folder
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(format!("invalid pack name '{}'", id));
+        }
+        let toml_path = dir.join(format!("{}.toml", id));
+        if let Ok(text) = std::fs::read_to_string(&toml_path) {
+            return Self::parse_toml(&text).map_err(|e| format!("{}: {}", toml_path.display(), e));
+        }
+        let json_path = dir.join(format!("{}.json", id));
+        match std::fs::read_to_string(&json_path) {
+            Ok(text) => Self::parse_json(&text).map_err(|e| format!("{}: {}", json_path.display(), e)),
+            Err(_) => Err(format!("no pack named '{}' in {}", id, dir.display())),
+        }
+    }
+}
+
+pub fn packs_dir() -> PathBuf {
+    std::env::var("TRIVIA_PACKS_DIR").map(PathBuf::from).unwrap_or_else(|_| PathBuf::from(DEFAULT_PACKS_DIR))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaPhase {
+    Waiting,
+    Question,
+    Reveal,
+    Finished,
+}
+
+impl TriviaPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TriviaPhase::Waiting => "waiting",
+            TriviaPhase::Question => "question",
+            TriviaPhase::Reveal => "reveal",
+            TriviaPhase::Finished => "finished",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriviaAnswer {
+    pub choice: usize,
+    pub elapsed: Duration, // Time from the question going out to the answer arriving
+}
+
+/// A quiz over one question pack. The server's timer task drives it through
+/// `next_question` and `reveal`; players only send answers.
+#[derive(Debug, Clone)]
+pub struct TriviaModel {
+    pub pack_id: String,
+    pub pack: Option<QuestionPack>,
+    pub question_secs: u64,
+    pub players: Vec<String>, // Seat order
+    pub scores: HashMap<String, u32>,
+    pub phase: TriviaPhase,
+    pub question_idx: Option<usize>,
+    pub asked_at: Option<Instant>,
+    pub answers: HashMap<String, TriviaAnswer>, // For the current question
+    pub last_points: HashMap<String, u32>, // Points each player earned on the revealed question
+    pub run_id: u64, // Bumped on every start and reset, so a stale timer task knows to stop
+}
+
+impl TriviaModel {
+    pub fn new() -> Self {
+        Self {
+            pack_id: DEFAULT_PACK.to_string(),
+            pack: None,
+            question_secs: DEFAULT_QUESTION_SECS,
+            players: Vec::new(),
+            scores: HashMap::new(),
+            phase: TriviaPhase::Waiting,
+            question_idx: None,
+            asked_at: None,
+            answers: HashMap::new(),
+            last_points: HashMap::new(),
+            run_id: 0,
+        }
+    }
+
+    /// Back to the lobby, keeping the pack and time limit
+    pub fn reset(&mut self) {
+        self.players.clear();
+        self.scores.clear();
+        self.phase = TriviaPhase::Waiting;
+        self.question_idx = None;
+        self.asked_at = None;
+        self.answers.clear();
+        self.last_points.clear();
+        self.run_id += 1;
+    }
+
+    /// Seat the players; the timer task then asks the first question. Returns the new run id.
+    pub fn start(&mut self, players: &[String]) -> u64 {
+        self.reset();
+        if self.pack.is_none() {
+            match QuestionPack::load(&self.pack_id) {
+                Ok(pack) => self.pack = Some(pack),
+                Err(reason) => eprintln!("[Trivia] Can't load pack: {}", reason),
+            }
+        }
+        self.players = players.to_vec();
+        self.scores = players.iter().map(|p| (p.clone(), 0)).collect();
+        self.run_id
+    }
+
+    pub fn total_questions(&self) -> usize {
+        self.pack.as_ref().map(|p| p.questions.len()).unwrap_or(0)
+    }
+
+    pub fn current_question(&self) -> Option<&TriviaQuestion> {
+        self.pack.as_ref()?.questions.get(self.question_idx?)
+    }
+
+    /// Time limit for the current question
+    pub fn time_limit(&self) -> Duration {
+        let secs = self.current_question().and_then(|q| q.seconds).unwrap_or(self.question_secs);
+        Duration::from_secs(secs.clamp(1, MAX_QUESTION_SECS))
+    }
+
+    pub fn seconds_left(&self, now: Instant) -> Option<u64> {
+        if self.phase != TriviaPhase::Question {
+            return None;
+        }
+        let elapsed = now.saturating_duration_since(self.asked_at?);
+        Some(self.time_limit().saturating_sub(elapsed).as_secs_f64().ceil() as u64)
+    }
+
+    /// Ask the next question, or finish if the pack is done. Returns false once finished.
+    pub fn next_question(&mut self, now: Instant) -> bool {
+        let next = self.question_idx.map(|i| i + 1).unwrap_or(0);
+        self.answers.clear();
+        self.last_points.clear();
+        if next >= self.total_questions() {
+            self.phase = TriviaPhase::Finished;
+            self.asked_at = None;
+            return false;
+        }
+        self.question_idx = Some(next);
+        self.asked_at = Some(now);
+        self.phase = TriviaPhase::Question;
+        true
+    }
+
+    /// Lock in `player`'s one answer to the open question
+    pub fn answer(&mut self, player: &str, choice: usize, now: Instant) -> Result<(), &'static str> {
+        if !self.players.iter().any(|p| p == player) {
+            return Err("not_playing");
+        }
+        if self.phase != TriviaPhase::Question {
+            return Err("no_open_question");
+        }
+        let (Some(question), Some(asked_at)) = (self.current_question(), self.asked_at) else {
+            return Err("no_open_question");
+        };
+        if choice >= question.choices.len() {
+            return Err("invalid_choice");
+        }
+        let elapsed = now.saturating_duration_since(asked_at);
+        if elapsed > self.time_limit() {
+            return Err("time_up");
+        }
+        if self.answers.contains_key(player) {
+            return Err("already_answered");
+        }
+        self.answers.insert(player.to_string(), TriviaAnswer { choice, elapsed });
+        Ok(())
+    }
+
+    pub fn all_answered(&self) -> bool {
+        self.players.iter().all(|p| self.answers.contains_key(p))
+    }
+
+    /// Close the question and score it: right answers earn the base points plus a bonus
+    /// that shrinks linearly to nothing at the time limit
+    pub fn reveal(&mut self) {
+        let Some(question) = self.current_question() else { return; };
+        let correct = question.answer;
+        let limit = self.time_limit().as_secs_f64();
+
+        self.last_points = self
+            .players
+            .iter()
+            .map(|p| {
+                let points = match self.answers.get(p) {
+                    Some(a) if a.choice == correct => {
+                        let left = (1.0 - a.elapsed.as_secs_f64() / limit).clamp(0.0, 1.0);
+                        BASE_POINTS + (SPEED_POINTS as f64 * left).round() as u32
+                    }
+                    _ => 0,
+                };
+                (p.clone(), points)
+            })
+            .collect();
+        for (player, points) in &self.last_points {
+            *self.scores.entry(player.clone()).or_default() += points;
+        }
+        self.phase = TriviaPhase::Reveal;
+    }
+
+    /// Players by score, best first; ties keep seat order
+    pub fn leaderboard(&self) -> Vec<(String, u32)> {
+        let mut board: Vec<(String, u32)> =
+            self.players.iter().map(|p| (p.clone(), self.scores.get(p).copied().unwrap_or(0))).collect();
+        board.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        board
+    }
+}
+
+impl Default for TriviaModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACK: &str = r#"
+        name = "Test"
+
+        [[questions]]
+        prompt = "2 + 2?"
+        choices = ["3", "4"]
+        answer = 1
+        seconds = 10
+    "#;
+
+    #[test]
+    fn packs_load_from_toml_and_json() {
+        let pack = QuestionPack::parse_toml(PACK).unwrap();
+        assert_eq!(pack.questions[0].answer, 1);
+
+        let json = r#"{ "name": "Test", "questions": [{ "prompt": "?", "choices": ["a", "b"], "answer": 0 }] }"#;
+        assert_eq!(QuestionPack::parse_json(json).unwrap().questions[0].seconds, None);
+        assert!(QuestionPack::parse_json(r#"{ "name": "Bad", "questions": [{ "prompt": "?", "choices": ["a"], "answer": 3 }] }"#).is_err());
+        assert!(QuestionPack::load_from(Path::new("."), "../secrets").is_err());
+
+        // The packs shipped with the server must load
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_PACKS_DIR);
+        assert!(QuestionPack::load_from(&dir, DEFAULT_PACK).is_ok());
+        assert!(QuestionPack::load_from(&dir, "computing").is_ok());
+    }
+
+    #[test]
+    fn one_answer_each_and_faster_right_answers_score_more() {
+        let mut game = TriviaModel::new();
+        game.pack = Some(QuestionPack::parse_toml(PACK).unwrap());
+        game.start(&["Ada".into(), "Alan".into(), "Grace".into()]);
+
+        let t0 = Instant::now();
+        assert!(game.next_question(t0));
+        assert_eq!(game.seconds_left(t0), Some(10));
+        game.answer("Ada", 1, t0 + Duration::from_secs(2)).unwrap();
+        assert_eq!(game.answer("Ada", 0, t0 + Duration::from_secs(3)), Err("already_answered"));
+        game.answer("Alan", 1, t0 + Duration::from_secs(8)).unwrap();
+        assert_eq!(game.answer("Grace", 5, t0), Err("invalid_choice"));
+        assert_eq!(game.answer("Grace", 0, t0 + Duration::from_secs(11)), Err("time_up"));
+
+        game.reveal();
+        assert_eq!(game.last_points["Ada"], 900);
+        assert_eq!(game.last_points["Alan"], 600);
+        assert_eq!(game.last_points["Grace"], 0);
+        assert_eq!(game.leaderboard()[0], ("Ada".to_string(), 900));
+
+        assert!(!game.next_question(t0));
+        assert_eq!(game.phase, TriviaPhase::Finished);
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode, Json};
+
+use crate::models::appstate::AppState;
+use crate::types::{AccountRequest, AccountResponse};
+
+/// `POST /register`: create an account and return a token for it
+pub async fn register_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AccountRequest>,
+) -> (StatusCode, Json<AccountResponse>) {
+    let name = request.player_name.trim().to_string();
+    // Hashing is deliberately slow; keep it off the async workers
+    let result = {
+        let state = state.clone();
+        let name = name.clone();
+        tokio::task::spawn_blocking(move || state.accounts.register(&name, &request.password))
+            .await
+            .unwrap_or(Err("storage_error"))
+    };
+    match &result {
+        Ok(_) => println!("[Accounts] registered {}", name),
+        Err(reason) => eprintln!("[Accounts] registration of {} rejected: {}", name, reason),
+    }
+    respond(name, result, StatusCode::CREATED)
+}
+
+/// `POST /login`: check a password and return a fresh token
+pub async fn login_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AccountRequest>,
+) -> (StatusCode, Json<AccountResponse>) {
+    let name = request.player_name.trim().to_string();
+    let result = {
+        let state = state.clone();
+        let name = name.clone();
+        tokio::task::spawn_blocking(move || state.accounts.login(&name, &request.password))
+            .await
+            .unwrap_or(Err("storage_error"))
+    };
+    if let Err(reason) = &result {
+        eprintln!("[Accounts] login as {} rejected: {}", name, reason);
+    }
+    respond(name, result, StatusCode::OK)
+}
+
+fn respond(
+    player_name: String,
+    result: Result<String, &'static str>,
+    success: StatusCode,
+) -> (StatusCode, Json<AccountResponse>) {
+    let (code, status, token) = match result {
+        Ok(token) => (success, "ok", Some(token)),
+        Err(reason) => {
+            let code = match reason {
+                "name_taken" => StatusCode::CONFLICT,
+                "invalid_credentials" => StatusCode::UNAUTHORIZED,
+                "storage_error" => StatusCode::INTERNAL_SERVER_ERROR,
+                _ => StatusCode::BAD_REQUEST,
+            };
+            (code, reason, None)
+        }
+    };
+    (code, Json(AccountResponse { status: status.to_string(), player_name, token }))
+}
@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    battleship::model::{BattleshipModel, ShipPlacement, GRID_SIZE},
+    gameroom::GameType,
+    lobby::LobbyPhase,
+    seats::TwoSeats,
+    tictactoe::model::{format_coordinate, parse_coordinate, GameWinner},
+};
+use crate::routes::gameroom_handler;
+use crate::types::{
+    BattleshipPayloadToClient,
+    BattleshipPayloadToServer,
+    BattleshipShipPayload,
+    BattleshipShotPayload,
+    ServerMessage,
+    ShipPlacementPayload,
+};
+
+/// Handles fleet placement and shots. Returns the public view; each fleet follows privately from the ws loop.
+pub async fn battleship_handler(
+    payload: BattleshipPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[Battleship] Room not found: {}", game_id);
+        return Some(build_view(&game_id, &BattleshipModel::new(), None, "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[Battleship] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_view(&game_id, &BattleshipModel::new(), None, "identity_mismatch"));
+        return None;
+    }
+
+    let phase = room.lobby.phase;
+    let GameType::Battleship(game) = &mut room.game else {
+        eprintln!("Tried to play Battleship in a non-Battleship room: {}", game_id);
+        return Some(build_view(&game_id, &BattleshipModel::new(), None, "wrong_game_type"));
+    };
+
+    if phase != LobbyPhase::InGame {
+        return Some(build_view(&game_id, game, None, "waiting_for_players"));
+    }
+    if payload.action == "request_state" {
+        return Some(build_view(&game_id, game, None, "ok"));
+    }
+
+    let Some(seat) = game.seat_of(&payload.player_name) else {
+        eprintln!("[Battleship] Unknown player: {}", payload.player_name);
+        return Some(build_view(&game_id, game, None, "unknown_player"));
+    };
+
+    let result = match payload.action.as_str() {
+        "place" => match payload.ships.as_deref().map(parse_placements) {
+            Some(Some(placements)) => game.place_fleet(seat, &placements),
+            Some(None) => Err("invalid_coordinate"),
+            None => Err("missing_ships"),
+        },
+        "fire" => {
+            if game.current_player_name() != Some(payload.player_name.as_str()) && !game.placing() {
+                Err("not_your_turn")
+            } else {
+                match payload.target.as_deref().map(|t| parse_coordinate(t, GRID_SIZE, GRID_SIZE)) {
+                    Some(Some((row, col))) => game.fire(row, col).map(|shot| {
+                        println!("[Battleship] {} fires at {}: {}", payload.player_name,
+                                 format_coordinate(row, col), if shot.hit { "hit" } else { "miss" });
+                    }),
+                    Some(None) => Err("invalid_coordinate"),
+                    None => Err("missing_target"),
+                }
+            }
+        }
+        _ => Err("unknown_action"),
+    };
+
+    match result {
+        Ok(()) => {
+            if game.winner != GameWinner::Pending {
+                println!("[Battleship] {} wins in room {}", game.winner_name().unwrap_or("?"), game_id);
+            }
+            Some(build_view(&game_id, game, None, "ok"))
+        }
+        Err(reason) => {
+            eprintln!("[Battleship] {} {} refused: {}", payload.player_name, payload.action, reason);
+            Some(build_view(&game_id, game, None, reason))
+        }
+    }
+}
+
+fn parse_placements(ships: &[ShipPlacementPayload]) -> Option<Vec<ShipPlacement>> {
+    ships
+        .iter()
+        .map(|ship| {
+            let (row, col) = parse_coordinate(&ship.start, GRID_SIZE, GRID_SIZE)?;
+            Some(ShipPlacement { kind: ship.ship.to_lowercase(), row, col, horizontal: ship.horizontal })
+        })
+        .collect()
+}
+
+/// The game as seen by `viewer`: public shots for everyone, plus the viewer's own fleet
+pub fn build_view(game_id: &str, game: &BattleshipModel, viewer: Option<&str>, status: &str) -> ServerMessage {
+    let phase = if game.winner != GameWinner::Pending {
+        "game_over"
+    } else if game.placing() {
+        "placing"
+    } else {
+        "playing"
+    };
+    let shots = |seat: usize| {
+        game.shots[seat]
+            .iter()
+            .map(|shot| BattleshipShotPayload {
+                target: format_coordinate(shot.row, shot.col),
+                hit: shot.hit,
+                sunk: shot.sunk.clone(),
+            })
+            .collect()
+    };
+    let fleet = viewer.and_then(|v| game.seat_of(v)).and_then(|seat| game.fleets[seat].as_ref()).map(|ships| {
+        ships
+            .iter()
+            .map(|ship| BattleshipShipPayload {
+                ship: ship.kind.clone(),
+                cells: ship.cells.iter().map(|&(r, c)| format_coordinate(r, c)).collect(),
+                hits: ship.hits.clone(),
+                sunk: ship.is_sunk(),
+            })
+            .collect()
+    });
+
+    ServerMessage::Battleship(BattleshipPayloadToClient {
+        game_id: game_id.to_string(),
+        status: status.to_string(),
+        phase: phase.to_string(),
+        player1: game.player1_name.clone(),
+        player2: game.player2_name.clone(),
+        placed: [game.fleets[0].is_some(), game.fleets[1].is_some()],
+        current_player: game.current_player_name().map(|s| s.to_string()),
+        winner: game.winner_name().map(|s| s.to_string()),
+        shots: [shots(0), shots(1)],
+        fleet,
+        viewer: viewer.map(|s| s.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn firing_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "battleship", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "battleship", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "action": "fire", "target": "A1" })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(battleship_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(battleship_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
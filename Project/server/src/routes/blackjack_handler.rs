@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    blackjack::model::{hand_value, BlackjackModel, BlackjackPhase},
+    gameroom::GameType,
+    lobby::LobbyPhase,
+};
+use crate::routes::gameroom_handler;
+use crate::types::{
+    BlackjackHandPayload,
+    BlackjackPayloadToClient,
+    BlackjackPayloadToServer,
+    BlackjackSeatPayload,
+    ServerMessage,
+};
+
+/// Handles Blackjack table actions. Returns the public view; private views follow from the ws loop.
+pub async fn blackjack_handler(
+    payload: BlackjackPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[Blackjack] Room not found: {}", game_id);
+        return Some(build_view(&game_id, &BlackjackModel::new(), None, "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[Blackjack] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_view(&game_id, &BlackjackModel::new(), None, "identity_mismatch"));
+        return None;
+    }
+
+    let phase = room.lobby.phase;
+    let GameType::Blackjack(game) = &mut room.game else {
+        eprintln!("Tried to play Blackjack in a non-Blackjack room: {}", game_id);
+        return Some(build_view(&game_id, &BlackjackModel::new(), None, "wrong_game_type"));
+    };
+
+    if phase != LobbyPhase::InGame {
+        return Some(build_view(&game_id, game, None, "waiting_for_players"));
+    }
+
+    let result = match payload.action.as_str() {
+        "bet" => match payload.amount {
+            Some(amount) => game.place_bet(&payload.player_name, amount),
+            None => Err("missing_amount"),
+        },
+        "hit" | "stand" | "double" | "split" => game.act(&payload.player_name, &payload.action),
+        "next_round" => game.next_round(),
+        "request_state" => Ok(()),
+        _ => Err("unknown_action"),
+    };
+
+    match result {
+        Ok(()) => {
+            println!("[Blackjack] {} -> {} in room {}", payload.player_name, payload.action, game_id);
+            if game.phase == BlackjackPhase::RoundOver {
+                for player in &game.players {
+                    println!("[Blackjack] {} net {} (chips {})", player.name, player.net, player.chips);
+                }
+            }
+            Some(build_view(&game_id, game, None, "ok"))
+        }
+        Err(reason) => {
+            eprintln!("[Blackjack] {} {} refused: {}", payload.player_name, payload.action, reason);
+            Some(build_view(&game_id, game, None, reason))
+        }
+    }
+}
+
+/// The table as seen by `viewer` (None = spectators / public broadcast)
+pub fn build_view(game_id: &str, game: &BlackjackModel, viewer: Option<&str>, status: &str) -> ServerMessage {
+    let players = game
+        .players
+        .iter()
+        .map(|player| BlackjackSeatPayload {
+            name: player.name.clone(),
+            chips: player.chips,
+            bet: player.bet,
+            hands: player
+                .hands
+                .iter()
+                .map(|hand| {
+                    let (cards, hidden) = game.hand_view(&player.name, hand, viewer);
+                    BlackjackHandPayload {
+                        cards,
+                        value: (!hidden).then(|| hand.value()),
+                        bet: hand.bet,
+                        doubled: hand.doubled,
+                        outcome: hand.outcome.map(|o| o.as_str().to_string()),
+                    }
+                })
+                .collect(),
+            net: player.net,
+        })
+        .collect();
+
+    let dealer_hidden = game.phase == BlackjackPhase::PlayerTurns;
+    ServerMessage::Blackjack(BlackjackPayloadToClient {
+        game_id: game_id.to_string(),
+        phase: game.phase.as_str().to_string(),
+        status: status.to_string(),
+        players,
+        dealer: game.dealer_view(),
+        dealer_value: (!dealer_hidden && !game.dealer.is_empty()).then(|| hand_value(&game.dealer).0),
+        current_player: game.current_player_name().map(|s| s.to_string()),
+        current_hand: game.current.map(|i| game.players[i].active_hand),
+        min_bet: game.min_bet,
+        viewer: viewer.map(|s| s.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn betting_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "blackjack", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "blackjack", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "action": "bet", "amount": 10 })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(blackjack_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(blackjack_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
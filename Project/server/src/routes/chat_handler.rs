@@ -1,18 +1,123 @@
+use axum::extract::ws::Message;
 use chrono::Local;
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
+use tokio::sync::mpsc::UnboundedSender;
 
-use crate::models::appstate::AppState;
-use crate::types::{ChatPayload, ServerMessage};
+use crate::models::{
+    appstate::AppState,
+    gameroom::GameType,
+    pictionary::model::{GuessResult, PictionaryPhase},
+};
+use crate::routes::{gameroom_handler, pictionary_handler};
+use crate::types::{ChatPayload, EchoPayload, ServerMessage};
 
 fn get_timestamp() -> String {
     Local::now().format("%I:%M %p").to_string()
 }
 
+/// Returns None when the message was refused and the sender told privately
 pub async fn chat_handler(
     payload: ChatPayload,
-    _state: &Arc<AppState>
-) -> ServerMessage {
+    state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
     let mut response = payload.clone();
     response.time = get_timestamp();
-    ServerMessage::Chat(response)
+
+    let mut rooms = state.rooms.write().await;
+    let room = rooms.get_mut(&payload.game_id);
+    // Chat (and Pictionary guesses with it) only goes to a room the connection itself joined
+    if let Some(room) = &room
+        && !room.is_member(sender, user_tx)
+    {
+        eprintln!("[Chat] {} is not in room {}", sender, payload.game_id);
+        let message = format!("identity_mismatch: not in room '{}'", payload.game_id);
+        gameroom_handler::send_direct(user_tx, &ServerMessage::Echo(EchoPayload { message }));
+        return None;
+    }
+
+    // In Pictionary the chat doubles as the guess box
+    if let Some(room) = room
+        && let GameType::Pictionary(game) = &mut room.game
+    {
+        if !game.knows_word(&payload.player_name)
+            && game.guess(&payload.player_name, &payload.chat_message, Instant::now()) == GuessResult::Correct
+        {
+            // The guess itself is never shown; everyone just learns who got it
+            println!("[Pictionary] {} guessed the word in room {}", payload.player_name, payload.game_id);
+            return Some(pictionary_handler::build_view_with_guess(
+                &payload.game_id, game, None, "correct_guess", Some(&payload.player_name),
+            ));
+        }
+        // Near misses ("apple!") contain the word too, so nothing goes out unmasked mid-turn
+        if game.phase == PictionaryPhase::Drawing {
+            response.chat_message = game.mask_word(&payload.chat_message);
+        }
+    }
+
+    Some(ServerMessage::Chat(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, started_test_room};
+
+    #[tokio::test]
+    async fn chat_during_a_drawing_turn_never_shows_the_word() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "pictionary", "room", &["alice", "bob", "carol"]).await;
+        let (word, guesser) = {
+            let mut rooms = state.rooms.write().await;
+            let GameType::Pictionary(game) = &mut rooms.get_mut("room").unwrap().game else { unreachable!() };
+            if game.phase != PictionaryPhase::Drawing {
+                game.next_turn(Instant::now());
+            }
+            let drawer = game.players.iter().find(|p| game.is_drawer(p)).unwrap();
+            let guesser = game.players.iter().find(|p| !game.is_drawer(p)).unwrap().clone();
+            (game.word_for(Some(drawer)).unwrap().to_string(), guesser)
+        };
+
+        let payload = ChatPayload {
+            game_id: "room".into(),
+            player_name: guesser.clone(),
+            chat_message: format!("{}!", word.to_uppercase()),
+            time: String::new(),
+        };
+        let guesser_tx = member_tx(&state, "room", &payload.player_name).await;
+        let Some(ServerMessage::Chat(reply)) = chat_handler(payload, &state, &guesser, &guesser_tx).await else {
+            panic!("a near miss is still a chat message");
+        };
+        assert_eq!(reply.chat_message, format!("{}!", "*".repeat(word.len())));
+    }
+
+    #[tokio::test]
+    async fn guessing_in_another_room_under_the_same_name_is_refused() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "pictionary", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "pictionary", "other", &["alice", "carol"]).await;
+        let word = {
+            let mut rooms = state.rooms.write().await;
+            let GameType::Pictionary(game) = &mut rooms.get_mut("other").unwrap().game else { unreachable!() };
+            if game.phase != PictionaryPhase::Drawing {
+                game.next_turn(Instant::now());
+            }
+            let drawer = game.players.iter().find(|p| game.is_drawer(p)).unwrap();
+            game.word_for(Some(drawer)).unwrap().to_string()
+        };
+
+        // The "alice" of the first room sends the word as the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        let payload = ChatPayload {
+            game_id: "other".into(),
+            player_name: "alice".into(),
+            chat_message: word,
+            time: String::new(),
+        };
+        assert!(chat_handler(payload, &state, "alice", &alice_tx).await.is_none());
+        let rooms = state.rooms.read().await;
+        let GameType::Pictionary(game) = &rooms["other"].game else { unreachable!() };
+        assert!(game.guessed.is_empty());
+    }
 }
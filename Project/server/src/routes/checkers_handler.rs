@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    checkers::model::{CheckersModel, SIZE},
+    gameroom::GameType,
+    lobby::LobbyPhase,
+    seats::TwoSeats,
+    tictactoe::model::{format_coordinate, parse_coordinate, GameWinner},
+};
+use crate::routes::gameroom_handler;
+use crate::types::{CheckersPayloadToClient, CheckersPayloadToServer, ServerMessage};
+
+/// Handles Checkers moves. In a bot room the bot answers once the rooms lock is released.
+pub async fn checkers_handler(
+    payload: CheckersPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[Checkers] Room not found: {}", game_id);
+        return Some(build_payload(&game_id, &CheckersModel::new(), "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[Checkers] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_payload(&game_id, &CheckersModel::new(), "identity_mismatch"));
+        return None;
+    }
+
+    let phase = room.lobby.phase;
+    let GameType::Checkers(game) = &mut room.game else {
+        eprintln!("Tried to play Checkers in a non-Checkers room: {}", game_id);
+        return Some(build_payload(&game_id, &CheckersModel::new(), "wrong_game_type"));
+    };
+
+    if phase != LobbyPhase::InGame {
+        return Some(build_payload(&game_id, game, "waiting_for_players"));
+    }
+
+    // No path: just send the latest state
+    let Some(path) = payload.path.as_ref() else {
+        return Some(build_snapshot(&game_id, game));
+    };
+
+    if game.winner != GameWinner::Pending {
+        return Some(build_snapshot(&game_id, game));
+    }
+    let Some(seat) = game.seat_of(&payload.player_name) else {
+        eprintln!("[Checkers] Unknown player: {}", payload.player_name);
+        return Some(build_payload(&game_id, game, "unknown_player"));
+    };
+    if game.current_player_name() != game.seat_names()[seat].as_deref() {
+        eprintln!("[Checkers] Not {}'s turn", payload.player_name);
+        return Some(build_payload(&game_id, game, "not_your_turn"));
+    }
+
+    let Some(squares) = path.iter().map(|s| parse_coordinate(s, SIZE, SIZE)).collect::<Option<Vec<_>>>() else {
+        return Some(build_payload(&game_id, game, "invalid_coordinate"));
+    };
+    if let Err(reason) = game.play(&squares) {
+        eprintln!("[Checkers] {} can't play {:?}: {}", payload.player_name, path, reason);
+        return Some(build_payload(&game_id, game, reason));
+    }
+    println!("[Checkers] {} played {}", payload.player_name, path.join("-"));
+
+    drop(rooms);
+
+    for mv in gameroom_handler::play_bot_turns(app_state, &game_id, checkers).await {
+        println!("[Checkers] Bot played {}", format_path(&mv.path).join("-"));
+    }
+
+    let rooms = app_state.rooms.read().await;
+    let Some(GameType::Checkers(game)) = rooms.get(&game_id).map(|room| &room.game) else {
+        return Some(build_payload(&game_id, &CheckersModel::new(), "room_not_found"));
+    };
+    match game.winner {
+        GameWinner::Tie => println!("[Checkers] Drawn by no progress in room {}", game_id),
+        _ => {
+            if let Some(name) = game.winner_name() {
+                println!("[Checkers] {} wins!", name);
+            }
+        }
+    }
+
+    Some(build_snapshot(&game_id, game))
+}
+
+fn checkers(game: &mut GameType) -> Option<&mut CheckersModel> {
+    match game {
+        GameType::Checkers(game) => Some(game),
+        _ => None,
+    }
+}
+
+fn format_path(path: &[(usize, usize)]) -> Vec<String> {
+    path.iter().map(|&(r, c)| format_coordinate(r, c)).collect()
+}
+
+/// Board, turn, status and legal moves for broadcasting
+pub fn build_snapshot(game_id: &str, game: &CheckersModel) -> ServerMessage {
+    let status = match game.winner {
+        GameWinner::Pending => "IN_PROGRESS",
+        GameWinner::Player1 => "gameover_p1",
+        GameWinner::Player2 => "gameover_p2",
+        GameWinner::Tie => "gameover_draw",
+    };
+    build_payload(game_id, game, status)
+}
+
+fn build_payload(game_id: &str, game: &CheckersModel, status: &str) -> ServerMessage {
+    ServerMessage::Checkers(CheckersPayloadToClient {
+        game_id: game_id.to_string(),
+        board: game.board.cells.iter().map(|row| row.iter().map(|&v| v as i32).collect()).collect(),
+        whos_turn: game.current_player_name().map(|s| s.to_string()),
+        status: status.to_string(),
+        winner: game.winner_name().map(|s| s.to_string()),
+        legal_moves: game.legal_moves().iter().map(|mv| format_path(&mv.path)).collect(),
+        last_move: game.last_move.as_ref().map(|mv| format_path(&mv.path)),
+        captured: game.last_move.as_ref().map(|mv| format_path(&mv.captured)).unwrap_or_default(),
+        quiet_moves: game.board.quiet_plies,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn moving_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "checkers", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "checkers", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "path": ["F2", "E3"] })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(checkers_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(checkers_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    connectfour::model::{ConnectFourModel, COLS},
+    gameroom::GameType,
+    lobby::LobbyPhase,
+    seats::TwoSeats,
+    tictactoe::model::GameWinner,
+};
+use crate::routes::gameroom_handler;
+use crate::types::{ConnectFourPayloadToClient, ConnectFourPayloadToServer, ServerMessage};
+
+/// Handles Connect Four moves. In a bot room the bot answers once the rooms lock is released.
+pub async fn connectfour_handler(
+    payload: ConnectFourPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[ConnectFour] Room not found: {}", game_id);
+        return Some(build_payload(&game_id, &ConnectFourModel::new(), "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[ConnectFour] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_payload(&game_id, &ConnectFourModel::new(), "identity_mismatch"));
+        return None;
+    }
+
+    let phase = room.lobby.phase;
+    let GameType::ConnectFour(game) = &mut room.game else {
+        eprintln!("Tried to play Connect Four in a non-Connect Four room: {}", game_id);
+        return Some(build_payload(&game_id, &ConnectFourModel::new(), "wrong_game_type"));
+    };
+
+    if phase != LobbyPhase::InGame {
+        return Some(build_payload(&game_id, game, "waiting_for_players"));
+    }
+
+    // No column: just send the latest state
+    let Some(column) = payload.column else {
+        return Some(build_snapshot(&game_id, game));
+    };
+
+    if game.winner != GameWinner::Pending {
+        return Some(build_snapshot(&game_id, game));
+    }
+    let Some(seat) = game.seat_of(&payload.player_name) else {
+        eprintln!("[ConnectFour] Unknown player: {}", payload.player_name);
+        return Some(build_payload(&game_id, game, "unknown_player"));
+    };
+    if game.current_player_name() != game.seat_names()[seat].as_deref() {
+        eprintln!("[ConnectFour] Not {}'s turn", payload.player_name);
+        return Some(build_payload(&game_id, game, "not_your_turn"));
+    }
+
+    // Columns are 1-based on the wire
+    let col = column.wrapping_sub(1);
+    if col >= COLS {
+        return Some(build_payload(&game_id, game, "invalid_column"));
+    }
+    if let Err(reason) = game.drop_disc(col) {
+        eprintln!("[ConnectFour] {} can't play column {}: {}", payload.player_name, column, reason);
+        return Some(build_payload(&game_id, game, reason));
+    }
+    println!("[ConnectFour] {} dropped a disc in column {}", payload.player_name, column);
+
+    drop(rooms);
+
+    for bot_col in gameroom_handler::play_bot_turns(app_state, &game_id, connect_four).await {
+        println!("[ConnectFour] Bot dropped a disc in column {}", bot_col + 1);
+    }
+
+    let rooms = app_state.rooms.read().await;
+    let Some(GameType::ConnectFour(game)) = rooms.get(&game_id).map(|room| &room.game) else {
+        return Some(build_payload(&game_id, &ConnectFourModel::new(), "room_not_found"));
+    };
+    if let Some(name) = game.winner_name() {
+        println!("[ConnectFour] {} wins!", name);
+    }
+
+    Some(build_snapshot(&game_id, game))
+}
+
+/// A bot that starts a game opens it straight away; everyone is sent its move
+pub async fn play_bot_opening(app_state: &Arc<AppState>, game_id: &str) {
+    if gameroom_handler::play_bot_turns(app_state, game_id, connect_four).await.is_empty() {
+        return;
+    }
+    let rooms = app_state.rooms.read().await;
+    if let Some(room) = rooms.get(game_id) {
+        println!("[ConnectFour] Bot opened the game in room {}", game_id);
+        room.broadcast(&gameroom_handler::game_snapshot(room));
+    }
+}
+
+fn connect_four(game: &mut GameType) -> Option<&mut ConnectFourModel> {
+    match game {
+        GameType::ConnectFour(game) => Some(game),
+        _ => None,
+    }
+}
+
+/// Board, turn, status, winning line and series for broadcasting
+pub fn build_snapshot(game_id: &str, game: &ConnectFourModel) -> ServerMessage {
+    let status = match game.winner {
+        GameWinner::Pending => "IN_PROGRESS",
+        GameWinner::Player1 => "gameover_p1",
+        GameWinner::Player2 => "gameover_p2",
+        GameWinner::Tie => "gameover_draw",
+    };
+    let mut msg = build_payload(game_id, game, status);
+    if let ServerMessage::ConnectFour(payload) = &mut msg {
+        payload.series = Some(game.series.payload(game.player1_name.as_ref(), game.player2_name.as_ref()));
+    }
+    msg
+}
+
+fn build_payload(game_id: &str, game: &ConnectFourModel, status: &str) -> ServerMessage {
+    ServerMessage::ConnectFour(ConnectFourPayloadToClient {
+        game_id: game_id.to_string(),
+        board: game.board.iter().map(|row| row.iter().map(|&v| v as i32).collect()).collect(),
+        whos_turn: game.current_player_name().map(|s| s.to_string()),
+        status: status.to_string(),
+        winner: game.winner_name().map(|s| s.to_string()),
+        winning_line: (!game.winning_line.is_empty())
+            .then(|| game.winning_line.iter().map(|&(r, c)| [r, c]).collect()),
+        last_move: game.last_move.map(|(r, c)| [r, c]),
+        series: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn moving_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "connectfour", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "connectfour", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "column": 4 })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(connectfour_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(connectfour_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    cards::Suit,
+    crazyeights::model::CrazyEightsModel,
+    gameroom::GameType,
+};
+use crate::routes::gameroom_handler;
+use crate::types::{CrazyEightsPayloadToClient, CrazyEightsPayloadToServer, ServerMessage};
+
+/// Handles Crazy Eights moves. Returns the public view; private hands follow from the ws loop.
+pub async fn crazyeights_handler(
+    payload: CrazyEightsPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[CrazyEights] Room not found: {}", game_id);
+        return Some(build_view(&game_id, &CrazyEightsModel::new(), None, "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[CrazyEights] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_view(&game_id, &CrazyEightsModel::new(), None, "identity_mismatch"));
+        return None;
+    }
+
+    let GameType::CrazyEights(game) = &mut room.game else {
+        eprintln!("Tried to play Crazy Eights in a non-Crazy-Eights room: {}", game_id);
+        return Some(build_view(&game_id, &CrazyEightsModel::new(), None, "wrong_game_type"));
+    };
+
+    // The lobby deals the cards once every seated player is ready
+    if !game.table.started {
+        return Some(build_view(&game_id, game, None, "waiting_for_players"));
+    }
+
+    let player = payload.player_name.as_str();
+    let result = match payload.action.as_str() {
+        "play_card" => match payload.card.as_ref() {
+            Some(card) => {
+                let suit = payload.choose_suit.as_deref().and_then(|s| s.chars().next()).and_then(Suit::from_code);
+                game.play(player, card, suit)
+            }
+            None => Err("missing_card"),
+        },
+        "draw_card" => game.draw(player).map(|_| ()),
+        "pass_turn" => game.pass(player),
+        "request_state" => Ok(()),
+        _ => Err("unknown_action"),
+    };
+
+    match result {
+        Ok(()) => {
+            println!("[CrazyEights] {} -> {} in room {}", player, payload.action, game_id);
+            if let Some(winner) = &game.table.winner {
+                println!("[CrazyEights] {} wins in room {}", winner, game_id);
+            }
+            Some(build_view(&game_id, game, None, "ok"))
+        }
+        Err(reason) => {
+            eprintln!("[CrazyEights] {} {} refused: {}", player, payload.action, reason);
+            Some(build_view(&game_id, game, None, reason))
+        }
+    }
+}
+
+/// The table as seen by `viewer`; None leaves out every hand
+pub fn build_view(game_id: &str, game: &CrazyEightsModel, viewer: Option<&str>, status: &str) -> ServerMessage {
+    ServerMessage::CrazyEights(CrazyEightsPayloadToClient {
+        game_id: game_id.to_string(),
+        status: status.to_string(),
+        players: game.table.players.clone(),
+        current_idx: game.table.current_idx,
+        top_discard: game.top().copied(),
+        chosen_suit: game.chosen_suit.map(|s| s.code().to_string()),
+        draw_pile: game.table.deck.len(),
+        public_counts: game.table.public_counts(),
+        winner: game.table.winner.clone(),
+        hand: viewer.map(|v| game.table.hand(v).to_vec()),
+        viewer: viewer.map(|v| v.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn drawing_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "crazy_eights", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "crazy_eights", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "action": "draw_card" })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(crazyeights_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(crazyeights_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
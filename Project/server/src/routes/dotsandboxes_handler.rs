@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    dotsandboxes::model::{DotsAndBoxesModel, Edge},
+    gameroom::GameType,
+};
+use crate::routes::gameroom_handler;
+use crate::types::{DotsAndBoxesEdgePayload, DotsAndBoxesPayloadToClient, DotsAndBoxesPayloadToServer, ServerMessage};
+
+/// Handles Dots and Boxes edges. A player who closes a box keeps the turn.
+pub async fn dotsandboxes_handler(
+    payload: DotsAndBoxesPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[DotsAndBoxes] Room not found: {}", game_id);
+        return Some(build_view(&game_id, &DotsAndBoxesModel::new(), "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[DotsAndBoxes] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_view(&game_id, &DotsAndBoxesModel::new(), "identity_mismatch"));
+        return None;
+    }
+
+    let GameType::DotsAndBoxes(game) = &mut room.game else {
+        eprintln!("Tried to play Dots and Boxes in a non-Dots-and-Boxes room: {}", game_id);
+        return Some(build_view(&game_id, &DotsAndBoxesModel::new(), "wrong_game_type"));
+    };
+
+    if !game.started {
+        return Some(build_view(&game_id, game, "waiting_for_players"));
+    }
+
+    // No edge: just send the latest state
+    let Some(edge) = payload.edge.as_ref() else {
+        return Some(build_view(&game_id, game, "ok"));
+    };
+    let horizontal = match edge.orientation.as_str() {
+        "h" => true,
+        "v" => false,
+        _ => return Some(build_view(&game_id, game, "invalid_edge")),
+    };
+
+    let player = payload.player_name.as_str();
+    if let Err(reason) = game.draw_edge(player, Edge { horizontal, row: edge.row, col: edge.col }) {
+        eprintln!("[DotsAndBoxes] {} can't draw {} {},{}: {}", player, edge.orientation, edge.row, edge.col, reason);
+        return Some(build_view(&game_id, game, reason));
+    }
+    println!("[DotsAndBoxes] {} drew {} {},{} and closed {} box(es)",
+             player, edge.orientation, edge.row, edge.col, game.completed.len());
+    if game.is_over() {
+        println!("[DotsAndBoxes] Game over in room {}. Winners: {:?}", game_id, game.winners());
+    }
+
+    Some(build_view(&game_id, game, "ok"))
+}
+
+fn edge_payload(edge: &Edge) -> DotsAndBoxesEdgePayload {
+    DotsAndBoxesEdgePayload {
+        orientation: if edge.horizontal { "h" } else { "v" }.to_string(),
+        row: edge.row,
+        col: edge.col,
+    }
+}
+
+/// Edges, box owners and scores for broadcasting
+pub fn build_view(game_id: &str, game: &DotsAndBoxesModel, status: &str) -> ServerMessage {
+    ServerMessage::DotsAndBoxes(DotsAndBoxesPayloadToClient {
+        game_id: game_id.to_string(),
+        status: status.to_string(),
+        rows: game.rows,
+        cols: game.cols,
+        players: game.players.clone(),
+        whos_turn: game.current_player().cloned(),
+        horizontal: game.horizontal.clone(),
+        vertical: game.vertical.clone(),
+        boxes: game
+            .owners
+            .iter()
+            .map(|row| row.iter().map(|o| o.and_then(|i| game.players.get(i).cloned())).collect())
+            .collect(),
+        scores: game.scores.clone(),
+        last_edge: game.last_edge.as_ref().map(edge_payload),
+        completed: game.completed.iter().map(|&(r, c)| [r, c]).collect(),
+        game_over: game.is_over(),
+        winners: game.winners(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn drawing_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "dots_and_boxes", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "dots_and_boxes", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "edge": { "orientation": "h", "row": 0, "col": 0 } })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(dotsandboxes_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(dotsandboxes_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::{
     mpsc::UnboundedSender,
     RwLock,
@@ -7,30 +7,84 @@ use axum::extract::ws::Message;
 use crate::models::{
     appstate::AppState,
     gameroom::{GameRoom, GameType},
-    rockpaperscissors::model::RockPaperScissorsModel,
-    tictactoe::model::TicTacToeModel,
+    battleship::model::BattleshipModel,
+    blackjack::model::BlackjackModel,
+    checkers::model::{CheckersModel, MAX_BOT_DEPTH as MAX_CHECKERS_BOT_DEPTH},
+    crazyeights::model::CrazyEightsModel,
+    dotsandboxes::model::DotsAndBoxesModel,
+    gofish::model::GoFishModel,
+    hangman::model::{HangmanMode, HangmanModel, MAX_LIVES},
+    holdem::model::HoldemModel,
+    reversi::model::{BotLevel, ReversiModel},
+    connectfour::model::{ConnectFourModel, MAX_BOT_DEPTH},
+    lobby::LobbyPhase,
+    mancala::model::{MancalaModel, MAX_BOT_DEPTH as MAX_MANCALA_BOT_DEPTH},
+    minimax::{self, BotPlayer, BOT_NAME},
+    pictionary::model::{PictionaryModel, MAX_DRAW_SECS, MAX_ROUNDS, MIN_DRAW_SECS},
+    ratings::GameResult,
+    seats::TwoSeats,
+    rockpaperscissors::{
+        model::{RockPaperScissorsModel, RpsRoundResult},
+        rules::{RuleEdge, RuleSet},
+        tournament::{BracketFormat, TournamentModel},
+    },
+    series::Series,
+    tictactoe::{
+        model::{GameWinner, TicTacToeModel, TicTacToeVariant},
+        ultimate::UltimateTicTacToeModel,
+    },
+    trivia::model::{QuestionPack, TriviaModel, MAX_QUESTION_SECS},
     uno::model::UnoModel,
-    uno::model::UnoColor,
+};
+use crate::routes::{
+    checkers_handler,
+    reversi_handler,
+    mancala_handler,
+    connectfour_handler,
+    rockpaperscissors_handler,
+    tictactoe_handler,
+    battleship_handler,
+    blackjack_handler,
+    crazyeights_handler,
+    dotsandboxes_handler,
+    gofish_handler,
+    hangman_handler,
+    trivia_handler,
+    pictionary_handler,
+    holdem_handler,
+    uno_handler,
 };
 use crate::types::{
     GameRoomPayload,
-    RockPaperScissorsPayloadToClient,
+    RoomOptions,
     ServerMessage,
-    TicTacToePayloadToClient,
-    UnoPayloadToClient,
 };
 
-/// Handles join/leave operations for game rooms.
+/// Handles join/leave/ready/rematch operations for game rooms.
 pub async fn gameroom_handler(
     payload: GameRoomPayload,
     state: &Arc<AppState>,
     user_tx: UnboundedSender<Message>,
     current_room: Arc<RwLock<Option<String>>>,
 ) -> ServerMessage {
+    // Everything but a join acts on a room this connection already joined under that name
+    if payload.action != "join" {
+        let member = state.rooms.read().await.get(&payload.game_id).is_some_and(|room| room.is_member(&payload.player_name, &user_tx));
+        if !member {
+            eprintln!("[GameRoom] {} is not in room {}", payload.player_name, payload.game_id);
+            let mut refused = payload.clone();
+            refused.action = "invalid".into();
+            return ServerMessage::GameRoom(refused);
+        }
+    }
+
     match payload.action.as_str() {
         "join" => handle_join(payload, state, user_tx, current_room).await,
         "leave" => handle_leave(payload, state, user_tx, current_room).await,
         "reset" => handle_reset(payload, state).await,
+        "ready" => handle_ready(payload, state).await,
+        "rematch" => handle_rematch(payload, state).await,
+        "takeback" | "takeback_accept" | "takeback_decline" => handle_takeback(payload, state).await,
         _ => {
             let mut invalid = payload.clone();
             invalid.action = "invalid".into();
@@ -51,20 +105,44 @@ pub async fn handle_join(
     // Match the requested game type and create the appropriate GameType
     let game_type = match payload.game.as_str() {
         "tictactoe" => GameType::TicTacToe(TicTacToeModel::new()),
+        "ultimate_tictactoe" => GameType::UltimateTicTacToe(UltimateTicTacToeModel::new()),
+        "connectfour" => GameType::ConnectFour(ConnectFourModel::new()),
         "rockpaperscissors" => GameType::RockPaperScissors(RockPaperScissorsModel::new()),
+        "rps_tournament" => GameType::RpsTournament(TournamentModel::new()),
         "uno" => GameType::Uno(UnoModel::new()),
+        "blackjack" => GameType::Blackjack(BlackjackModel::new()),
+        "crazy_eights" => GameType::CrazyEights(CrazyEightsModel::new()),
+        "go_fish" => GameType::GoFish(GoFishModel::new()),
+        "texas_holdem" => GameType::TexasHoldem(HoldemModel::new()),
+        "battleship" => GameType::Battleship(BattleshipModel::new()),
+        "checkers" => GameType::Checkers(CheckersModel::new()),
+        "reversi" => GameType::Reversi(ReversiModel::new()),
+        "mancala" => GameType::Mancala(MancalaModel::new()),
+        "dots_and_boxes" => GameType::DotsAndBoxes(DotsAndBoxesModel::new()),
+        "hangman" => GameType::Hangman(HangmanModel::new()),
+        "trivia" => GameType::Trivia(TriviaModel::new()),
+        "pictionary" => GameType::Pictionary(PictionaryModel::new()),
         other => {
             eprintln!("Unknown game type requested: {}", other);
+            payload.action = "invalid".into();
             return ServerMessage::GameRoom(payload);
         }
     };
 
     // Insert new room if it doesn't exist
     let room = rooms.entry(payload.game_id.clone())
-        .or_insert_with(|| GameRoom::new(
-            payload.game_id.clone(),
-            game_type
-        ));
+        .or_insert_with(|| {
+            let mut room = GameRoom::new(payload.game_id.clone(), game_type);
+            apply_room_options(&mut room, &payload);
+            room
+        });
+
+    // A name in the room belongs to the connection that joined with it until that connection drops
+    if let Some(tx) = room.members.get(&payload.player_name) && !tx.same_channel(&user_tx) {
+        eprintln!("[GameRoom] {} is already connected to room {}", payload.player_name, payload.game_id);
+        payload.action = "invalid".into();
+        return ServerMessage::GameRoom(payload);
+    }
 
     // Add player if not already present
     if !room.users.contains(&payload.player_name) {
@@ -73,7 +151,13 @@ pub async fn handle_join(
                  payload.player_name, payload.game_id, room.users);
     }
 
+    // Take a seat if one is free; otherwise the user spectates
+    if !room.lobby.sit(&payload.player_name) {
+        println!("[GameRoom] {} is spectating room {}", payload.player_name, payload.game_id);
+    }
+
     // Add the sender if not already present
+    room.members.insert(payload.player_name.clone(), user_tx.clone());
     if !room.txs.iter().any(|tx| tx.same_channel(&user_tx)) {
         room.txs.push(user_tx);
     }
@@ -81,117 +165,18 @@ pub async fn handle_join(
     let mut room_guard = current_room.write().await;
     *room_guard = Some(payload.game_id.clone());
 
-    // Clone the complete player list
-    let all_players = room.users.clone();
-    
-    // ✅ Build a lobby/initial snapshot to broadcast
-    // - For TicTacToe and RPS: only when exactly 2 players
-    // - For Uno: on every join (so FE always sees up-to-date lobby state and can start when players.len() >= 2)
-    let initial_game_state: Option<ServerMessage> = match &mut room.game {
-        GameType::TicTacToe(game) => {
-            if all_players.len() == 2 {
-                game.player1_name = Some(all_players[0].clone());
-                game.player2_name = Some(all_players[1].clone());
-
-                println!(
-                    "[GameRoom] TicTacToe starting! Player 1 (X): {}, Player 2 (O): {}",
-                    all_players[0], all_players[1]
-                );
-
-                Some(ServerMessage::TicTacToe(TicTacToePayloadToClient {
-                    board: Some(vec![vec![0, 0, 0], vec![0, 0, 0], vec![0, 0, 0]]),
-                    whos_turn: Some(all_players[0].clone()), // Player 1 starts
-                    status: Some("IN_PROGRESS".to_string()),
-                }))
-            } else { None }
-        }
-        GameType::RockPaperScissors(game) => {
-            if all_players.len() == 2 {
-                game.player1_name = Some(all_players[0].clone());
-                game.player2_name = Some(all_players[1].clone());
-                game.reset_round();
-
-                println!(
-                    "[GameRoom] RockPaperScissors starting! Player 1: {}, Player 2: {}",
-                    all_players[0], all_players[1]
-                );
-
-                Some(ServerMessage::RockPaperScissors(
-                    RockPaperScissorsPayloadToClient {
-                        game_id: payload.game_id.clone(),
-                        player1: Some(all_players[0].clone()),
-                        player2: Some(all_players[1].clone()),
-                        player1_choice: None,
-                        player2_choice: None,
-                        status: "waiting_for_choices".to_string(),
-                        winner: None,
-                        message: Some("Both players joined. Make your selection!".to_string()),
-                    },
-                ))
-            } else { None }
-        }
-        GameType::Uno(game) => {
-            // Register current players in the Uno model
-            for player in &all_players {
-                if !game.players.contains(player) {
-                    game.add_player(player);
-                }
-            }
+    // ✅ If a game is already running, resend its snapshot so the newcomer can render it
+    if room.lobby.phase == LobbyPhase::InGame {
+        room.broadcast(&game_snapshot(room));
+    }
+
+    fill_lobby_fields(&mut payload, room);
 
-            if game.started {
-                // If a round is already in progress, emit the current game snapshot
-                Some(ServerMessage::Uno(UnoPayloadToClient {
-                    game_id: payload.game_id.clone(),
-                    players: Some(all_players.clone()),
-                    current_idx: Some(game.current_idx as i32),
-                    direction: Some(game.direction),
-                    top_discard: game.discard_top.clone(),
-                    chosen_color: game.chosen_color.as_ref().map(|c| match c {
-                        UnoColor::Red => "Red".to_string(),
-                        UnoColor::Yellow => "Yellow".to_string(),
-                        UnoColor::Green => "Green".to_string(),
-                        UnoColor::Blue => "Blue".to_string(),
-                        UnoColor::Wild => "Wild".to_string(),
-                    }),
-                    pending_draw: Some(game.pending_draw),
-                    public_counts: Some(game.public_counts()),
-                    hand: None,
-                    winner: game.winner.clone(),
-                }))
-            } else {
-                // Otherwise, emit a lobby snapshot so FE can enable Start when players.len() >= 2
-                let counts = vec![0u8; all_players.len()];
-                Some(ServerMessage::Uno(UnoPayloadToClient {
-                    game_id: payload.game_id.clone(),
-                    players: Some(all_players.clone()),
-                    current_idx: Some(0),
-                    direction: Some(1),
-                    top_discard: None,
-                    chosen_color: None,
-                    pending_draw: Some(0),
-                    public_counts: Some(counts),
-                    hand: None,
-                    winner: None,
-                }))
-            }
-        }
-    };
-    
     drop(room_guard);
     drop(rooms);
 
-    // Add players list to response
-    payload.players = Some(all_players);
-    
-    // ✅ Broadcast initial game state if game just started
-    if let Some(game_state) = initial_game_state {
-        let rooms = state.rooms.read().await;
-        if let Some(room) = rooms.get(&payload.game_id) {
-            let serialized = serde_json::to_string(&game_state).unwrap();
-            for tx in &room.txs {
-                let _ = tx.send(Message::Text(serialized.clone().into()));
-            }
-        }
+    if let Some(room_id) = &*current_room.read().await {
+        dm_private_views(state.clone(), room_id).await;
     }
 
     ServerMessage::GameRoom(payload)
@@ -214,9 +199,20 @@ async fn handle_leave(
 
         // Remove sender
         room.txs.retain(|tx| !tx.same_channel(&user_tx));
-        
-        // ✅ Update payload with remaining players
-        payload.players = Some(room.users.clone());
+        room.members.remove(&payload.player_name);
+
+        // A seated player leaving ends the current game; spectators move up into free seats
+        if room.lobby.is_seated(&payload.player_name) {
+            if room.lobby.phase == LobbyPhase::InGame {
+                room.lobby.back_to_lobby();
+            }
+            room.lobby.stand(&payload.player_name);
+            for user in room.users.clone() {
+                room.lobby.sit(&user);
+            }
+        }
+
+        fill_lobby_fields(&mut payload, room);
 
         // If empty, drop room
         if room.users.is_empty() {
@@ -231,7 +227,8 @@ async fn handle_leave(
     ServerMessage::GameRoom(payload)
 }
 
-/// Reset game state - allows players to play again
+/// Reset game state - moves a best-of-N series on to its next game, otherwise
+/// sends the room back to the lobby so players can ready up again
 async fn handle_reset(
     mut payload: GameRoomPayload,
     state: &Arc<AppState>,
@@ -239,107 +236,1017 @@ async fn handle_reset(
     let mut rooms = state.rooms.write().await;
 
     if let Some(room) = rooms.get_mut(&payload.game_id) {
-        println!("[GameRoom] Resetting game in room {}", payload.game_id);
-        
-        // Reset the game model and broadcast new game state
-        match &mut room.game {
-            GameType::TicTacToe(model) => {
-                // Preserve player names
-                let p1 = model.player1_name.clone();
-                let p2 = model.player2_name.clone();
-                
-                // Reset the model
-                *model = TicTacToeModel::new();
-                model.player1_name = p1.clone();
-                model.player2_name = p2.clone();
-                
-                println!("[GameRoom] TicTacToe game reset. Players: {:?} vs {:?}", p1, p2);
-                
-                // ✅ Broadcast fresh game state to all players in the room
-                if let (Some(player1), Some(player2)) = (p1, p2) {
-                    let game_state = TicTacToePayloadToClient {
-                        board: Some(vec![vec![0, 0, 0], vec![0, 0, 0], vec![0, 0, 0]]),
-                        whos_turn: Some(player1.clone()), // Player 1 starts
-                        status: Some("IN_PROGRESS".to_string()),
-                    };
-                    
-                    let game_msg = ServerMessage::TicTacToe(game_state);
-                    let serialized = serde_json::to_string(&game_msg).unwrap();
-                    
-                    for tx in &room.txs {
-                        let _ = tx.send(Message::Text(serialized.clone().into()));
-                    }
+        // A finished game inside an undecided series rolls straight into the next game
+        let next_in_series = room.lobby.phase == LobbyPhase::InGame && match &mut room.game {
+            GameType::TicTacToe(model) => model.winner != GameWinner::Pending && model.next_game(),
+            GameType::UltimateTicTacToe(model) => model.meta.winner != GameWinner::Pending && model.next_game(),
+            GameType::ConnectFour(model) => model.winner != GameWinner::Pending && model.next_game(),
+            GameType::RockPaperScissors(model) => {
+                model.winner != RpsRoundResult::Pending && model.next_game()
+            }
+            GameType::RpsTournament(_)
+            | GameType::Uno(_)
+            | GameType::Blackjack(_)
+            | GameType::CrazyEights(_)
+            | GameType::GoFish(_)
+            | GameType::TexasHoldem(_)
+            | GameType::Battleship(_)
+            | GameType::Checkers(_)
+            | GameType::Reversi(_)
+            | GameType::DotsAndBoxes(_)
+            | GameType::Hangman(_)
+            | GameType::Trivia(_)
+            | GameType::Pictionary(_)
+            | GameType::Mancala(_) => false,
+        };
+
+        room.takebacks.clear();
+        room.result_recorded = false;
+        if next_in_series {
+            println!("[GameRoom] Next game of the series in room {}", payload.game_id);
+            room.broadcast(&game_snapshot(room));
+        } else {
+            println!("[GameRoom] Resetting game in room {}", payload.game_id);
+
+            match &mut room.game {
+                GameType::TicTacToe(model) => model.new_series(),
+                GameType::UltimateTicTacToe(model) => model.new_series(),
+                GameType::ConnectFour(model) => model.new_series(),
+                GameType::RockPaperScissors(model) => model.new_series(),
+                GameType::RpsTournament(model) => model.reset(),
+                GameType::Uno(model) => model.reset(),
+                GameType::Blackjack(model) => model.start(&[]),
+                GameType::CrazyEights(model) => model.reset(),
+                GameType::GoFish(model) => model.reset(),
+                GameType::TexasHoldem(model) => model.reset(),
+                GameType::Battleship(model) => model.reset(),
+                GameType::Checkers(model) => model.reset(),
+                GameType::Reversi(model) => model.reset(),
+                GameType::DotsAndBoxes(model) => model.reset(),
+                GameType::Hangman(model) => model.reset(),
+                GameType::Trivia(model) => model.reset(),
+                GameType::Pictionary(model) => model.reset(),
+                GameType::Mancala(model) => model.reset(),
+            }
+            room.lobby.back_to_lobby();
+
+            println!("[GameRoom] {} game reset. Seats: {:?}", room.game.name(), room.lobby.seats);
+        }
+
+        // ✅ Include player list and lobby state in response
+        fill_lobby_fields(&mut payload, room);
+    }
+    drop(rooms);
+
+    connectfour_handler::play_bot_opening(state, &payload.game_id).await;
+
+    ServerMessage::GameRoom(payload)
+}
+
+/// A seated player votes for a rematch after a decided series. Once both have voted a new series starts.
+async fn handle_rematch(
+    mut payload: GameRoomPayload,
+    state: &Arc<AppState>,
+) -> ServerMessage {
+    let mut rooms = state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&payload.game_id) else {
+        payload.action = "invalid".into();
+        return ServerMessage::GameRoom(payload);
+    };
+
+    let seats = room.lobby.seats.clone();
+    let seated = room.lobby.is_seated(&payload.player_name);
+
+    let series = match &mut room.game {
+        GameType::TicTacToe(model) => Some(&mut model.series),
+        GameType::UltimateTicTacToe(model) => Some(&mut model.meta.series),
+        GameType::ConnectFour(model) => Some(&mut model.series),
+        GameType::RockPaperScissors(model) => Some(&mut model.series),
+        GameType::RpsTournament(_)
+        | GameType::Uno(_)
+        | GameType::Blackjack(_)
+        | GameType::CrazyEights(_)
+        | GameType::GoFish(_)
+        | GameType::TexasHoldem(_)
+        | GameType::Battleship(_)
+        | GameType::Checkers(_)
+        | GameType::Reversi(_)
+        | GameType::DotsAndBoxes(_)
+        | GameType::Hangman(_)
+        | GameType::Trivia(_)
+        | GameType::Pictionary(_)
+        | GameType::Mancala(_) => None,
+    };
+
+    let everyone_agreed = match series {
+        Some(series) if seated && series.is_over() => series.vote_rematch(&payload.player_name, &seats),
+        _ => {
+            eprintln!("[GameRoom] Rematch not available for {} in room {}",
+                      payload.player_name, payload.game_id);
+            payload.action = "invalid".into();
+            fill_lobby_fields(&mut payload, room);
+            return ServerMessage::GameRoom(payload);
+        }
+    };
+
+    if everyone_agreed {
+        println!("[GameRoom] Rematch accepted in room {}", payload.game_id);
+        start_game(room);
+    }
+    room.broadcast(&game_snapshot(room));
+
+    fill_lobby_fields(&mut payload, room);
+    drop(rooms);
+
+    connectfour_handler::play_bot_opening(state, &payload.game_id).await;
+
+    ServerMessage::GameRoom(payload)
+}
+
+/// A seated player toggles ready. Once every seat is filled and ready, a countdown starts.
+async fn handle_ready(
+    mut payload: GameRoomPayload,
+    state: &Arc<AppState>,
+) -> ServerMessage {
+    let mut rooms = state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&payload.game_id) else {
+        payload.action = "invalid".into();
+        return ServerMessage::GameRoom(payload);
+    };
+
+    match room.lobby.toggle_ready(&payload.player_name) {
+        Ok(is_ready) => {
+            println!("[GameRoom] {} is {}ready in room {}",
+                     payload.player_name, if is_ready { "" } else { "not " }, payload.game_id);
+        }
+        Err(reason) => {
+            eprintln!("[GameRoom] {} cannot ready in room {}: {}",
+                      payload.player_name, payload.game_id, reason);
+            payload.action = "invalid".into();
+        }
+    }
+
+    if room.lobby.phase == LobbyPhase::Waiting && room.lobby.all_ready() {
+        let countdown_id = room.lobby.begin_countdown();
+        tokio::spawn(run_countdown(state.clone(), payload.game_id.clone(), countdown_id));
+    }
+
+    fill_lobby_fields(&mut payload, room);
+    ServerMessage::GameRoom(payload)
+}
+
+/// Broadcast the start countdown once per second, then start the game.
+/// Bails out if anyone unreadies or leaves while it is running.
+async fn run_countdown(state: Arc<AppState>, game_id: String, countdown_id: u64) {
+    let secs = match state.rooms.read().await.get(&game_id) {
+        Some(room) => room.lobby.countdown_secs,
+        None => return,
+    };
+
+    for remaining in (1..=secs).rev() {
+        {
+            let rooms = state.rooms.read().await;
+            let Some(room) = rooms.get(&game_id) else { return; };
+            if !room.lobby.countdown_active(countdown_id) {
+                return;
+            }
+            room.broadcast(&lobby_message(room, "countdown", Some(remaining)));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    {
+        let mut rooms = state.rooms.write().await;
+        let Some(room) = rooms.get_mut(&game_id) else { return; };
+        if !room.lobby.countdown_active(countdown_id) {
+            return;
+        }
+
+        start_game(room);
+        room.broadcast(&lobby_message(room, "start", None));
+        room.broadcast(&game_snapshot(room));
+
+        // Trivia and Pictionary run on the server's clock rather than on player moves
+        match &room.game {
+            GameType::Trivia(game) => {
+                tokio::spawn(trivia_handler::run_quiz(state.clone(), game_id.clone(), game.run_id));
+            }
+            GameType::Pictionary(game) => {
+                tokio::spawn(pictionary_handler::run_turns(state.clone(), game_id.clone(), game.run_id));
+            }
+            _ => {}
+        }
+    }
+
+    connectfour_handler::play_bot_opening(&state, &game_id).await;
+    dm_private_views(state, &game_id).await;
+}
+
+/// Takeback requests for TicTacToe and Uno. The player who made the last move asks to undo it
+/// ("takeback"); every other seated player must accept ("takeback_accept") or one can decline.
+async fn handle_takeback(
+    mut payload: GameRoomPayload,
+    state: &Arc<AppState>,
+) -> ServerMessage {
+    let mut rooms = state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&payload.game_id) else {
+        payload.action = "invalid".into();
+        return ServerMessage::GameRoom(payload);
+    };
+
+    let supported = matches!(room.game, GameType::TicTacToe(_) | GameType::Uno(_));
+    let result = if !supported {
+        Err("takebacks_unsupported")
+    } else if room.lobby.phase != LobbyPhase::InGame || !room.lobby.is_seated(&payload.player_name) {
+        Err("not_playing")
+    } else {
+        let seats = room.lobby.seats.clone();
+        match payload.action.as_str() {
+            "takeback" => room.takebacks.request(&payload.player_name, game_over(&room.game)).map(|()| None),
+            "takeback_accept" => room.takebacks.respond(&payload.player_name, true, &seats),
+            _ => room.takebacks.respond(&payload.player_name, false, &seats),
+        }
+    };
+
+    let mut takeback = room.takebacks.payload();
+    let mut restored = false;
+    match result {
+        Ok(Some(before)) => {
+            println!("[GameRoom] Takeback accepted in room {}", payload.game_id);
+            room.game = before;
+            room.broadcast(&game_snapshot(room));
+            takeback = room.takebacks.payload();
+            restored = true;
+        }
+        Ok(None) => {
+            println!("[GameRoom] {} by {} in room {}", payload.action, payload.player_name, payload.game_id);
+        }
+        Err(reason) => {
+            eprintln!("[GameRoom] {} by {} refused in room {}: {}",
+                      payload.action, payload.player_name, payload.game_id, reason);
+            takeback.error = Some(reason.to_string());
+        }
+    }
+
+    payload.takeback = Some(Box::new(takeback));
+    fill_lobby_fields(&mut payload, room);
+    drop(rooms);
+
+    // Hands changed back; resend them privately
+    if restored {
+        dm_private_views(state.clone(), &payload.game_id).await;
+    }
+
+    ServerMessage::GameRoom(payload)
+}
+
+fn game_over(game: &GameType) -> bool {
+    match game {
+        GameType::TicTacToe(model) => model.winner != GameWinner::Pending,
+        GameType::Uno(model) => model.table.winner.is_some(),
+        _ => false,
+    }
+}
+
+/// Rated game name and result once a rated game has finished
+fn rated_result(game: &GameType) -> Option<(&'static str, GameResult)> {
+    fn two_player(names: [&Option<String>; 2], winner: Option<usize>) -> Option<GameResult> {
+        let [Some(p1), Some(p2)] = names else { return None };
+        Some(match winner {
+            Some(0) => GameResult::Win { winner: p1.clone(), losers: vec![p2.clone()] },
+            Some(_) => GameResult::Win { winner: p2.clone(), losers: vec![p1.clone()] },
+            None => GameResult::Draw(vec![p1.clone(), p2.clone()]),
+        })
+    }
+
+    match game {
+        GameType::TicTacToe(model) => {
+            let winner = match model.winner {
+                GameWinner::Pending => return None,
+                GameWinner::Player1 => Some(0),
+                GameWinner::Player2 => Some(1),
+                GameWinner::Tie => None,
+            };
+            Some(("tictactoe", two_player([&model.player1_name, &model.player2_name], winner)?))
+        }
+        // The whole series is the rated unit, so one match can't farm a rating round by round
+        GameType::RockPaperScissors(model) => {
+            let winner = model.series.winner?;
+            Some(("rockpaperscissors", two_player([&model.player1_name, &model.player2_name], Some(winner))?))
+        }
+        GameType::Uno(model) => {
+            let winner = model.table.winner.clone()?;
+            let losers = model.table.players.iter().filter(|p| **p != winner).cloned().collect();
+            Some(("uno", GameResult::Win { winner, losers }))
+        }
+        _ => None,
+    }
+}
+
+/// Once the room's game is decided, write its result to the ratings (once per game). Games
+/// with any unregistered player are skipped, since anyone can play under those names.
+pub async fn record_result(state: &Arc<AppState>, game_id: &str) {
+    let (game, result) = {
+        let mut rooms = state.rooms.write().await;
+        let Some(room) = rooms.get_mut(game_id) else { return };
+        if room.result_recorded {
+            return;
+        }
+        let Some(rated) = rated_result(&room.game) else { return };
+        room.result_recorded = true;
+        rated
+    };
+
+    let writer = state.clone();
+    let outcome = tokio::task::spawn_blocking(move || {
+        if !result.players().iter().all(|name| writer.accounts.is_registered(name)) {
+            return Ok(None);
+        }
+        writer.ratings.record(game, &result).map(|()| Some(result))
+    })
+    .await
+    .unwrap_or(Err("storage_error"));
+    match outcome {
+        Ok(Some(result)) => println!("[Ratings] Recorded {} result in room {}: {:?}", game, game_id, result),
+        Ok(None) => println!("[Ratings] Not rating {} game in room {}: not every player is registered", game, game_id),
+        Err(reason) => eprintln!("[Ratings] Could not record {} result in room {}: {}", game, game_id, reason),
+    }
+}
+
+/// Reset the room's game model and seat the ready players in it.
+pub fn start_game(room: &mut GameRoom) {
+    let seats = room.lobby.seats.clone();
+    room.lobby.phase = LobbyPhase::InGame;
+    room.takebacks.clear();
+    room.result_recorded = false;
+
+    match &mut room.game {
+        GameType::TicTacToe(game) => {
+            game.new_series();
+            game.seat_players(&seats);
+        }
+        GameType::UltimateTicTacToe(game) => {
+            game.new_series();
+            game.meta.seat_players(&seats);
+        }
+        GameType::RockPaperScissors(game) => {
+            game.new_series();
+            game.seat_players(&seats);
+        }
+        GameType::ConnectFour(game) => {
+            game.new_series();
+            if game.vs_bot {
+                game.seat_players(&[seats[0].clone(), BOT_NAME.to_string()]);
+            } else {
+                game.seat_players(&seats);
+            }
+        }
+        GameType::RpsTournament(tournament) => tournament.start(&seats),
+        GameType::Uno(game) => {
+            game.reset();
+            for player in &seats {
+                game.add_player(player);
+            }
+            game.start();
+        }
+        GameType::Blackjack(game) => game.start(&seats),
+        GameType::TexasHoldem(game) => game.start(&seats),
+        GameType::DotsAndBoxes(game) => game.start(&seats),
+        GameType::Hangman(game) => game.start(&seats),
+        GameType::Trivia(game) => {
+            game.start(&seats);
+        }
+        GameType::Pictionary(game) => {
+            game.start(&seats);
+        }
+        GameType::Battleship(game) => {
+            game.reset();
+            game.seat_players(&seats);
+        }
+        GameType::Checkers(game) => {
+            game.reset();
+            if game.vs_bot {
+                game.seat_players(&[seats[0].clone(), BOT_NAME.to_string()]);
+            } else {
+                game.seat_players(&seats);
+            }
+        }
+        GameType::Reversi(game) => {
+            game.reset();
+            if game.vs_bot {
+                game.seat_players(&[seats[0].clone(), BOT_NAME.to_string()]);
+            } else {
+                game.seat_players(&seats);
+            }
+        }
+        GameType::Mancala(game) => {
+            game.reset();
+            if game.vs_bot {
+                game.seat_players(&[seats[0].clone(), BOT_NAME.to_string()]);
+            } else {
+                game.seat_players(&seats);
+            }
+        }
+        GameType::CrazyEights(game) => {
+            game.reset();
+            for player in &seats {
+                game.add_player(player);
+            }
+            game.start();
+        }
+        GameType::GoFish(game) => {
+            game.reset();
+            for player in &seats {
+                game.add_player(player);
+            }
+            game.start();
+        }
+    }
+
+    println!("[GameRoom] {} starting in room {}! Players: {:?}",
+             room.game.name(), room.game_id, seats);
+}
+
+/// Let the bot play while it is to move in a running game. Each search runs on a blocking
+/// thread without holding the rooms lock; if the game changed meanwhile, the bot searches again.
+pub async fn play_bot_turns<M: BotPlayer>(
+    state: &Arc<AppState>,
+    game_id: &str,
+    model: fn(&mut GameType) -> Option<&mut M>,
+) -> Vec<M::Played> {
+    let mut played = Vec::new();
+    loop {
+        let search = {
+            let mut rooms = state.rooms.write().await;
+            rooms.get_mut(game_id)
+                .filter(|room| room.lobby.phase == LobbyPhase::InGame)
+                .and_then(|room| model(&mut room.game))
+                .and_then(|game| game.bot_search())
+        };
+        let Some((position, depth)) = search else { break; };
+
+        let searched = position.clone();
+        let Ok(Some(mv)) = tokio::task::spawn_blocking(move || minimax::best_move(&searched, depth)).await else {
+            break;
+        };
+
+        let mut rooms = state.rooms.write().await;
+        let Some(game) = rooms.get_mut(game_id)
+            .filter(|room| room.lobby.phase == LobbyPhase::InGame)
+            .and_then(|room| model(&mut room.game))
+        else {
+            break;
+        };
+        // A reset or another move landed while the bot was thinking
+        if game.bot_search().map(|(current, _)| current) != Some(position) {
+            continue;
+        }
+        match game.play_bot_move(mv) {
+            Some(result) => played.push(result),
+            None => break,
+        }
+    }
+    played
+}
+
+/// Send a reply to one connection only, e.g. a refusal for a room it isn't in
+pub fn send_direct(tx: &UnboundedSender<Message>, msg: &ServerMessage) {
+    let serialized = serde_json::to_string(msg).unwrap();
+    let _ = tx.send(Message::Text(serialized.into()));
+}
+
+/// Public snapshot of the room's current game
+pub fn game_snapshot(room: &GameRoom) -> ServerMessage {
+    match &room.game {
+        GameType::TicTacToe(game) => tictactoe_handler::build_snapshot(game),
+        GameType::UltimateTicTacToe(game) => tictactoe_handler::build_ultimate_snapshot(game),
+        GameType::ConnectFour(game) => connectfour_handler::build_snapshot(&room.game_id, game),
+        GameType::RockPaperScissors(game) => ServerMessage::RockPaperScissors(
+            rockpaperscissors_handler::build_state_payload(&room.game_id, game),
+        ),
+        GameType::RpsTournament(tournament) => {
+            rockpaperscissors_handler::tournament_snapshot(&room.game_id, tournament)
+        }
+        GameType::Uno(game) => uno_handler::build_public_update(&room.game_id, game),
+        GameType::Blackjack(game) => blackjack_handler::build_view(&room.game_id, game, None, "ok"),
+        GameType::CrazyEights(game) => crazyeights_handler::build_view(&room.game_id, game, None, "ok"),
+        GameType::GoFish(game) => gofish_handler::build_view(&room.game_id, game, None, "ok"),
+        GameType::TexasHoldem(game) => holdem_handler::build_view(&room.game_id, game, None, "ok"),
+        GameType::Battleship(game) => battleship_handler::build_view(&room.game_id, game, None, "ok"),
+        GameType::Checkers(game) => checkers_handler::build_snapshot(&room.game_id, game),
+        GameType::Reversi(game) => reversi_handler::build_snapshot(&room.game_id, game),
+        GameType::Mancala(game) => mancala_handler::build_snapshot(&room.game_id, game),
+        GameType::DotsAndBoxes(game) => dotsandboxes_handler::build_view(&room.game_id, game, "ok"),
+        GameType::Hangman(game) => hangman_handler::build_view(&room.game_id, game, "ok"),
+        GameType::Trivia(game) => trivia_handler::build_view(&room.game_id, game, "ok"),
+        GameType::Pictionary(game) => pictionary_handler::build_view(&room.game_id, game, None, "ok"),
+    }
+}
+
+/// Send every player their private view of the game (hands in the shedding games, hole cards, fleets)
+pub async fn dm_private_views(state: Arc<AppState>, game_id: &str) {
+    let rooms = state.rooms.read().await;
+    let Some(room) = rooms.get(game_id) else { return; };
+
+    match &room.game {
+        GameType::Uno(game) => {
+            for player in &game.table.players {
+                room.send_to(player, &uno_handler::build_private_hand(game_id, game, player));
+            }
+        }
+        GameType::Blackjack(game) => {
+            for player in &game.players {
+                room.send_to(&player.name, &blackjack_handler::build_view(game_id, game, Some(&player.name), "ok"));
+            }
+        }
+        GameType::CrazyEights(game) => {
+            for player in &game.table.players {
+                room.send_to(player, &crazyeights_handler::build_view(game_id, game, Some(player), "ok"));
+            }
+        }
+        GameType::GoFish(game) => {
+            for player in &game.table.players {
+                room.send_to(player, &gofish_handler::build_view(game_id, game, Some(player), "ok"));
+            }
+        }
+        GameType::TexasHoldem(game) => {
+            for seat in &game.seats {
+                room.send_to(&seat.name, &holdem_handler::build_view(game_id, game, Some(&seat.name), "ok"));
+            }
+        }
+        GameType::Battleship(game) => {
+            for player in game.seat_names().into_iter().flatten() {
+                room.send_to(player, &battleship_handler::build_view(game_id, game, Some(player), "ok"));
+            }
+        }
+        GameType::Pictionary(game) => {
+            if let Some(drawer) = game.drawer() {
+                room.send_to(drawer, &pictionary_handler::build_view(game_id, game, Some(drawer), "ok"));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Apply creator-chosen options to a freshly created room
+fn apply_room_options(room: &mut GameRoom, payload: &GameRoomPayload) {
+    let Some(options) = &payload.options else { return; };
+    let game_id = room.game_id.clone();
+
+    match &mut room.game {
+        GameType::TicTacToe(game) => {
+            if let Some(best_of) = options.best_of {
+                game.series = Series::new(best_of);
+            }
+            if options.rows.is_some() || options.cols.is_some() || options.win_length.is_some() {
+                let rows = options.rows.or(options.cols).unwrap_or(game.rows);
+                let cols = options.cols.unwrap_or(rows);
+                let win_length = options.win_length.unwrap_or(rows.min(cols).min(5));
+                match game.configure(rows, cols, win_length) {
+                    Ok(()) => println!("[GameRoom] Room {} plays {}x{}, {} in a row", game_id, rows, cols, win_length),
+                    Err(reason) => eprintln!("[GameRoom] Ignoring board options for room {}: {}", game_id, reason),
                 }
             }
-            GameType::RockPaperScissors(model) => {
-                let p1 = model.player1_name.clone();
-                let p2 = model.player2_name.clone();
-                model.reset_round();
-
-                println!(
-                    "[GameRoom] RockPaperScissors game reset. Players: {:?} vs {:?}",
-                    p1, p2
-                );
-
-                if let (Some(player1), Some(player2)) = (p1, p2) {
-                    let payload = RockPaperScissorsPayloadToClient {
-                        game_id: payload.game_id.clone(),
-                        player1: Some(player1.clone()),
-                        player2: Some(player2.clone()),
-                        player1_choice: None,
-                        player2_choice: None,
-                        status: "waiting_for_choices".to_string(),
-                        winner: None,
-                        message: Some("Round reset. Choose rock, paper, or scissors.".to_string()),
-                    };
-
-                    let game_msg = ServerMessage::RockPaperScissors(payload);
-                    let serialized = serde_json::to_string(&game_msg).unwrap();
-
-                    for tx in &room.txs {
-                        let _ = tx.send(Message::Text(serialized.clone().into()));
+            match options.ttt_variant.as_deref().map(TicTacToeVariant::from_str) {
+                Some(Some(variant)) => game.set_variant(variant),
+                Some(None) => eprintln!("[GameRoom] Unknown TicTacToe variant for room {}", game_id),
+                None => {}
+            }
+        }
+        GameType::UltimateTicTacToe(game) => {
+            if let Some(best_of) = options.best_of {
+                game.meta.series = Series::new(best_of);
+            }
+        }
+        GameType::ConnectFour(game) => {
+            if let Some(best_of) = options.best_of {
+                game.series = Series::new(best_of);
+            }
+            if options.vs_bot == Some(true) {
+                game.vs_bot = true;
+                if let Some(depth) = options.bot_depth {
+                    game.bot_depth = depth.clamp(1, MAX_BOT_DEPTH);
+                }
+                // Only the human takes a seat; the bot plays player 2
+                room.lobby.min_players = 1;
+                room.lobby.max_players = 1;
+            }
+        }
+        GameType::Reversi(game) => {
+            if options.vs_bot == Some(true) {
+                game.vs_bot = true;
+                match options.bot_level.as_deref().map(BotLevel::from_str) {
+                    Some(Some(level)) => game.bot_level = level,
+                    Some(None) => eprintln!("[GameRoom] Unknown bot level for room {}", game_id),
+                    None => {}
+                }
+                room.lobby.min_players = 1;
+                room.lobby.max_players = 1;
+            }
+        }
+        GameType::RockPaperScissors(game) => {
+            if let Some(best_of) = options.best_of {
+                game.series = Series::new(best_of);
+            }
+            if let Some(rules) = rps_rules_from_options(options, &game_id) {
+                game.rules = rules;
+            }
+            game.commit_reveal = options.commit_reveal.unwrap_or(false);
+        }
+        GameType::RpsTournament(tournament) => {
+            if let Some(best_of) = options.best_of {
+                tournament.best_of = best_of;
+            }
+            if let Some(rules) = rps_rules_from_options(options, &game_id) {
+                tournament.rules = rules;
+            }
+            tournament.commit_reveal = options.commit_reveal.unwrap_or(false);
+            match options.tournament_format.as_deref().map(BracketFormat::from_str) {
+                Some(Some(format)) => tournament.format = format,
+                Some(None) => eprintln!("[GameRoom] Unknown tournament format for room {}", game_id),
+                None => {}
+            }
+        }
+        GameType::Uno(_) | GameType::CrazyEights(_) | GameType::GoFish(_) => {}
+        GameType::Battleship(game) => game.extra_shot_on_hit = options.extra_shot_on_hit.unwrap_or(false),
+        GameType::Pictionary(game) => {
+            if let Some(categories) = &options.word_categories {
+                // Same word list as Hangman, so the same category names
+                let mut check = HangmanModel::new();
+                match check.set_categories(categories) {
+                    Ok(()) => game.categories = check.categories,
+                    Err(reason) => eprintln!("[GameRoom] Ignoring word categories for room {}: {}", game_id, reason),
+                }
+            }
+            if let Some(secs) = options.draw_secs {
+                game.draw_secs = secs.clamp(MIN_DRAW_SECS, MAX_DRAW_SECS);
+            }
+            if let Some(rounds) = options.drawing_rounds {
+                game.rounds = rounds.clamp(1, MAX_ROUNDS);
+            }
+        }
+        GameType::Trivia(game) => {
+            if let Some(pack_id) = &options.trivia_pack {
+                match QuestionPack::load(pack_id) {
+                    Ok(pack) => {
+                        println!("[GameRoom] Room {} plays pack '{}' ({} questions)", game_id, pack.name, pack.questions.len());
+                        game.pack_id = pack_id.clone();
+                        game.pack = Some(pack);
                     }
+                    Err(reason) => eprintln!("[GameRoom] Ignoring trivia pack for room {}: {}", game_id, reason),
+                }
+            }
+            if let Some(secs) = options.question_secs {
+                game.question_secs = secs.clamp(1, MAX_QUESTION_SECS);
+            }
+        }
+        GameType::Hangman(game) => {
+            if let Some(categories) = &options.word_categories
+                && let Err(reason) = game.set_categories(categories)
+            {
+                eprintln!("[GameRoom] Ignoring word categories for room {}: {}", game_id, reason);
+            }
+            match options.hangman_mode.as_deref().map(HangmanMode::from_str) {
+                Some(Some(mode)) => game.mode = mode,
+                Some(None) => eprintln!("[GameRoom] Unknown Hangman mode for room {}", game_id),
+                None => {}
+            }
+            if let Some(lives) = options.lives {
+                game.max_lives = lives.clamp(1, MAX_LIVES);
+            }
+        }
+        GameType::DotsAndBoxes(game) => {
+            if options.rows.is_some() || options.cols.is_some() {
+                let rows = options.rows.or(options.cols).unwrap_or(game.rows);
+                let cols = options.cols.unwrap_or(rows);
+                match game.configure(rows, cols) {
+                    Ok(()) => println!("[GameRoom] Room {} plays {}x{} boxes", game_id, rows, cols),
+                    Err(reason) => eprintln!("[GameRoom] Ignoring grid options for room {}: {}", game_id, reason),
                 }
             }
-            
-            GameType::Uno(model) => {
-                // Reset UNO model to lobby state and preserve current room players
-                *model = UnoModel::new();
-                let players = room.users.clone();
-                for p in &players { model.add_player(p); }
-
-                println!(
-                    "[GameRoom] UNO game reset. Players: {:?}",
-                    players
-                );
-
-                // Broadcast a fresh lobby snapshot so FE can start when players.len() >= 2
-                let counts = vec![0u8; players.len()];
-                let payload_uno = UnoPayloadToClient {
-                    game_id: payload.game_id.clone(),
-                    players: Some(players.clone()),
-                    current_idx: Some(0),
-                    direction: Some(1),
-                    top_discard: None,
-                    chosen_color: None,
-                    pending_draw: Some(0),
-                    public_counts: Some(counts),
-                    hand: None,
-                    winner: None,
-                };
-
-                let game_msg = ServerMessage::Uno(payload_uno);
-                let serialized = serde_json::to_string(&game_msg).unwrap();
-
-                for tx in &room.txs {
-                    let _ = tx.send(Message::Text(serialized.clone().into()));
+        }
+        GameType::Checkers(game) => {
+            if options.vs_bot == Some(true) {
+                game.vs_bot = true;
+                if let Some(depth) = options.bot_depth {
+                    game.bot_depth = depth.clamp(1, MAX_CHECKERS_BOT_DEPTH);
+                }
+                // Only the human takes a seat; the bot plays player 2
+                room.lobby.min_players = 1;
+                room.lobby.max_players = 1;
+            }
+        }
+        GameType::Mancala(game) => {
+            if options.pits_per_side.is_some() || options.seeds_per_pit.is_some() {
+                let pits = options.pits_per_side.unwrap_or(game.board.pits);
+                let seeds = options.seeds_per_pit.unwrap_or(game.seeds);
+                match game.configure(pits, seeds) {
+                    Ok(()) => println!("[GameRoom] Room {} plays {} pits of {} seeds", game_id, pits, seeds),
+                    Err(reason) => eprintln!("[GameRoom] Ignoring Mancala options for room {}: {}", game_id, reason),
+                }
+            }
+            if options.vs_bot == Some(true) {
+                game.vs_bot = true;
+                if let Some(depth) = options.bot_depth {
+                    game.bot_depth = depth.clamp(1, MAX_MANCALA_BOT_DEPTH);
                 }
+                room.lobby.min_players = 1;
+                room.lobby.max_players = 1;
+            }
+        }
+        GameType::Blackjack(game) => {
+            if let Some(decks) = options.decks {
+                game.decks = decks.clamp(1, 8);
+            }
+            if let Some(chips) = options.starting_chips {
+                game.starting_chips = chips;
+            }
+            if let Some(min_bet) = options.min_bet {
+                game.min_bet = min_bet.max(1);
             }
         }
-        
-        // ✅ Include player list in response
-        payload.players = Some(room.users.clone());
+        GameType::TexasHoldem(game) => {
+            if let Some(chips) = options.starting_chips {
+                game.starting_stack = chips.max(1);
+            }
+            if let Some(big_blind) = options.big_blind {
+                game.big_blind = big_blind.max(2);
+                game.small_blind = options.small_blind.unwrap_or(game.big_blind / 2).clamp(1, game.big_blind);
+            } else if let Some(small_blind) = options.small_blind {
+                game.small_blind = small_blind.clamp(1, game.big_blind);
+            }
+        }
+    }
+
+    if options.ranked == Some(true) {
+        room.takebacks.enabled = false;
+    }
+    if let Some(limit) = options.takeback_limit {
+        room.takebacks.limit = limit;
     }
 
+    let lobby_sized = matches!(
+        room.game,
+        GameType::Uno(_)
+            | GameType::CrazyEights(_)
+            | GameType::GoFish(_)
+            | GameType::RpsTournament(_)
+            | GameType::Blackjack(_)
+            | GameType::TexasHoldem(_)
+            | GameType::DotsAndBoxes(_)
+            | GameType::Hangman(_)
+            | GameType::Trivia(_)
+            | GameType::Pictionary(_)
+    );
+    if lobby_sized && let Some(min) = options.min_players {
+        let (floor, _) = room.game.seat_limits();
+        room.lobby.min_players = min.clamp(floor, room.lobby.max_players);
+    }
+}
+
+/// Bundled (`rps_variant`) or custom (`rps_rules`) rule set requested for an RPS room
+fn rps_rules_from_options(options: &RoomOptions, game_id: &str) -> Option<RuleSet> {
+    let rules = if let Some(edges) = &options.rps_rules {
+        let edges: Vec<RuleEdge> = edges.iter().map(|[w, v, l]| (w.as_str(), v.as_str(), l.as_str())).collect();
+        RuleSet::from_edges("custom", &edges)
+    } else {
+        let name = options.rps_variant.as_deref()?;
+        RuleSet::by_name(name).ok_or_else(|| format!("unknown rule set '{}'", name))
+    };
+
+    match rules {
+        Ok(rules) => {
+            println!("[GameRoom] Room {} plays {} rules: {:?}", game_id, rules.name, rules.choices);
+            Some(rules)
+        }
+        Err(reason) => {
+            eprintln!("[GameRoom] Invalid RPS rules for room {}, using classic: {}", game_id, reason);
+            None
+        }
+    }
+}
+
+/// Lobby broadcast not tied to a particular client request (countdown ticks, game start)
+fn lobby_message(room: &GameRoom, action: &str, countdown: Option<u8>) -> ServerMessage {
+    let mut payload = GameRoomPayload {
+        game: room.game.name().to_string(),
+        action: action.to_string(),
+        player_name: String::new(),
+        game_id: room.game_id.clone(),
+        players: None,
+        options: None,
+        seats: None,
+        ready: None,
+        phase: None,
+        countdown,
+        takeback: None,
+    };
+    fill_lobby_fields(&mut payload, room);
     ServerMessage::GameRoom(payload)
 }
+
+fn fill_lobby_fields(payload: &mut GameRoomPayload, room: &GameRoom) {
+    payload.players = Some(room.users.clone());
+    payload.seats = Some(room.lobby.seats.clone());
+    payload.ready = Some(room.lobby.ready.clone());
+    payload.phase = Some(room.lobby.phase_str().to_string());
+}
+
+/// Room `game_id` with `players` joined (one connection each) and the game started without
+/// the ready-check. Returns the connections' receivers so the room keeps broadcasting to them.
+#[cfg(test)]
+pub async fn started_test_room(
+    state: &Arc<AppState>,
+    game: &str,
+    game_id: &str,
+    players: &[&str],
+) -> Vec<tokio::sync::mpsc::UnboundedReceiver<Message>> {
+    let mut receivers = Vec::new();
+    for player in players {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let payload = serde_json::from_value(serde_json::json!({
+            "game": game, "action": "join", "player_name": player, "game_id": game_id,
+        }))
+        .unwrap();
+        gameroom_handler(payload, state, tx, Arc::new(RwLock::new(None))).await;
+        receivers.push(rx);
+    }
+    start_game(state.rooms.write().await.get_mut(game_id).unwrap());
+    receivers
+}
+
+/// The connection `player` joined `game_id` on, as a test would pass it to a game handler
+#[cfg(test)]
+pub async fn member_tx(state: &Arc<AppState>, game_id: &str, player: &str) -> UnboundedSender<Message> {
+    state.rooms.read().await[game_id].members[player].clone()
+}
+
+/// Public snapshot of `game_id` as JSON, for checking that a refused action changed nothing
+#[cfg(test)]
+pub async fn snapshot_json(state: &Arc<AppState>, game_id: &str) -> serde_json::Value {
+    serde_json::to_value(game_snapshot(&state.rooms.read().await[game_id])).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn a_second_connection_cannot_join_under_a_connected_name() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "tictactoe", "room", &["alice", "bob"]).await;
+
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let payload: GameRoomPayload = serde_json::from_value(serde_json::json!({
+            "game": "tictactoe", "action": "join", "player_name": "alice", "game_id": "room",
+        }))
+        .unwrap();
+        let ServerMessage::GameRoom(reply) = gameroom_handler(payload, &state, tx.clone(), Arc::new(RwLock::new(None))).await else {
+            panic!("expected a GameRoom reply");
+        };
+        assert_eq!(reply.action, "invalid");
+        let rooms = state.rooms.read().await;
+        assert!(!rooms["room"].members["alice"].same_channel(&tx));
+    }
+
+    #[tokio::test]
+    async fn room_actions_for_another_room_under_the_same_name_are_refused() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "tictactoe", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "tictactoe", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let alice_tx = member_tx(&state, "room", "alice").await;
+
+        for action in ["leave", "reset", "ready", "rematch", "takeback"] {
+            let payload = serde_json::from_value(serde_json::json!({
+                "game": "tictactoe", "action": action, "player_name": "alice", "game_id": "other",
+            }))
+            .unwrap();
+            let ServerMessage::GameRoom(reply) = gameroom_handler(payload, &state, alice_tx.clone(), Arc::new(RwLock::new(None))).await else {
+                panic!("expected a GameRoom reply");
+            };
+            assert_eq!(reply.action, "invalid", "{} was not refused", action);
+        }
+        let rooms = state.rooms.read().await;
+        assert!(rooms["other"].members.contains_key("alice"));
+        assert_eq!(rooms["other"].lobby.phase, LobbyPhase::InGame);
+        drop(rooms);
+        assert_eq!(snapshot_json(&state, "other").await, before);
+    }
+
+    #[tokio::test]
+    async fn a_finished_game_is_rated_once() {
+        let state = Arc::new(AppState::default());
+        for name in ["alice", "bob"] {
+            state.accounts.register(name, "correct horse").unwrap();
+        }
+        let _connections = started_test_room(&state, "tictactoe", "room", &["alice", "bob"]).await;
+        let winner = {
+            let mut rooms = state.rooms.write().await;
+            let GameType::TicTacToe(game) = &mut rooms.get_mut("room").unwrap().game else { unreachable!() };
+            game.winner = GameWinner::Player2;
+            game.player2_name.clone().unwrap()
+        };
+
+        record_result(&state, "room").await;
+        record_result(&state, "room").await;
+        let stats = &state.ratings.player_stats(&winner).unwrap()["tictactoe"];
+        assert_eq!((stats.games_played, stats.wins, stats.streak), (1, 1, 1));
+
+        // Unfinished games aren't rated, and a reset makes the next result count
+        let payload = serde_json::from_value(serde_json::json!({
+            "game": "tictactoe", "action": "reset", "player_name": "alice", "game_id": "room",
+        }))
+        .unwrap();
+        gameroom_handler(payload, &state, member_tx(&state, "room", "alice").await, Arc::new(RwLock::new(None))).await;
+        record_result(&state, "room").await;
+        assert!(!state.rooms.read().await["room"].result_recorded);
+        assert_eq!(state.ratings.player_stats(&winner).unwrap()["tictactoe"].games_played, 1);
+    }
+
+    #[tokio::test]
+    async fn only_games_between_registered_players_are_rated() {
+        let state = Arc::new(AppState::default());
+        state.accounts.register("alice", "correct horse").unwrap();
+        let _connections = started_test_room(&state, "tictactoe", "room", &["alice", "bob"]).await;
+        {
+            let mut rooms = state.rooms.write().await;
+            let GameType::TicTacToe(game) = &mut rooms.get_mut("room").unwrap().game else { unreachable!() };
+            game.winner = GameWinner::Player1;
+        }
+
+        record_result(&state, "room").await;
+        assert!(state.ratings.player_stats("alice").unwrap().is_empty());
+        assert!(state.ratings.player_stats("bob").unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn an_rps_series_is_rated_once_not_per_round() {
+        let state = Arc::new(AppState::default());
+        for name in ["alice", "bob"] {
+            state.accounts.register(name, "correct horse").unwrap();
+        }
+        let _connections = started_test_room(&state, "rockpaperscissors", "room", &["alice", "bob"]).await;
+        let reset = || {
+            serde_json::from_value::<GameRoomPayload>(serde_json::json!({
+                "game": "rockpaperscissors", "action": "reset", "player_name": "alice", "game_id": "room",
+            }))
+            .unwrap()
+        };
+        let win_round = |state: Arc<AppState>| async move {
+            let mut rooms = state.rooms.write().await;
+            let GameType::RockPaperScissors(game) = &mut rooms.get_mut("room").unwrap().game else { unreachable!() };
+            let (p1, p2) = (game.player1_name.clone().unwrap(), game.player2_name.clone().unwrap());
+            let (rock, scissors) = (game.rules.parse_choice("rock").unwrap(), game.rules.parse_choice("scissors").unwrap());
+            game.submit_choice(&p1, rock).unwrap();
+            game.submit_choice(&p2, scissors).unwrap();
+            game.resolve_round();
+            p1
+        };
+        {
+            let mut rooms = state.rooms.write().await;
+            let GameType::RockPaperScissors(game) = &mut rooms.get_mut("room").unwrap().game else { unreachable!() };
+            game.series = crate::models::series::Series::new(3);
+        }
+
+        let winner = win_round(state.clone()).await;
+        record_result(&state, "room").await;
+        assert!(state.ratings.player_stats(&winner).unwrap().is_empty());
+
+        gameroom_handler(reset(), &state, member_tx(&state, "room", "alice").await, Arc::new(RwLock::new(None))).await;
+        win_round(state.clone()).await;
+        record_result(&state, "room").await;
+        record_result(&state, "room").await;
+        let stats = &state.ratings.player_stats(&winner).unwrap()["rockpaperscissors"];
+        assert_eq!((stats.games_played, stats.wins), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn the_bot_answers_after_the_rooms_lock_is_released() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "connectfour", "room", &["alice"]).await;
+        {
+            let mut rooms = state.rooms.write().await;
+            let room = rooms.get_mut("room").unwrap();
+            let GameType::ConnectFour(game) = &mut room.game else { unreachable!() };
+            game.vs_bot = true;
+            start_game(room);
+        }
+
+        let payload = serde_json::from_value(serde_json::json!({
+            "game_id": "room", "player_name": "alice", "column": 4,
+        }))
+        .unwrap();
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        let Some(ServerMessage::ConnectFour(reply)) =
+            connectfour_handler::connectfour_handler(payload, &state, "alice", &alice_tx).await
+        else {
+            panic!("expected a ConnectFour reply");
+        };
+        assert_eq!(reply.board.iter().flatten().filter(|&&cell| cell != 0).count(), 2);
+        assert_eq!(reply.whos_turn.as_deref(), Some("alice"));
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    cards::Rank,
+    gameroom::GameType,
+    gofish::model::GoFishModel,
+};
+use crate::routes::gameroom_handler;
+use crate::types::{GoFishAskPayload, GoFishPayloadToClient, GoFishPayloadToServer, ServerMessage};
+
+/// Handles Go Fish asks. Returns the public view; private hands follow from the ws loop.
+pub async fn gofish_handler(
+    payload: GoFishPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[GoFish] Room not found: {}", game_id);
+        return Some(build_view(&game_id, &GoFishModel::new(), None, "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[GoFish] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_view(&game_id, &GoFishModel::new(), None, "identity_mismatch"));
+        return None;
+    }
+
+    let GameType::GoFish(game) = &mut room.game else {
+        eprintln!("Tried to play Go Fish in a non-Go-Fish room: {}", game_id);
+        return Some(build_view(&game_id, &GoFishModel::new(), None, "wrong_game_type"));
+    };
+
+    // The lobby deals the cards once every seated player is ready
+    if !game.table.started {
+        return Some(build_view(&game_id, game, None, "waiting_for_players"));
+    }
+
+    let player = payload.player_name.as_str();
+    let result = match payload.action.as_str() {
+        "ask" => match (payload.target.as_deref(), payload.rank.as_deref().and_then(Rank::from_code)) {
+            (Some(target), Some(rank)) => game.ask(player, target, rank),
+            (None, _) => Err("missing_target"),
+            (_, None) => Err("invalid_rank"),
+        },
+        "request_state" => Ok(()),
+        _ => Err("unknown_action"),
+    };
+
+    match result {
+        Ok(()) => {
+            if let Some(ask) = &game.last_ask && payload.action == "ask" {
+                println!("[GoFish] {} asked {} for {}s and got {} in room {}",
+                         ask.asker, ask.target, ask.rank.code(), ask.received, game_id);
+            }
+            if game.game_over {
+                println!("[GoFish] Game over in room {}. Winners: {:?}", game_id, game.winners());
+            }
+            Some(build_view(&game_id, game, None, "ok"))
+        }
+        Err(reason) => {
+            eprintln!("[GoFish] {} {} refused: {}", player, payload.action, reason);
+            Some(build_view(&game_id, game, None, reason))
+        }
+    }
+}
+
+/// The table as seen by `viewer`; None leaves out every hand
+pub fn build_view(game_id: &str, game: &GoFishModel, viewer: Option<&str>, status: &str) -> ServerMessage {
+    ServerMessage::GoFish(GoFishPayloadToClient {
+        game_id: game_id.to_string(),
+        status: status.to_string(),
+        players: game.table.players.clone(),
+        current_idx: game.table.current_idx,
+        pond: game.table.deck.len(),
+        public_counts: game.table.public_counts(),
+        books: game
+            .books
+            .iter()
+            .map(|(player, ranks)| (player.clone(), ranks.iter().map(|r| r.code().to_string()).collect()))
+            .collect(),
+        last_ask: game.last_ask.as_ref().map(|ask| GoFishAskPayload {
+            asker: ask.asker.clone(),
+            target: ask.target.clone(),
+            rank: ask.rank.code().to_string(),
+            received: ask.received,
+            lucky_draw: ask.lucky_draw,
+        }),
+        game_over: game.game_over,
+        winners: game.winners(),
+        hand: viewer.map(|v| game.table.hand(v).to_vec()),
+        viewer: viewer.map(|v| v.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn asking_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "go_fish", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "go_fish", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "action": "ask", "target": "carol", "rank": "2" })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(gofish_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(gofish_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
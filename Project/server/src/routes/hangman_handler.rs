@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    gameroom::GameType,
+    hangman::model::{HangmanModel, HangmanOutcome},
+};
+use crate::routes::gameroom_handler;
+use crate::types::{HangmanPayloadToClient, HangmanPayloadToServer, ServerMessage};
+
+/// Handles Hangman guesses. Everything returned here is broadcast, so it only ever
+/// carries the masked word until the game is over.
+pub async fn hangman_handler(
+    payload: HangmanPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[Hangman] Room not found: {}", game_id);
+        return Some(build_view(&game_id, &HangmanModel::new(), "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[Hangman] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_view(&game_id, &HangmanModel::new(), "identity_mismatch"));
+        return None;
+    }
+
+    let GameType::Hangman(game) = &mut room.game else {
+        eprintln!("Tried to play Hangman in a non-Hangman room: {}", game_id);
+        return Some(build_view(&game_id, &HangmanModel::new(), "wrong_game_type"));
+    };
+
+    if !game.started {
+        return Some(build_view(&game_id, game, "waiting_for_players"));
+    }
+
+    // No guess: just send the latest state
+    let Some(guess) = payload.guess.as_deref() else {
+        return Some(build_view(&game_id, game, "ok"));
+    };
+
+    let player = payload.player_name.as_str();
+    if let Err(reason) = game.guess(player, guess) {
+        eprintln!("[Hangman] {} can't guess: {}", player, reason);
+        return Some(build_view(&game_id, game, reason));
+    }
+    // The guess itself may be the secret, so keep it out of the log
+    println!("[Hangman] {} guessed in room {}, {} lives left", player, game_id, game.lives);
+    match &game.outcome {
+        HangmanOutcome::Solved(by) => println!("[Hangman] {} found the word in room {}", by, game_id),
+        HangmanOutcome::Hanged => println!("[Hangman] Out of lives in room {}", game_id),
+        HangmanOutcome::Playing => {}
+    }
+
+    Some(build_view(&game_id, game, "ok"))
+}
+
+/// Masked word, misses, lives and scores for broadcasting
+pub fn build_view(game_id: &str, game: &HangmanModel, status: &str) -> ServerMessage {
+    ServerMessage::Hangman(HangmanPayloadToClient {
+        game_id: game_id.to_string(),
+        status: status.to_string(),
+        mode: game.mode.as_str().to_string(),
+        category: game.category.clone(),
+        masked_word: game.masked_word(),
+        correct_letters: game.correct_letters.iter().map(|c| c.to_string()).collect(),
+        wrong_letters: game.wrong_letters.iter().map(|c| c.to_string()).collect(),
+        wrong_words: game.wrong_words.clone(),
+        lives: game.lives,
+        max_lives: game.max_lives,
+        players: game.players.clone(),
+        whos_turn: game.current_player().cloned(),
+        scores: game.scores.clone(),
+        game_over: game.is_over(),
+        solved_by: match &game.outcome {
+            HangmanOutcome::Solved(by) => Some(by.clone()),
+            _ => None,
+        },
+        winners: game.winners(),
+        secret: game.revealed_secret().map(|s| s.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn guessing_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "hangman", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "hangman", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "guess": "e" })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(hangman_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(hangman_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    cards::CARD_BACK,
+    gameroom::GameType,
+    holdem::model::HoldemModel,
+    lobby::LobbyPhase,
+};
+use crate::routes::gameroom_handler;
+use crate::types::{
+    HoldemPayloadToClient,
+    HoldemPayloadToServer,
+    HoldemPotPayload,
+    HoldemSeatPayload,
+    ServerMessage,
+};
+
+/// Handles Texas Hold'em actions. Returns the public view; hole cards follow privately from the ws loop.
+pub async fn holdem_handler(
+    payload: HoldemPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[Holdem] Room not found: {}", game_id);
+        return Some(build_view(&game_id, &HoldemModel::new(), None, "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[Holdem] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_view(&game_id, &HoldemModel::new(), None, "identity_mismatch"));
+        return None;
+    }
+
+    let phase = room.lobby.phase;
+    let GameType::TexasHoldem(game) = &mut room.game else {
+        eprintln!("Tried to play Texas Hold'em in a non-Hold'em room: {}", game_id);
+        return Some(build_view(&game_id, &HoldemModel::new(), None, "wrong_game_type"));
+    };
+
+    if phase != LobbyPhase::InGame {
+        return Some(build_view(&game_id, game, None, "waiting_for_players"));
+    }
+
+    let result = match payload.action.as_str() {
+        "next_hand" => game.next_hand(),
+        "request_state" => Ok(()),
+        action => game.act(&payload.player_name, action, payload.amount),
+    };
+
+    match result {
+        Ok(()) => {
+            println!("[Holdem] {} -> {} in room {}", payload.player_name, payload.action, game_id);
+            for pot in &game.results {
+                println!("[Holdem] Hand {}: pot of {} to {:?}", game.hand_number, pot.amount, pot.winners);
+            }
+            Some(build_view(&game_id, game, None, "ok"))
+        }
+        Err(reason) => {
+            eprintln!("[Holdem] {} {} refused: {}", payload.player_name, payload.action, reason);
+            Some(build_view(&game_id, game, None, reason))
+        }
+    }
+}
+
+/// The table as seen by `viewer` (None = spectators / public broadcast)
+pub fn build_view(game_id: &str, game: &HoldemModel, viewer: Option<&str>, status: &str) -> ServerMessage {
+    let seats = game
+        .seats
+        .iter()
+        .map(|seat| {
+            let visible = seat.shown || viewer == Some(seat.name.as_str());
+            let hole = if !seat.in_hand() {
+                Vec::new()
+            } else if visible {
+                seat.hole.iter().map(|c| c.code()).collect()
+            } else {
+                vec![CARD_BACK.to_string(); seat.hole.len()]
+            };
+            HoldemSeatPayload {
+                name: seat.name.clone(),
+                stack: seat.stack,
+                bet: seat.bet,
+                committed: seat.committed,
+                folded: seat.folded,
+                all_in: seat.all_in,
+                hole,
+                hand: seat.hand.as_ref().filter(|_| seat.shown).map(|h| h.category.as_str().to_string()),
+                won: seat.won,
+            }
+        })
+        .collect();
+
+    ServerMessage::TexasHoldem(HoldemPayloadToClient {
+        game_id: game_id.to_string(),
+        phase: game.phase.as_str().to_string(),
+        status: status.to_string(),
+        hand_number: game.hand_number,
+        seats,
+        board: game.board.clone(),
+        pot: game.pot(),
+        dealer: game.seats.get(game.dealer).map(|s| s.name.clone()).unwrap_or_default(),
+        current_player: game.current_player_name().map(|s| s.to_string()),
+        current_bet: game.current_bet,
+        min_raise_to: game.current_bet + game.min_raise,
+        small_blind: game.small_blind,
+        big_blind: game.big_blind,
+        results: game
+            .results
+            .iter()
+            .map(|pot| HoldemPotPayload {
+                amount: pot.amount,
+                winners: pot.winners.clone(),
+                hand: pot.hand.map(|h| h.as_str().to_string()),
+            })
+            .collect(),
+        viewer: viewer.map(|s| s.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn acting_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "texas_holdem", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "texas_holdem", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "action": "fold" })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(holdem_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(holdem_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
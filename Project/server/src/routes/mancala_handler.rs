@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    gameroom::GameType,
+    lobby::LobbyPhase,
+    mancala::model::MancalaModel,
+    seats::TwoSeats,
+    tictactoe::model::{GameWinner, Player},
+};
+use crate::routes::gameroom_handler;
+use crate::types::{MancalaPayloadToClient, MancalaPayloadToServer, ServerMessage};
+
+/// Handles Mancala (Kalah) moves. In a bot room the bot answers once the rooms lock is
+/// released, including any extra turns it earns.
+pub async fn mancala_handler(
+    payload: MancalaPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[Mancala] Room not found: {}", game_id);
+        return Some(build_payload(&game_id, &MancalaModel::new(), "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[Mancala] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_payload(&game_id, &MancalaModel::new(), "identity_mismatch"));
+        return None;
+    }
+
+    let phase = room.lobby.phase;
+    let GameType::Mancala(game) = &mut room.game else {
+        eprintln!("Tried to play Mancala in a non-Mancala room: {}", game_id);
+        return Some(build_payload(&game_id, &MancalaModel::new(), "wrong_game_type"));
+    };
+
+    if phase != LobbyPhase::InGame {
+        return Some(build_payload(&game_id, game, "waiting_for_players"));
+    }
+
+    // No pit: just send the latest state
+    let Some(pit) = payload.pit else {
+        return Some(build_snapshot(&game_id, game));
+    };
+
+    if game.winner != GameWinner::Pending {
+        return Some(build_snapshot(&game_id, game));
+    }
+    let Some(seat) = game.seat_of(&payload.player_name) else {
+        eprintln!("[Mancala] Unknown player: {}", payload.player_name);
+        return Some(build_payload(&game_id, game, "unknown_player"));
+    };
+    if game.current_player_name() != game.seat_names()[seat].as_deref() {
+        eprintln!("[Mancala] Not {}'s turn", payload.player_name);
+        return Some(build_payload(&game_id, game, "not_your_turn"));
+    }
+
+    match game.play(pit) {
+        Ok(sowing) => println!("[Mancala] {} sowed pit {}{}", payload.player_name, pit,
+                               if sowing.extra_turn { " and goes again" } else { "" }),
+        Err(reason) => {
+            eprintln!("[Mancala] {} can't sow pit {}: {}", payload.player_name, pit, reason);
+            return Some(build_payload(&game_id, game, reason));
+        }
+    }
+
+    drop(rooms);
+
+    let bot_pits = gameroom_handler::play_bot_turns(app_state, &game_id, mancala).await;
+    if !bot_pits.is_empty() {
+        println!("[Mancala] Bot sowed {:?}", bot_pits);
+    }
+
+    let rooms = app_state.rooms.read().await;
+    let Some(GameType::Mancala(game)) = rooms.get(&game_id).map(|room| &room.game) else {
+        return Some(build_payload(&game_id, &MancalaModel::new(), "room_not_found"));
+    };
+    match game.winner {
+        GameWinner::Tie => println!("[Mancala] Drawn in room {}", game_id),
+        _ => {
+            if let Some(name) = game.winner_name() {
+                println!("[Mancala] {} wins!", name);
+            }
+        }
+    }
+
+    Some(build_snapshot(&game_id, game))
+}
+
+fn mancala(game: &mut GameType) -> Option<&mut MancalaModel> {
+    match game {
+        GameType::Mancala(game) => Some(game),
+        _ => None,
+    }
+}
+
+/// Pits, stores, turn, status and legal moves for broadcasting
+pub fn build_snapshot(game_id: &str, game: &MancalaModel) -> ServerMessage {
+    let status = match game.winner {
+        GameWinner::Pending => "IN_PROGRESS",
+        GameWinner::Player1 => "gameover_p1",
+        GameWinner::Player2 => "gameover_p2",
+        GameWinner::Tie => "gameover_draw",
+    };
+    build_payload(game_id, game, status)
+}
+
+fn build_payload(game_id: &str, game: &MancalaModel, status: &str) -> ServerMessage {
+    let board = &game.board;
+    ServerMessage::Mancala(MancalaPayloadToClient {
+        game_id: game_id.to_string(),
+        pits: [board.side_pits(1).to_vec(), board.side_pits(-1).to_vec()],
+        stores: [board.store_count(1), board.store_count(-1)],
+        whos_turn: game.current_player_name().map(|s| s.to_string()),
+        status: status.to_string(),
+        winner: game.winner_name().map(|s| s.to_string()),
+        legal_moves: game.legal_moves(),
+        last_move: game.last_move.as_ref().map(|(player, pit)| {
+            let name = match player {
+                Player::Player1 => game.player1_name.as_deref(),
+                Player::Player2 => game.player2_name.as_deref(),
+            };
+            (name.unwrap_or_default().to_string(), *pit)
+        }),
+        extra_turn: game.last_sowing.is_some_and(|s| s.extra_turn),
+        captured: game.last_sowing.map(|s| s.captured).unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn moving_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "mancala", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "mancala", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "pit": 2 })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(mancala_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(mancala_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
@@ -1,6 +1,21 @@
+pub mod account_handler;
+pub mod battleship_handler;
+pub mod blackjack_handler;
 pub mod chat_handler;
+pub mod checkers_handler;
+pub mod connectfour_handler;
+pub mod crazyeights_handler;
+pub mod dotsandboxes_handler;
 pub mod echo_handler;
 pub mod gameroom_handler;
+pub mod gofish_handler;
+pub mod hangman_handler;
+pub mod holdem_handler;
+pub mod mancala_handler;
+pub mod pictionary_handler;
+pub mod reversi_handler;
 pub mod tictactoe_handler;
 pub mod rockpaperscissors_handler;
+pub mod stats_handler;
+pub mod trivia_handler;
 pub mod uno_handler;
\ No newline at end of file
@@ -0,0 +1,208 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    gameroom::GameType,
+    pictionary::model::{PictionaryModel, Stroke, TURN_BREAK_SECS},
+};
+use crate::routes::gameroom_handler;
+use crate::types::{
+    PictionaryPayloadToClient, PictionaryPayloadToServer, PictionaryStrokePayload, PictionaryStrokeRelay,
+    ServerMessage,
+};
+
+/// Handles Pictionary messages, holding the rooms map only for reading (the canvas has its
+/// own lock). Strokes are relayed here straight to everyone but the drawer and return None,
+/// so the ws loop doesn't broadcast again. Other actions return the snapshot to broadcast.
+pub async fn pictionary_handler(
+    payload: PictionaryPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+
+    if payload.action == "stroke" {
+        let rooms = app_state.rooms.read().await;
+        let room = rooms.get(&game_id).filter(|room| room.is_member(sender, user_tx))?;
+        let GameType::Pictionary(game) = &room.game else { return None; };
+        let stroke = payload.stroke?;
+
+        let result = game.add_stroke(&payload.player_name, Stroke {
+            points: stroke.points.iter().map(|p| (p[0], p[1])).collect(),
+            color: stroke.color.clone(),
+            width: stroke.width,
+        });
+        // Bad strokes are dropped quietly; answering each one would flood the drawer
+        if let Err(reason) = result {
+            eprintln!("[Pictionary] Dropped stroke from {}: {}", payload.player_name, reason);
+            return None;
+        }
+        room.broadcast_except(&payload.player_name, &ServerMessage::PictionaryStroke(PictionaryStrokeRelay {
+            game_id,
+            stroke,
+        }));
+        return None;
+    }
+
+    let rooms = app_state.rooms.read().await;
+    let Some(room) = rooms.get(&game_id) else {
+        eprintln!("[Pictionary] Room not found: {}", game_id);
+        return Some(build_view(&game_id, &PictionaryModel::new(), None, "room_not_found"));
+    };
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[Pictionary] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_view(&game_id, &PictionaryModel::new(), None, "identity_mismatch"));
+        return None;
+    }
+    let GameType::Pictionary(game) = &room.game else {
+        eprintln!("Tried to play Pictionary in a non-Pictionary room: {}", game_id);
+        return Some(build_view(&game_id, &PictionaryModel::new(), None, "wrong_game_type"));
+    };
+
+    match payload.action.as_str() {
+        "clear" if game.is_drawer(&payload.player_name) => {
+            game.clear_canvas();
+            println!("[Pictionary] {} cleared the canvas in room {}", payload.player_name, game_id);
+            Some(build_view(&game_id, game, None, "ok"))
+        }
+        "clear" => Some(build_view(&game_id, game, None, "not_the_drawer")),
+        "request_state" => {
+            // The drawer asking again gets the word back, privately
+            if game.is_drawer(&payload.player_name) {
+                room.send_to(&payload.player_name, &build_view(&game_id, game, Some(&payload.player_name), "ok"));
+                return None;
+            }
+            Some(build_view(&game_id, game, None, "ok"))
+        }
+        _ => Some(build_view(&game_id, game, None, "unknown_action")),
+    }
+}
+
+/// Server-driven turn loop, spawned when a Pictionary room starts. Starts each turn (the
+/// drawer gets the word privately), waits for the time limit or for everyone to guess,
+/// then reveals the word. Stops if the room is gone or was reset (`run_id` changed).
+pub async fn run_turns(state: Arc<AppState>, game_id: String, run_id: u64) {
+    loop {
+        {
+            let mut rooms = state.rooms.write().await;
+            let Some(room) = rooms.get_mut(&game_id) else { return; };
+            let GameType::Pictionary(game) = &mut room.game else { return; };
+            if game.run_id != run_id {
+                return;
+            }
+            let started = game.next_turn(Instant::now());
+            let drawer = game.drawer().cloned();
+            if started {
+                println!("[Pictionary] Turn {} of {} in room {}: {:?} draws",
+                         game.turn, game.total_turns(), game_id, drawer);
+            } else {
+                println!("[Pictionary] Game over in room {}. Winners: {:?}", game_id, game.winners());
+            }
+            let public = build_view(&game_id, game, None, "ok");
+            let private = drawer.as_deref().map(|d| build_view(&game_id, game, Some(d), "ok"));
+            room.broadcast(&public);
+            if let (Some(drawer), Some(view)) = (drawer, private) {
+                room.send_to(&drawer, &view);
+            }
+            if !started {
+                return;
+            }
+        }
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            let rooms = state.rooms.read().await;
+            let Some(room) = rooms.get(&game_id) else { return; };
+            let GameType::Pictionary(game) = &room.game else { return; };
+            if game.run_id != run_id {
+                return;
+            }
+            if game.turn_is_done(Instant::now()) {
+                break;
+            }
+        }
+
+        {
+            let mut rooms = state.rooms.write().await;
+            let Some(room) = rooms.get_mut(&game_id) else { return; };
+            let GameType::Pictionary(game) = &mut room.game else { return; };
+            if game.run_id != run_id {
+                return;
+            }
+            game.end_turn();
+            let view = build_view(&game_id, game, None, "ok");
+            room.broadcast(&view);
+        }
+        tokio::time::sleep(Duration::from_secs(TURN_BREAK_SECS)).await;
+    }
+}
+
+fn stroke_payload(stroke: &Stroke) -> PictionaryStrokePayload {
+    PictionaryStrokePayload {
+        points: stroke.points.iter().map(|&(x, y)| [x, y]).collect(),
+        color: stroke.color.clone(),
+        width: stroke.width,
+    }
+}
+
+/// The game as seen by `viewer`. The word is only included for the drawer, or once the turn is over.
+pub fn build_view(game_id: &str, game: &PictionaryModel, viewer: Option<&str>, status: &str) -> ServerMessage {
+    build_view_with_guess(game_id, game, viewer, status, None)
+}
+
+pub fn build_view_with_guess(
+    game_id: &str,
+    game: &PictionaryModel,
+    viewer: Option<&str>,
+    status: &str,
+    correct_guess: Option<&str>,
+) -> ServerMessage {
+    ServerMessage::Pictionary(PictionaryPayloadToClient {
+        game_id: game_id.to_string(),
+        status: status.to_string(),
+        phase: game.phase.as_str().to_string(),
+        players: game.players.clone(),
+        drawer: game.drawer().cloned(),
+        turn: game.turn,
+        total_turns: game.total_turns(),
+        hint: game.hint(),
+        word: game.word_for(viewer).map(|w| w.to_string()),
+        seconds_left: game.seconds_left(Instant::now()),
+        guessed: game.guessed.clone(),
+        correct_guess: correct_guess.map(|g| g.to_string()),
+        scores: game.scores.clone(),
+        strokes: game.strokes().iter().map(stroke_payload).collect(),
+        winners: game.winners(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn acting_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "pictionary", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "pictionary", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "action": "clear" })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(pictionary_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(pictionary_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    gameroom::GameType,
+    lobby::LobbyPhase,
+    reversi::model::{ReversiModel, SIZE},
+    seats::TwoSeats,
+    tictactoe::model::{format_coordinate, parse_coordinate, GameWinner},
+};
+use crate::routes::gameroom_handler;
+use crate::types::{ReversiPayloadToClient, ReversiPayloadToServer, ServerMessage};
+
+/// Handles Reversi moves. Players without a move are skipped by the model; in a bot room
+/// the bot answers once the rooms lock is released.
+pub async fn reversi_handler(
+    payload: ReversiPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[Reversi] Room not found: {}", game_id);
+        return Some(build_payload(&game_id, &ReversiModel::new(), "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[Reversi] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_payload(&game_id, &ReversiModel::new(), "identity_mismatch"));
+        return None;
+    }
+
+    let phase = room.lobby.phase;
+    let GameType::Reversi(game) = &mut room.game else {
+        eprintln!("Tried to play Reversi in a non-Reversi room: {}", game_id);
+        return Some(build_payload(&game_id, &ReversiModel::new(), "wrong_game_type"));
+    };
+
+    if phase != LobbyPhase::InGame {
+        return Some(build_payload(&game_id, game, "waiting_for_players"));
+    }
+
+    // No square: just send the latest state
+    let Some(square) = payload.square.as_deref() else {
+        return Some(build_snapshot(&game_id, game));
+    };
+
+    if game.winner != GameWinner::Pending {
+        return Some(build_snapshot(&game_id, game));
+    }
+    let Some(seat) = game.seat_of(&payload.player_name) else {
+        eprintln!("[Reversi] Unknown player: {}", payload.player_name);
+        return Some(build_payload(&game_id, game, "unknown_player"));
+    };
+    if game.current_player_name() != game.seat_names()[seat].as_deref() {
+        eprintln!("[Reversi] Not {}'s turn", payload.player_name);
+        return Some(build_payload(&game_id, game, "not_your_turn"));
+    }
+
+    let Some((row, col)) = parse_coordinate(square, SIZE, SIZE) else {
+        return Some(build_payload(&game_id, game, "invalid_coordinate"));
+    };
+    if let Err(reason) = game.play(row, col) {
+        eprintln!("[Reversi] {} can't play {}: {}", payload.player_name, square, reason);
+        return Some(build_payload(&game_id, game, reason));
+    }
+    println!("[Reversi] {} played {}", payload.player_name, square);
+
+    drop(rooms);
+
+    for (r, c) in gameroom_handler::play_bot_turns(app_state, &game_id, reversi).await {
+        println!("[Reversi] Bot played {}", format_coordinate(r, c));
+    }
+
+    let rooms = app_state.rooms.read().await;
+    let Some(GameType::Reversi(game)) = rooms.get(&game_id).map(|room| &room.game) else {
+        return Some(build_payload(&game_id, &ReversiModel::new(), "room_not_found"));
+    };
+    if let Some(skipped) = game.passed_name() {
+        println!("[Reversi] {} has no move and passes", skipped);
+    }
+    match game.winner {
+        GameWinner::Tie => println!("[Reversi] Drawn on discs in room {}", game_id),
+        _ => {
+            if let Some(name) = game.winner_name() {
+                println!("[Reversi] {} wins!", name);
+            }
+        }
+    }
+
+    Some(build_snapshot(&game_id, game))
+}
+
+fn reversi(game: &mut GameType) -> Option<&mut ReversiModel> {
+    match game {
+        GameType::Reversi(game) => Some(game),
+        _ => None,
+    }
+}
+
+/// Board, turn, status, legal moves and disc counts for broadcasting
+pub fn build_snapshot(game_id: &str, game: &ReversiModel) -> ServerMessage {
+    let status = match game.winner {
+        GameWinner::Pending => "IN_PROGRESS",
+        GameWinner::Player1 => "gameover_p1",
+        GameWinner::Player2 => "gameover_p2",
+        GameWinner::Tie => "gameover_draw",
+    };
+    build_payload(game_id, game, status)
+}
+
+fn build_payload(game_id: &str, game: &ReversiModel, status: &str) -> ServerMessage {
+    ServerMessage::Reversi(ReversiPayloadToClient {
+        game_id: game_id.to_string(),
+        board: game.board.cells.iter().map(|row| row.iter().map(|&v| v as i32).collect()).collect(),
+        whos_turn: game.current_player_name().map(|s| s.to_string()),
+        status: status.to_string(),
+        winner: game.winner_name().map(|s| s.to_string()),
+        legal_moves: game.legal_moves().iter().map(|&(r, c)| format_coordinate(r, c)).collect(),
+        discs: game.board.counts(),
+        last_move: game.last_move.map(|(r, c)| format_coordinate(r, c)),
+        flipped: game.flipped.iter().map(|&(r, c)| format_coordinate(r, c)).collect(),
+        passed: game.passed_name().map(|s| s.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn moving_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "reversi", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "reversi", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "square": "D3" })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(reversi_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(reversi_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
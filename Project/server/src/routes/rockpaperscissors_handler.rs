@@ -1,52 +1,82 @@
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::{sync::Arc, time::Instant};
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
 
 use crate::models::{
     appstate::AppState,
     gameroom::GameType,
-    rockpaperscissors::model::{RockPaperScissorsModel, RpsChoice, RpsRoundResult},
+    lobby::LobbyPhase,
+    rockpaperscissors::{
+        model::{RockPaperScissorsModel, RpsRoundResult, MIN_NONCE_LEN},
+        rules::RuleSet,
+        tournament::TournamentModel,
+    },
 };
+use crate::routes::gameroom_handler;
 use crate::types::{
+    CommitRevealPayload,
     RockPaperScissorsPayloadToClient,
     RockPaperScissorsPayloadToServer,
+    RpsStandingPayload,
+    RpsTournamentPayloadToClient,
     ServerMessage,
 };
 
-/// Handles RockPaperScissors messages sent by clients.
+/// Handles RockPaperScissors messages sent by clients. Returns None when the reply was sent
+/// to the sender alone rather than for broadcasting.
 pub async fn rockpaperscissors_handler(
     payload: RockPaperScissorsPayloadToServer,
     app_state: &Arc<AppState>,
-    _current_room: Arc<RwLock<Option<String>>>,
-) -> ServerMessage {
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
     let game_id = payload.game_id.clone();
+
+    // Choices belong to the connection; a payload naming someone else is refused
+    if payload.player_name != sender {
+        eprintln!("[RPS] {} tried to play as {}", sender, payload.player_name);
+        return Some(build_error_payload(game_id, None, "identity_mismatch", "You can only play for yourself."));
+    }
     let mut rooms = app_state.rooms.write().await;
 
     let Some(room) = rooms.get_mut(&game_id) else {
-        return build_error_payload(game_id, None, "room_not_found", "Room not found.");
+        return Some(build_error_payload(game_id, None, "room_not_found", "Room not found."));
     };
 
+    // ...and only the connection that joined this room as `sender` may play in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[RPS] {} is not in room {}", sender, game_id);
+        let refusal = build_error_payload(game_id, None, "identity_mismatch", "You can only play for yourself.");
+        gameroom_handler::send_direct(user_tx, &refusal);
+        return None;
+    }
+
+    let phase = room.lobby.phase;
     let game = match &mut room.game {
         GameType::RockPaperScissors(model) => model,
+        GameType::RpsTournament(tournament) => {
+            let deadline_of = |t: &mut TournamentModel| t.match_for_mut(sender).and_then(|m| m.game.reveal_deadline);
+            let had_deadline = deadline_of(tournament);
+            let reply = tournament_choice(&game_id, &payload, tournament, phase);
+            if let Some(deadline) = deadline_of(tournament).filter(|d| had_deadline != Some(*d)) {
+                tokio::spawn(run_reveal_deadline(app_state.clone(), game_id.clone(), deadline));
+            }
+            return Some(reply);
+        }
         _ => {
-            return build_error_payload(
+            return Some(build_error_payload(
                 game_id,
                 None,
                 "wrong_game_type",
                 "This room is not a RockPaperScissors game.",
-            );
+            ));
         }
     };
 
-    // Assign player names from the room roster if they haven't been set yet.
-    if game.player1_name.is_none() && !room.users.is_empty() {
-        game.player1_name = Some(room.users[0].clone());
-    }
-    if game.player2_name.is_none() && room.users.len() > 1 {
-        game.player2_name = Some(room.users[1].clone());
-    }
-
-    if !game.both_players_joined() {
-        return ServerMessage::RockPaperScissors(RockPaperScissorsPayloadToClient {
+    // Players are seated by the lobby; no choices until everyone is ready and the game started
+    if phase != LobbyPhase::InGame {
+        return Some(ServerMessage::RockPaperScissors(RockPaperScissorsPayloadToClient {
             game_id,
             player1: game.player1_name.clone(),
             player2: game.player2_name.clone(),
@@ -54,38 +84,109 @@ pub async fn rockpaperscissors_handler(
             player2_choice: None,
             status: "waiting_for_opponent".to_string(),
             winner: None,
-            message: Some("Waiting for another player to join.".to_string()),
-        });
+            message: Some("Waiting for both players to ready up.".to_string()),
+            series: None,
+            available_choices: Some(game.rules.choices.clone()),
+            commit_reveal: None,
+        }));
     }
 
-    if let Some(choice_str) = payload.choice.as_deref() {
-        let Some(choice) = RpsChoice::from_str(choice_str) else {
-            return build_error_payload(
-                game_id,
-                Some(&*game),
-                "invalid_choice",
-                "Choice must be rock, paper, or scissors.",
-            );
-        };
+    let had_deadline = game.reveal_deadline;
+    if let Err(reason) = apply_move(game, &payload) {
+        let message = move_error_message(reason, &game.rules);
+        return Some(build_error_payload(game_id, Some(&*game), reason, &message));
+    }
+    // The second commitment starts the reveal clock
+    if let Some(deadline) = game.reveal_deadline.filter(|d| had_deadline != Some(*d)) {
+        tokio::spawn(run_reveal_deadline(app_state.clone(), game_id.clone(), deadline));
+    }
 
-        if let Err(_) = game.submit_choice(&payload.player_name, choice) {
-            return build_error_payload(
-                game_id,
-                Some(&*game),
-                "unknown_player",
-                "Only players in this room may submit choices.",
-            );
+    Some(ServerMessage::RockPaperScissors(build_state_payload(&game_id, game)))
+}
+
+/// Apply whatever the payload carries (a commitment, a reveal or a plain choice) to one RPS
+/// game, resolving the round once both choices are in. No choice or commitment is a state query.
+fn apply_move(game: &mut RockPaperScissorsModel, payload: &RockPaperScissorsPayloadToServer) -> Result<(), &'static str> {
+    let player = payload.player_name.as_str();
+
+    if let Some(commitment) = payload.commitment.as_deref() {
+        game.submit_commitment(player, commitment, Instant::now())?;
+    } else if let Some(choice_str) = payload.choice.as_deref() {
+        if game.commit_reveal {
+            let nonce = payload.nonce.as_deref().ok_or("missing_nonce")?;
+            game.reveal(player, choice_str, nonce)?;
+        } else {
+            let choice = game.rules.parse_choice(choice_str).ok_or("invalid_choice")?;
+            game.submit_choice(player, choice)?;
         }
     }
 
     if game.both_choices_made() {
         game.resolve_round();
     }
+    Ok(())
+}
+
+fn move_error_message(reason: &str, rules: &RuleSet) -> String {
+    match reason {
+        "invalid_choice" => format!("Choice must be one of: {}.", rules.choices.join(", ")),
+        "round_complete" => "This round is over. Reset to play the next round.".to_string(),
+        "commitment_required" => "This room uses commit-reveal: send a commitment first.".to_string(),
+        "commit_reveal_disabled" => "This room does not use commit-reveal.".to_string(),
+        "already_committed" => "You already committed this round.".to_string(),
+        "invalid_commitment" => "Commitment must be the hex SHA-256 of \"<choice>:<nonce>\".".to_string(),
+        "waiting_for_commitments" => "Wait for both commitments before revealing.".to_string(),
+        "already_revealed" => "You already revealed this round.".to_string(),
+        "missing_nonce" => "A reveal needs the nonce used in your commitment.".to_string(),
+        "commitment_mismatch" => "Reveal does not match your commitment.".to_string(),
+        "nonce_too_short" => format!("Nonces must be at least {} characters.", MIN_NONCE_LEN),
+        "tournament_over" => "The tournament is over.".to_string(),
+        "no_active_match" => "You have no match to play right now.".to_string(),
+        _ => "Only players in this room may submit choices.".to_string(),
+    }
+}
+
+/// Spawned when both players have committed. Once `deadline` passes, whoever hasn't revealed
+/// forfeits the round. A stale timer does nothing: each new round gets its own deadline.
+pub async fn run_reveal_deadline(state: Arc<AppState>, game_id: String, deadline: Instant) {
+    tokio::time::sleep_until(deadline.into()).await;
+    {
+        let mut rooms = state.rooms.write().await;
+        let Some(room) = rooms.get_mut(&game_id) else { return };
+        let now = Instant::now();
+        let update = match &mut room.game {
+            GameType::RockPaperScissors(game) => {
+                if !game.forfeit_unrevealed(now) {
+                    return;
+                }
+                ServerMessage::RockPaperScissors(build_state_payload(&game_id, game))
+            }
+            GameType::RpsTournament(tournament) => {
+                if !tournament.forfeit_unrevealed(now) {
+                    return;
+                }
+                tournament_snapshot(&game_id, tournament)
+            }
+            _ => return,
+        };
+        println!("[RPS] Reveal deadline passed in room {}", game_id);
+        room.broadcast(&update);
+    }
+    gameroom_handler::record_result(&state, &game_id).await;
+}
 
-    ServerMessage::RockPaperScissors(build_state_payload(&game_id, game))
+/// Commitments are shown as they arrive; nonces only once the round is decided
+fn commit_reveal_payload(game: &RockPaperScissorsModel, round_decided: bool) -> Option<CommitRevealPayload> {
+    if !game.commit_reveal {
+        return None;
+    }
+    Some(CommitRevealPayload {
+        commitments: game.commitments.to_vec(),
+        nonces: if round_decided { game.nonces.to_vec() } else { vec![None, None] },
+    })
 }
 
-fn build_state_payload(game_id: &str, game: &RockPaperScissorsModel) -> RockPaperScissorsPayloadToClient {
+pub fn build_state_payload(game_id: &str, game: &RockPaperScissorsModel) -> RockPaperScissorsPayloadToClient {
     let reveal_choices = game.both_choices_made() && game.winner != RpsRoundResult::Pending;
 
     let (status, message) = if !game.both_players_joined() {
@@ -93,16 +194,41 @@ fn build_state_payload(game_id: &str, game: &RockPaperScissorsModel) -> RockPape
             "waiting_for_opponent".to_string(),
             Some("Waiting for another player to join.".to_string()),
         )
+    } else if game.decided_by_forfeit() {
+        let msg = match game.winner_name() {
+            Some(name) if game.series.is_over() && game.series.best_of > 1 => {
+                format!("Only {} revealed in time and wins the series!", name)
+            }
+            Some(name) => format!("Only {} revealed in time and wins this round!", name),
+            None => "Neither player revealed in time. Round tied.".to_string(),
+        };
+        ("round_complete".to_string(), Some(msg))
     } else if reveal_choices {
         let msg = match game.winner {
             RpsRoundResult::Tie => "Round ended in a tie.".to_string(),
-            RpsRoundResult::Player1 | RpsRoundResult::Player2 => match game.winner_name() {
-                Some(name) => format!("{} wins this round!", name),
-                None => "Round complete.".to_string(),
-            },
+            RpsRoundResult::Player1 | RpsRoundResult::Player2 => {
+                let how = game.round_description().unwrap_or_default();
+                match game.winner_name() {
+                    Some(name) if game.series.is_over() && game.series.best_of > 1 => {
+                        format!("{}. {} wins the series!", how, name)
+                    }
+                    Some(name) => format!("{}. {} wins this round!", how, name),
+                    None => "Round complete.".to_string(),
+                }
+            }
             RpsRoundResult::Pending => "Round in progress.".to_string(),
         };
         ("round_complete".to_string(), Some(msg))
+    } else if game.commit_reveal && !game.both_committed() {
+        (
+            "waiting_for_commitments".to_string(),
+            Some("Waiting for both players to commit.".to_string()),
+        )
+    } else if game.commit_reveal {
+        (
+            "waiting_for_reveals".to_string(),
+            Some("Both players committed. Reveal your choice and nonce.".to_string()),
+        )
     } else if game.player1_choice.is_some() || game.player2_choice.is_some() {
         (
             "waiting_for_opponent_choice".to_string(),
@@ -111,7 +237,7 @@ fn build_state_payload(game_id: &str, game: &RockPaperScissorsModel) -> RockPape
     } else {
         (
             "waiting_for_choices".to_string(),
-            Some("Choose rock, paper, or scissors.".to_string()),
+            Some(format!("Choose {}.", game.rules.choices.join(", "))),
         )
     };
 
@@ -120,12 +246,12 @@ fn build_state_payload(game_id: &str, game: &RockPaperScissorsModel) -> RockPape
         player1: game.player1_name.clone(),
         player2: game.player2_name.clone(),
         player1_choice: if reveal_choices {
-            game.player1_choice.map(|c| c.as_str().to_string())
+            game.player1_choice.map(|c| game.rules.name_of(c).to_string())
         } else {
             None
         },
         player2_choice: if reveal_choices {
-            game.player2_choice.map(|c| c.as_str().to_string())
+            game.player2_choice.map(|c| game.rules.name_of(c).to_string())
         } else {
             None
         },
@@ -137,7 +263,84 @@ fn build_state_payload(game_id: &str, game: &RockPaperScissorsModel) -> RockPape
             RpsRoundResult::Pending => None,
         },
         message,
+        series: Some(game.series.payload(game.player1_name.as_ref(), game.player2_name.as_ref())),
+        available_choices: Some(game.rules.choices.clone()),
+        commit_reveal: commit_reveal_payload(game, reveal_choices),
+    }
+}
+
+/// A choice (or state query) from a player in a tournament room. The server routes it to the
+/// sender's live match; finished rounds of an undecided match roll over automatically.
+fn tournament_choice(
+    game_id: &str,
+    payload: &RockPaperScissorsPayloadToServer,
+    tournament: &mut TournamentModel,
+    phase: LobbyPhase,
+) -> ServerMessage {
+    if phase != LobbyPhase::InGame {
+        return build_tournament_payload(game_id, tournament, "waiting_for_players", Some("Waiting for everyone to ready up."));
+    }
+
+    let has_move = payload.choice.is_some() || payload.commitment.is_some();
+    if has_move && let Err(reason) = tournament.play(&payload.player_name, |game| apply_move(game, payload)) {
+        let message = move_error_message(reason, &tournament.rules);
+        return build_tournament_payload(game_id, tournament, reason, Some(&message));
     }
+
+    tournament_snapshot(game_id, tournament)
+}
+
+/// Bracket with the tournament's own status ("in_progress" / "complete")
+pub fn tournament_snapshot(game_id: &str, tournament: &TournamentModel) -> ServerMessage {
+    let message = tournament.champion.as_ref().map(|name| format!("{} wins the tournament!", name));
+    let status = if tournament.champion.is_some() { "complete" } else { "in_progress" };
+    build_tournament_payload(game_id, tournament, status, message.as_deref())
+}
+
+/// Full bracket: every match as an RPS snapshot plus standings
+fn build_tournament_payload(
+    game_id: &str,
+    tournament: &TournamentModel,
+    status: &str,
+    message: Option<&str>,
+) -> ServerMessage {
+    let rounds = tournament
+        .rounds
+        .iter()
+        .map(|round| {
+            round
+                .iter()
+                .map(|m| {
+                    let mut snapshot = build_state_payload(game_id, &m.game);
+                    if m.bye {
+                        snapshot.status = "bye".to_string();
+                        snapshot.winner = m.winner().cloned();
+                        snapshot.message = None;
+                    }
+                    snapshot.available_choices = None;
+                    snapshot
+                })
+                .collect()
+        })
+        .collect();
+
+    let standings = tournament
+        .standings()
+        .into_iter()
+        .map(|(player, match_wins, game_wins)| RpsStandingPayload { player, match_wins, game_wins })
+        .collect();
+
+    ServerMessage::RpsTournament(RpsTournamentPayloadToClient {
+        game_id: game_id.to_string(),
+        format: tournament.format.as_str().to_string(),
+        status: status.to_string(),
+        current_round: tournament.current_round,
+        rounds,
+        standings,
+        champion: tournament.champion.clone(),
+        available_choices: tournament.rules.choices.clone(),
+        message: message.map(|m| m.to_string()),
+    })
 }
 
 fn build_error_payload(
@@ -161,5 +364,115 @@ fn build_error_payload(
         status: status.to_string(),
         winner: None,
         message: Some(message.to_string()),
+        series: game.map(|model| model.series.payload(model.player1_name.as_ref(), model.player2_name.as_ref())),
+        available_choices: game.map(|model| model.rules.choices.clone()),
+        commit_reveal: game.and_then(|model| commit_reveal_payload(model, false)),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::rockpaperscissors::model::commitment_hash;
+    use crate::routes::gameroom_handler::{member_tx, started_test_room};
+
+    /// Whether player 1 and player 2 have chosen in `game_id`
+    fn choices(state: &Arc<AppState>, game_id: &str) -> (bool, bool) {
+        let rooms = state.rooms.try_read().unwrap();
+        let GameType::RockPaperScissors(game) = &rooms[game_id].game else { unreachable!() };
+        (game.player1_choice.is_some(), game.player2_choice.is_some())
+    }
+
+    fn choose(player_name: &str, choice: &str) -> RockPaperScissorsPayloadToServer {
+        RockPaperScissorsPayloadToServer {
+            game_id: "room".into(),
+            player_name: player_name.into(),
+            choice: Some(choice.into()),
+            commitment: None,
+            nonce: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn choosing_for_the_opponent_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "rockpaperscissors", "room", &["alice", "bob"]).await;
+        let bob_tx = member_tx(&state, "room", "bob").await;
+
+        let Some(ServerMessage::RockPaperScissors(reply)) =
+            rockpaperscissors_handler(choose("alice", "scissors"), &state, "bob", &bob_tx).await
+        else {
+            panic!("expected an RPS reply");
+        };
+        assert_eq!(reply.status, "identity_mismatch");
+        assert_eq!(choices(&state, "room"), (false, false));
+
+        rockpaperscissors_handler(choose("bob", "rock"), &state, "bob", &bob_tx).await;
+        assert_eq!(choices(&state, "room"), (false, true));
+    }
+
+    #[tokio::test]
+    async fn choosing_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let mut connections = started_test_room(&state, "rockpaperscissors", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "rockpaperscissors", "other", &["alice", "carol"]).await;
+
+        // The "alice" of the first room tries to choose for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        let payload = RockPaperScissorsPayloadToServer { game_id: "other".into(), ..choose("alice", "rock") };
+        assert!(rockpaperscissors_handler(payload, &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(choices(&state, "other"), (false, false));
+
+        let mut refused = false;
+        while let Ok(Message::Text(text)) = connections[0].try_recv() {
+            refused |= text.contains("identity_mismatch");
+        }
+        assert!(refused);
+    }
+
+    #[tokio::test]
+    async fn a_player_who_never_reveals_forfeits_at_the_deadline() {
+        let state = Arc::new(AppState::default());
+        let mut connections = started_test_room(&state, "rockpaperscissors", "room", &["alice", "bob"]).await;
+        {
+            let mut rooms = state.rooms.write().await;
+            let GameType::RockPaperScissors(game) = &mut rooms.get_mut("room").unwrap().game else { unreachable!() };
+            game.commit_reveal = true;
+        }
+        let (alice_tx, bob_tx) = (member_tx(&state, "room", "alice").await, member_tx(&state, "room", "bob").await);
+        let commit = |player: &str, choice: &str, nonce: &str| RockPaperScissorsPayloadToServer {
+            choice: None,
+            commitment: Some(commitment_hash(choice, nonce)),
+            ..choose(player, choice)
+        };
+
+        rockpaperscissors_handler(commit("alice", "rock", "alice-nonce"), &state, "alice", &alice_tx).await;
+        rockpaperscissors_handler(commit("bob", "paper", "bob-nonce"), &state, "bob", &bob_tx).await;
+        let short = RockPaperScissorsPayloadToServer { nonce: Some("bob".into()), ..choose("bob", "paper") };
+        let Some(ServerMessage::RockPaperScissors(reply)) = rockpaperscissors_handler(short, &state, "bob", &bob_tx).await else {
+            panic!("expected an RPS reply");
+        };
+        assert_eq!(reply.status, "nonce_too_short");
+        let reveal = RockPaperScissorsPayloadToServer { nonce: Some("bob-nonce".into()), ..choose("bob", "paper") };
+        rockpaperscissors_handler(reveal, &state, "bob", &bob_tx).await;
+
+        // Pretend the 30 seconds are up
+        let deadline = Instant::now();
+        {
+            let mut rooms = state.rooms.write().await;
+            let GameType::RockPaperScissors(game) = &mut rooms.get_mut("room").unwrap().game else { unreachable!() };
+            assert!(game.reveal_deadline.is_some());
+            game.reveal_deadline = Some(deadline);
+        }
+        run_reveal_deadline(state.clone(), "room".into(), deadline).await;
+
+        let rooms = state.rooms.read().await;
+        let GameType::RockPaperScissors(game) = &rooms["room"].game else { unreachable!() };
+        assert_eq!(game.winner_name(), Some("bob"));
+        let mut announced = false;
+        while let Ok(Message::Text(text)) = connections[0].try_recv() {
+            announced |= text.contains("Only bob revealed in time");
+        }
+        assert!(announced);
+    }
+}
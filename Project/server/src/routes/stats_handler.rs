@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+use crate::models::{
+    appstate::AppState,
+    ratings::{PlayerStats, DEFAULT_LEADERBOARD_SIZE, MAX_LEADERBOARD_SIZE, RATED_GAMES},
+};
+use crate::types::{LeaderboardEntryPayload, LeaderboardResponse, PlayerRatingPayload, PlayerStatsResponse};
+
+/// Query string of `GET /leaderboard/{game}`
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardParams {
+    pub limit: Option<usize>, // Default 10, at most 100
+}
+
+/// `GET /players/{name}/stats`: ratings and records in every rated game
+pub async fn player_stats_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> (StatusCode, Json<PlayerStatsResponse>) {
+    let (code, status, games) = match state.ratings.player_stats(&name) {
+        Ok(games) if games.is_empty() => (StatusCode::NOT_FOUND, "unknown_player", Default::default()),
+        Ok(games) => {
+            let games = games.iter().map(|(game, stats)| (game.clone(), rating_payload(stats))).collect();
+            (StatusCode::OK, "ok", games)
+        }
+        Err(reason) => (StatusCode::INTERNAL_SERVER_ERROR, reason, Default::default()),
+    };
+    (code, Json(PlayerStatsResponse { status: status.to_string(), player_name: name, games }))
+}
+
+/// `GET /leaderboard/{game}?limit=N`: best-rated players of one game
+pub async fn leaderboard_handler(
+    State(state): State<Arc<AppState>>,
+    Path(game): Path<String>,
+    Query(params): Query<LeaderboardParams>,
+) -> (StatusCode, Json<LeaderboardResponse>) {
+    if !RATED_GAMES.contains(&game.as_str()) {
+        let response = LeaderboardResponse { status: "unrated_game".to_string(), game, players: Vec::new() };
+        return (StatusCode::NOT_FOUND, Json(response));
+    }
+
+    let limit = params.limit.unwrap_or(DEFAULT_LEADERBOARD_SIZE).clamp(1, MAX_LEADERBOARD_SIZE);
+    let (code, status, players) = match state.ratings.leaderboard(&game, limit) {
+        Ok(players) => {
+            let players = players
+                .iter()
+                .enumerate()
+                .map(|(i, (name, stats))| LeaderboardEntryPayload {
+                    rank: i + 1,
+                    player_name: name.clone(),
+                    stats: rating_payload(stats),
+                })
+                .collect();
+            (StatusCode::OK, "ok", players)
+        }
+        Err(reason) => (StatusCode::INTERNAL_SERVER_ERROR, reason, Vec::new()),
+    };
+    (code, Json(LeaderboardResponse { status: status.to_string(), game, players }))
+}
+
+fn rating_payload(stats: &PlayerStats) -> PlayerRatingPayload {
+    PlayerRatingPayload {
+        rating: stats.rating.round() as i32,
+        games_played: stats.games_played,
+        wins: stats.wins,
+        losses: stats.losses,
+        draws: stats.draws,
+        streak: stats.streak,
+        best_streak: stats.best_streak,
+    }
+}
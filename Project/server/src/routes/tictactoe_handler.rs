@@ -1,16 +1,25 @@
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use crate::types::{ServerMessage, TicTacToePayloadToClient, TicTacToePayloadToServer};
-use crate::models::gameroom::{GameRoom, GameType};
-use crate::models::tictactoe::model::{TicTacToeModel, Player, GameWinner};
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+use crate::routes::gameroom_handler;
+use crate::types::{
+    ServerMessage, TicTacToeMovePayload, TicTacToePayloadToClient, TicTacToePayloadToServer,
+    UltimateTicTacToePayloadToClient,
+};
+use crate::models::gameroom::GameType;
+use crate::models::lobby::LobbyPhase;
+use crate::models::tictactoe::model::{format_coordinate, parse_coordinate, GameWinner, Player, TicTacToeModel};
+use crate::models::tictactoe::ultimate::UltimateTicTacToeModel;
 use crate::models::appstate::AppState;
 
-/// Handles incoming TicTacToe messages from clients.
+/// Handles incoming TicTacToe messages from clients. Returns None when the reply was sent
+/// to the sender alone rather than for broadcasting.
 pub async fn tictactoe_handler(
     payload: TicTacToePayloadToServer,
     app_state: &Arc<AppState>,
-    current_room: Arc<RwLock<Option<String>>>,
-) -> ServerMessage {
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
     // ✅ FIXED: Use game_id from payload instead of current_room
     let game_id = payload.game_id.clone();
     
@@ -20,101 +29,224 @@ pub async fn tictactoe_handler(
         Some(r) => r,
         None => {
             eprintln!("[TicTacToe] Room not found: {}", game_id);
-            return ServerMessage::TicTacToe(TicTacToePayloadToClient {
-                board: Some(vec![vec![0, 0, 0], vec![0, 0, 0], vec![0, 0, 0]]),
-                whos_turn: Some("".to_string()),
-                status: Some("room_not_found".to_string()),
-            });
+            return Some(status_only(&TicTacToeModel::new(), "room_not_found"));
         }
     };
 
+    // Only the connection that joined this room as `sender` may move in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[TicTacToe] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &status_only(&TicTacToeModel::new(), "identity_mismatch"));
+        return None;
+    }
+
     // Extract the TicTacToe model from the room
+    let phase = room.lobby.phase;
     let game = match &mut room.game {
         GameType::TicTacToe(m) => m,
+        GameType::UltimateTicTacToe(m) => return Some(ultimate_move(&payload, m, phase, sender)),
         _ => {
             eprintln!("Tried to play TicTacToe in a non-TicTacToe room: {}", game_id);
-            return ServerMessage::TicTacToe(TicTacToePayloadToClient {
-                board: Some(vec![vec![0, 0, 0], vec![0, 0, 0], vec![0, 0, 0]]),
-                whos_turn: Some("".to_string()),
-                status: Some("wrong_game_type".to_string()),
-            });
+            return Some(status_only(&TicTacToeModel::new(), "wrong_game_type"));
         }
     };
 
-    // ✅ FIXED: Assign players if not yet assigned
-    if game.player1_name.is_none() && !room.users.is_empty() {
-        game.player1_name = Some(room.users[0].clone());
+    // Players are seated by the lobby; no moves until everyone is ready and the game started
+    if phase != LobbyPhase::InGame {
+        return Some(status_only(game, "waiting_for_players"));
+    }
+
+    // The game is decided; wait for a reset (next game) or rematch
+    if game.winner != GameWinner::Pending {
+        return Some(build_snapshot(game));
     }
-    if game.player2_name.is_none() && room.users.len() > 1 {
-        game.player2_name = Some(room.users[1].clone());
+
+    // The connection decides who is moving; a payload naming someone else is refused
+    if payload.whos_turn != sender {
+        eprintln!("[TicTacToe] {} tried to move as {}", sender, payload.whos_turn);
+        return Some(status_only(game, "identity_mismatch"));
     }
 
     // Determine which Player this move is from
-    let player = match game.get_player_from_name(&payload.whos_turn) {
+    let player = match game.get_player_from_name(sender) {
         Some(p) => p,
         None => {
             eprintln!("[TicTacToe] Unknown player: {}", payload.whos_turn);
-            return ServerMessage::TicTacToe(TicTacToePayloadToClient {
-                board: Some(serialize_board_as_numbers(game)),
-                whos_turn: game.current_player_name().map(|s| s.to_string()),
-                status: Some("unknown_player".to_string()),
-            });
+            return Some(status_only(game, "unknown_player"));
         }
     };
 
     // Check if it's actually this player's turn
     if player != game.whos_turn {
         eprintln!("[TicTacToe] Not {}'s turn", payload.whos_turn);
-        return ServerMessage::TicTacToe(TicTacToePayloadToClient {
-            board: Some(serialize_board_as_numbers(game)),
-            whos_turn: game.current_player_name().map(|s| s.to_string()),
-            status: Some("not_your_turn".to_string()),
-        });
+        return Some(status_only(game, "not_your_turn"));
     }
 
-    // Parse choice like "A1"
-    let (row, col) = match parse_choice(&payload.choice) {
+    // Parse choice like "A1" (row letter, column number)
+    let (row, col) = match game.parse_cell(&payload.choice) {
         Some(rc) => rc,
         None => {
             eprintln!("[TicTacToe] Invalid choice: {}", payload.choice);
-            return ServerMessage::TicTacToe(TicTacToePayloadToClient {
-                board: Some(serialize_board_as_numbers(game)),
-                whos_turn: game.current_player_name().map(|s| s.to_string()),
-                status: Some("invalid_choice".to_string()),
-            });
+            return Some(status_only(game, "invalid_choice"));
         }
     };
 
     if !game.validate_choice(row, col) {
         eprintln!("[TicTacToe] Invalid move at ({}, {})", row, col);
-        return ServerMessage::TicTacToe(TicTacToePayloadToClient {
-            board: Some(serialize_board_as_numbers(game)),
-            whos_turn: game.current_player_name().map(|s| s.to_string()),
-            status: Some("invalid_move".to_string()),
-        });
+        return Some(status_only(game, "invalid_move"));
     }
 
-    // Make the move
-    game.mark_spot(row, col);
-    game.check_winner();
+    // Wild / Order and Chaos let the mover pick X or O
+    let mark = match game.resolve_mark(payload.mark.as_deref()) {
+        Ok(mark) => mark,
+        Err(reason) => {
+            eprintln!("[TicTacToe] Bad mark {:?} from {}", payload.mark, payload.whos_turn);
+            return Some(status_only(game, reason));
+        }
+    };
+
+    // Make the move, keeping the previous state for takebacks
+    let before = GameType::TicTacToe(game.clone());
+    game.mark_spot(row, col, mark);
+    game.check_winner(row, col);
     
     println!("[TicTacToe] {} made move at {}", payload.whos_turn, payload.choice);
     println!("[TicTacToe] Winner status: {:?}", game.winner);
+    if let Some(name) = game.winner_name() {
+        println!("[TicTacToe] {} wins!", name);
+    }
 
     if game.winner == GameWinner::Pending {
         game.next_turn();
+    } else {
+        game.record_result();
+    }
+
+    let snapshot = build_snapshot(game);
+    room.takebacks.record(&payload.whos_turn, before);
+    Some(snapshot)
+}
+
+/// A move in an Ultimate TicTacToe room; `choice` is a cell on the 9x9 grid
+fn ultimate_move(
+    payload: &TicTacToePayloadToServer,
+    game: &mut UltimateTicTacToeModel,
+    phase: LobbyPhase,
+    sender: &str,
+) -> ServerMessage {
+    if phase != LobbyPhase::InGame {
+        return ultimate_payload(game, "waiting_for_players");
+    }
+    if game.meta.winner != GameWinner::Pending {
+        return build_ultimate_snapshot(game);
     }
 
+    if payload.whos_turn != sender {
+        eprintln!("[UltimateTicTacToe] {} tried to move as {}", sender, payload.whos_turn);
+        return ultimate_payload(game, "identity_mismatch");
+    }
+
+    match game.meta.get_player_from_name(sender) {
+        None => {
+            eprintln!("[UltimateTicTacToe] Unknown player: {}", payload.whos_turn);
+            return ultimate_payload(game, "unknown_player");
+        }
+        Some(player) if player != game.meta.whos_turn => {
+            eprintln!("[UltimateTicTacToe] Not {}'s turn", payload.whos_turn);
+            return ultimate_payload(game, "not_your_turn");
+        }
+        Some(_) => {}
+    }
+
+    let Some((row, col)) = parse_coordinate(&payload.choice, 9, 9) else {
+        eprintln!("[UltimateTicTacToe] Invalid choice: {}", payload.choice);
+        return ultimate_payload(game, "invalid_choice");
+    };
+
+    // "wrong_board", "board_closed" or "invalid_move"
+    if let Err(reason) = game.play(row, col) {
+        eprintln!("[UltimateTicTacToe] Rejected move {} by {}: {}", payload.choice, payload.whos_turn, reason);
+        return ultimate_payload(game, reason);
+    }
+
+    println!("[UltimateTicTacToe] {} made move at {}", payload.whos_turn, payload.choice);
+    if let Some(name) = game.meta.winner_name() {
+        println!("[UltimateTicTacToe] {} wins!", name);
+    }
+
+    build_ultimate_snapshot(game)
+}
+
+// --- Utility functions ---
+
+/// Current board, turn and status for broadcasting
+pub fn build_snapshot(game: &TicTacToeModel) -> ServerMessage {
     ServerMessage::TicTacToe(TicTacToePayloadToClient {
         board: Some(serialize_board_as_numbers(game)),
         whos_turn: game.current_player_name().map(|s| s.to_string()),
         status: Some(format_status_with_names(game)),
+        series: Some(game.series.payload(game.player1_name.as_ref(), game.player2_name.as_ref())),
+        variant: Some(game.variant.as_str().to_string()),
+        winning_line: (!game.winning_line.is_empty())
+            .then(|| game.winning_line.iter().map(|&(r, c)| [r, c]).collect()),
+        moves: Some(serialize_moves(game)),
     })
 }
 
-// --- Utility functions ---
+/// Board and turn with a waiting or refusal status, for replies that change nothing
+fn status_only(game: &TicTacToeModel, status: &str) -> ServerMessage {
+    ServerMessage::TicTacToe(TicTacToePayloadToClient {
+        board: Some(serialize_board_as_numbers(game)),
+        whos_turn: game.current_player_name().map(|s| s.to_string()),
+        status: Some(status.to_string()),
+        series: None,
+        variant: Some(game.variant.as_str().to_string()),
+        winning_line: None,
+        moves: None,
+    })
+}
+
+/// Move history with player names and cell names
+fn serialize_moves(game: &TicTacToeModel) -> Vec<TicTacToeMovePayload> {
+    game.moves
+        .iter()
+        .map(|m| TicTacToeMovePayload {
+            player: match m.player {
+                Player::Player1 => game.player1_name.clone(),
+                Player::Player2 => game.player2_name.clone(),
+            }
+            .unwrap_or_default(),
+            cell: format_coordinate(m.row, m.col),
+            mark: if m.mark == 1 { "X" } else { "O" }.to_string(),
+            turn: m.turn,
+        })
+        .collect()
+}
+
+/// Both board levels, turn, status and series for broadcasting
+pub fn build_ultimate_snapshot(game: &UltimateTicTacToeModel) -> ServerMessage {
+    let mut msg = ultimate_payload(game, &format_status_with_names(&game.meta));
+    if let ServerMessage::UltimateTicTacToe(payload) = &mut msg {
+        payload.series = Some(game.meta.series.payload(game.meta.player1_name.as_ref(), game.meta.player2_name.as_ref()));
+    }
+    msg
+}
 
-/// ✅ FIXED: Serialize board as Vec<Vec<i32>> (number[][])
+fn ultimate_payload(game: &UltimateTicTacToeModel, status: &str) -> ServerMessage {
+    let to_numbers = |board: &Vec<Vec<i8>>| -> Vec<Vec<i32>> {
+        board.iter().map(|row| row.iter().map(|&v| v as i32).collect()).collect()
+    };
+    ServerMessage::UltimateTicTacToe(UltimateTicTacToePayloadToClient {
+        board: to_numbers(&game.full_board()),
+        meta_board: to_numbers(&game.meta.board),
+        forced_board: game.forced_board,
+        whos_turn: game.meta.current_player_name().map(|s| s.to_string()),
+        status: status.to_string(),
+        series: None,
+    })
+}
+
+/// ✅ FIXED: Serialize board as Vec<Vec<i32>> (number[][]), rows x cols
 fn serialize_board_as_numbers(game: &TicTacToeModel) -> Vec<Vec<i32>> {
     game.board
         .iter()
@@ -135,17 +267,65 @@ fn format_status_with_names(game: &TicTacToeModel) -> String {
     }
 }
 
-fn parse_choice(choice: &str) -> Option<(usize, usize)> {
-    match choice.to_uppercase().as_str() {
-        "A1" => Some((0, 0)),
-        "A2" => Some((0, 1)),
-        "A3" => Some((0, 2)),
-        "B1" => Some((1, 0)),
-        "B2" => Some((1, 1)),
-        "B3" => Some((1, 2)),
-        "C1" => Some((2, 0)),
-        "C2" => Some((2, 1)),
-        "C3" => Some((2, 2)),
-        _ => None,
-    }
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, started_test_room};
+
+    fn mv(whos_turn: &str, choice: &str) -> TicTacToePayloadToServer {
+        TicTacToePayloadToServer { game_id: "room".into(), whos_turn: whos_turn.into(), choice: choice.into(), mark: None }
+    }
+
+    #[tokio::test]
+    async fn moving_for_the_opponent_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "tictactoe", "room", &["alice", "bob"]).await;
+        let (mover, other) = {
+            let rooms = state.rooms.read().await;
+            let GameType::TicTacToe(game) = &rooms["room"].game else { unreachable!() };
+            let mover = game.current_player_name().unwrap().to_string();
+            let other = if mover == "alice" { "bob" } else { "alice" };
+            (mover, other.to_string())
+        };
+
+        // The waiting player's connection names the player to move
+        let other_tx = member_tx(&state, "room", &other).await;
+        let Some(ServerMessage::TicTacToe(reply)) = tictactoe_handler(mv(&mover, "A1"), &state, &other, &other_tx).await else {
+            panic!("expected a TicTacToe reply");
+        };
+        assert_eq!(reply.status.as_deref(), Some("identity_mismatch"));
+        assert_eq!(reply.board, Some(vec![vec![0; 3]; 3]));
+
+        let mover_tx = member_tx(&state, "room", &mover).await;
+        let Some(ServerMessage::TicTacToe(reply)) = tictactoe_handler(mv(&mover, "A1"), &state, &mover, &mover_tx).await else {
+            panic!("expected a TicTacToe reply");
+        };
+        assert_eq!(reply.status.as_deref(), Some("IN_PROGRESS"));
+    }
+
+    #[tokio::test]
+    async fn moving_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let mut connections = started_test_room(&state, "tictactoe", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "tictactoe", "other", &["alice", "carol"]).await;
+        {
+            let mut rooms = state.rooms.write().await;
+            let GameType::TicTacToe(game) = &mut rooms.get_mut("other").unwrap().game else { unreachable!() };
+            game.whos_turn = game.get_player_from_name("alice").unwrap();
+        }
+
+        // The "alice" of the first room tries to move for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        let payload = TicTacToePayloadToServer { game_id: "other".into(), ..mv("alice", "A1") };
+        assert!(tictactoe_handler(payload, &state, "alice", &alice_tx).await.is_none());
+
+        let rooms = state.rooms.read().await;
+        let GameType::TicTacToe(game) = &rooms["other"].game else { unreachable!() };
+        assert!(game.moves.is_empty());
+        let mut refused = false;
+        while let Ok(Message::Text(text)) = connections[0].try_recv() {
+            refused |= text.contains("identity_mismatch");
+        }
+        assert!(refused);
+    }
+}
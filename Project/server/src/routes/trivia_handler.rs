@@ -0,0 +1,172 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::models::{
+    appstate::AppState,
+    gameroom::GameType,
+    trivia::model::{TriviaModel, TriviaPhase, REVEAL_SECS},
+};
+use crate::routes::gameroom_handler;
+use crate::types::{TriviaPayloadToClient, TriviaPayloadToServer, TriviaResultPayload, TriviaScorePayload, ServerMessage};
+
+/// Handles Trivia answers. Questions, countdown ticks and reveals come from `run_quiz`.
+pub async fn trivia_handler(
+    payload: TriviaPayloadToServer,
+    app_state: &Arc<AppState>,
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage> {
+    let game_id = payload.game_id.clone();
+    let mut rooms = app_state.rooms.write().await;
+
+    let Some(room) = rooms.get_mut(&game_id) else {
+        eprintln!("[Trivia] Room not found: {}", game_id);
+        return Some(build_view(&game_id, &TriviaModel::new(), "room_not_found"));
+    };
+
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[Trivia] {} is not in room {}", sender, game_id);
+        gameroom_handler::send_direct(user_tx, &build_view(&game_id, &TriviaModel::new(), "identity_mismatch"));
+        return None;
+    }
+
+    let GameType::Trivia(game) = &mut room.game else {
+        eprintln!("Tried to play Trivia in a non-Trivia room: {}", game_id);
+        return Some(build_view(&game_id, &TriviaModel::new(), "wrong_game_type"));
+    };
+
+    // No choice: just send the latest state
+    let Some(choice) = payload.choice else {
+        return Some(build_view(&game_id, game, "ok"));
+    };
+
+    if let Err(reason) = game.answer(&payload.player_name, choice, Instant::now()) {
+        eprintln!("[Trivia] {} can't answer: {}", payload.player_name, reason);
+        return Some(build_view(&game_id, game, reason));
+    }
+    // Only who has answered goes out; choices stay hidden until the reveal
+    println!("[Trivia] {} answered in room {}", payload.player_name, game_id);
+    Some(build_view(&game_id, game, "ok"))
+}
+
+/// Server-driven quiz loop, spawned when a Trivia room starts. Broadcasts each question,
+/// ticks its countdown once a second, then reveals the answer and leaderboard. Stops as soon
+/// as the room is gone or the game was reset or restarted (`run_id` changed).
+pub async fn run_quiz(state: Arc<AppState>, game_id: String, run_id: u64) {
+    loop {
+        // Ask the next question, or finish
+        {
+            let mut rooms = state.rooms.write().await;
+            let Some(room) = rooms.get_mut(&game_id) else { return; };
+            let GameType::Trivia(game) = &mut room.game else { return; };
+            if game.run_id != run_id {
+                return;
+            }
+            let asked = game.next_question(Instant::now());
+            if !asked {
+                println!("[Trivia] Quiz over in room {}. Leaderboard: {:?}", game_id, game.leaderboard());
+            }
+            let view = build_view(&game_id, game, "ok");
+            room.broadcast(&view);
+            if !asked {
+                return;
+            }
+        }
+
+        // Countdown until time is up or everyone has answered
+        loop {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            let rooms = state.rooms.read().await;
+            let Some(room) = rooms.get(&game_id) else { return; };
+            let GameType::Trivia(game) = &room.game else { return; };
+            if game.run_id != run_id {
+                return;
+            }
+            if game.all_answered() || game.seconds_left(Instant::now()) == Some(0) {
+                break;
+            }
+            room.broadcast(&build_view(&game_id, game, "ok"));
+        }
+
+        // Reveal the answer and the scores so far
+        {
+            let mut rooms = state.rooms.write().await;
+            let Some(room) = rooms.get_mut(&game_id) else { return; };
+            let GameType::Trivia(game) = &mut room.game else { return; };
+            if game.run_id != run_id {
+                return;
+            }
+            game.reveal();
+            let view = build_view(&game_id, game, "ok");
+            room.broadcast(&view);
+        }
+        tokio::time::sleep(Duration::from_secs(REVEAL_SECS)).await;
+    }
+}
+
+/// Everything about the quiz that is safe to show everyone. The right answer and each
+/// player's choice are only filled in once the question has been revealed.
+pub fn build_view(game_id: &str, game: &TriviaModel, status: &str) -> ServerMessage {
+    let question = game.current_question().filter(|_| game.phase != TriviaPhase::Finished);
+    let revealed = game.phase == TriviaPhase::Reveal;
+
+    ServerMessage::Trivia(TriviaPayloadToClient {
+        game_id: game_id.to_string(),
+        status: status.to_string(),
+        phase: game.phase.as_str().to_string(),
+        pack: game.pack.as_ref().map(|p| p.name.clone()).unwrap_or_else(|| game.pack_id.clone()),
+        question_number: game.question_idx.map(|i| i + 1).unwrap_or(0),
+        total_questions: game.total_questions(),
+        prompt: question.map(|q| q.prompt.clone()),
+        choices: question.map(|q| q.choices.clone()).unwrap_or_default(),
+        seconds_left: game.seconds_left(Instant::now()),
+        answered: game.players.iter().filter(|p| game.answers.contains_key(*p)).cloned().collect(),
+        correct_choice: question.filter(|_| revealed).map(|q| q.answer),
+        results: if revealed {
+            game.players
+                .iter()
+                .map(|p| TriviaResultPayload {
+                    player: p.clone(),
+                    choice: game.answers.get(p).map(|a| a.choice),
+                    points: game.last_points.get(p).copied().unwrap_or(0),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        },
+        leaderboard: game
+            .leaderboard()
+            .into_iter()
+            .map(|(player, score)| TriviaScorePayload { player, score })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, snapshot_json, started_test_room};
+
+    #[tokio::test]
+    async fn answering_in_another_room_under_the_same_name_is_rejected() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "trivia", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "trivia", "other", &["alice", "carol"]).await;
+        let before = snapshot_json(&state, "other").await;
+        let payload = |game_id: &str| {
+            serde_json::from_value(serde_json::json!({ "game_id": game_id, "player_name": "alice", "choice": 0 })).unwrap()
+        };
+
+        // The "alice" of the first room tries to act for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(trivia_handler(payload("other"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot_json(&state, "other").await, before);
+        assert!(trivia_handler(payload("room"), &state, "alice", &alice_tx).await.is_some());
+    }
+}
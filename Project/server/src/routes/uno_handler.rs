@@ -1,59 +1,87 @@
 use std::sync::Arc;
-use tokio::sync::RwLock;
+
+use axum::extract::ws::Message;
+use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{
   AppState,
   models::{gameroom::{GameRoom, GameType}, uno::model::*},
+  routes::gameroom_handler,
   types::{UnoPayloadToServer, UnoPayloadToClient, ServerMessage},
 };
 
-use serde_json;
-use axum::extract::ws::Message;
-
+/// Returns None when the reply was sent to the sender alone rather than for broadcasting.
 pub async fn uno_handler(
     payload: UnoPayloadToServer,
     app_state: &Arc<AppState>,
-    _current_room: Arc<RwLock<Option<String>>>,
-) -> ServerMessage  {
+    sender: &str, // Who the connection joined as
+    user_tx: &UnboundedSender<Message>,
+) -> Option<ServerMessage>  {
     let mut rooms = app_state.rooms.write().await;
     let Some(room) = rooms.get_mut(&payload.game_id) else {
         // Room not found: return a neutral broadcast (FE can decide UX)
-        return ServerMessage::Uno(UnoPayloadToClient {
-            game_id: payload.game_id,
-            players: None, current_idx: None, direction: None,
-            top_discard: None, chosen_color: None, pending_draw: None,
-            public_counts: None, hand: None, winner: None,
-        });
+        return Some(public_snapshot_empty(payload.game_id));
     };
 
+    // Only the connection that joined this room as `sender` may act in it
+    if !room.is_member(sender, user_tx) {
+        eprintln!("[Uno] {} is not in room {}", sender, payload.game_id);
+        gameroom_handler::send_direct(user_tx, &public_snapshot_empty(payload.game_id));
+        return None;
+    }
+
+    Some(room_action(payload, room, sender))
+}
+
+/// Apply one player's action to the room's Uno game and build the public update
+fn room_action(payload: UnoPayloadToServer, room: &mut GameRoom, sender: &str) -> ServerMessage {
     let GameType::Uno(ref mut s) = room.game else {
         // wrong game type, send no-op state
         return public_snapshot_empty(payload.game_id);
     };
 
+    // Cards belong to the connection; a payload naming another seat changes nothing
+    if payload.player_name != sender {
+        eprintln!("[Uno] {} tried to act as {}", sender, payload.player_name);
+        return build_public_update(&payload.game_id, s);
+    }
+
+    // The lobby deals the cards once every seated player is ready; nothing to do before that.
+    if !s.table.started {
+        return build_public_update(&payload.game_id, s);
+    }
+
     // Auto-enforce pending draw penalties at the start of the current player's turn.
     // If this applies, short-circuit and broadcast updated state (no other action this turn).
     if s.is_players_turn(&payload.player_name) && s.enforce_pending_at_turn_start() {
+        room.takebacks.seal();
         return build_public_update(&payload.game_id, s);
     }
 
+    // Game before this action, kept for takebacks when the action changes something. Draws are
+    // never undoable: the drawer has seen the cards, so restoring the deck would tell them what comes next.
+    let before = GameType::Uno(s.clone());
+    let mut acted = false;
+    let mut drew = false;
+
     match payload.action.as_str() {
         "start" => {
-            s.start();
+            // Kept for older clients; starting is driven by the lobby ready-check now
         }
 
         "draw_card" => {
             if !s.is_players_turn(&payload.player_name) {
                 return build_public_update(&payload.game_id, s);
             }
-            let _ = s.draw_one(&payload.player_name);
+            drew = s.table.draw_one(&payload.player_name).is_some();
         }
 
         "pass_turn" => {
             if !s.is_players_turn(&payload.player_name) {
                 return build_public_update(&payload.game_id, s);
             }
-            s.advance_turn(1);
+            s.table.advance_turn(1);
+            acted = true;
         }
 
         "play_card" => {
@@ -73,6 +101,7 @@ pub async fn uno_handler(
             if let Err(_e) = s.play_card_tx(&payload.player_name, card, choose) {
                 return build_public_update(&payload.game_id, s);
             }
+            acted = true;
         }
 
         "call_uno" => {
@@ -88,7 +117,13 @@ pub async fn uno_handler(
     }
 
     // Return a public snapshot; in ws loop, follow up with private hands per player.
-    build_public_update(&payload.game_id, s)
+    let update = build_public_update(&payload.game_id, s);
+    if drew {
+        room.takebacks.seal();
+    } else if acted {
+        room.takebacks.record(&payload.player_name, before);
+    }
+    update
 }
 
 fn parse_choose(s: &Option<String>) -> Option<UnoColor> {
@@ -104,9 +139,9 @@ fn parse_choose(s: &Option<String>) -> Option<UnoColor> {
 pub fn build_public_update(game_id: &str, s: &UnoModel) -> ServerMessage {
     ServerMessage::Uno(UnoPayloadToClient {
         game_id: game_id.to_string(),
-        players: Some(s.players.clone()),
-        current_idx: Some(s.current_idx as i32),
-        direction: Some(s.direction),
+        players: Some(s.table.players.clone()),
+        current_idx: Some(s.table.current_idx as i32),
+        direction: Some(s.table.direction),
         top_discard: s.discard_top.clone(),
         chosen_color: s.chosen_color.as_ref().map(|c| match c {
             UnoColor::Red => "Red".to_string(),
@@ -116,14 +151,14 @@ pub fn build_public_update(game_id: &str, s: &UnoModel) -> ServerMessage {
             UnoColor::Wild => "Wild".to_string(),
         }),
         pending_draw: Some(s.pending_draw),
-        public_counts: Some(s.public_counts()),
+        public_counts: Some(s.table.public_counts()),
         hand: None, // public snapshot
-        winner: s.winner.clone(),
+        winner: s.table.winner.clone(),
     })
 }
 
 pub fn build_private_hand(game_id: &str, s: &UnoModel, player: &str) -> ServerMessage {
-    let hand = s.hands.get(player).cloned().unwrap_or_default();
+    let hand = s.table.hand(player).to_vec();
     ServerMessage::Uno(UnoPayloadToClient {
         game_id: game_id.to_string(),
         players: None, current_idx: None, direction: None,
@@ -143,20 +178,79 @@ fn public_snapshot_empty(game_id: String) -> ServerMessage {
     })
 }
 
-/// Send each UNO player's private hand to their own socket for this room.
-/// Lives in UNO route layer to keep ws.rs game-agnostic.
-pub async fn dm_uno_private_hands(app_state: Arc<AppState>, game_id: &str) {
-    let rooms = app_state.rooms.read().await;
-    if let Some(room) = rooms.get(game_id) {
-        if let GameType::Uno(ref model) = room.game {
-            for (i, player) in model.players.iter().enumerate() {
-                if let Some(tx) = room.txs.get(i) {
-                    let hand_msg = build_private_hand(game_id, model, player);
-                    if let Ok(text) = serde_json::to_string(&hand_msg) {
-                        let _ = tx.send(Message::Text(text.into()));
-                    }
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::gameroom_handler::{member_tx, started_test_room};
+
+    /// Player to move, their index and every hand size in `game_id`
+    fn snapshot(state: &Arc<AppState>, game_id: &str) -> (String, usize, Vec<usize>) {
+        let rooms = state.rooms.try_read().unwrap();
+        let GameType::Uno(game) = &rooms[game_id].game else { unreachable!() };
+        let hands: Vec<usize> = game.table.players.iter().map(|p| game.table.hands[p].len()).collect();
+        (game.table.players[game.table.current_idx].clone(), game.table.current_idx, hands)
+    }
+
+    fn draw(game_id: &str, player_name: &str) -> UnoPayloadToServer {
+        UnoPayloadToServer {
+            game_id: game_id.into(),
+            player_name: player_name.into(),
+            action: "draw_card".into(),
+            card: None,
+            choose_color: None,
+            call_uno: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn a_draw_cannot_be_taken_back_or_undone_past() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "uno", "room", &["alice", "bob"]).await;
+        let (first, _, _) = snapshot(&state, "room");
+        let second = if first == "alice" { "bob" } else { "alice" };
+
+        let first_tx = member_tx(&state, "room", &first).await;
+        uno_handler(UnoPayloadToServer { action: "pass_turn".into(), ..draw("room", &first) }, &state, &first, &first_tx).await;
+        let second_tx = member_tx(&state, "room", second).await;
+        uno_handler(draw("room", second), &state, second, &second_tx).await;
+
+        let mut rooms = state.rooms.write().await;
+        let takebacks = &mut rooms.get_mut("room").unwrap().takebacks;
+        assert_eq!(takebacks.request(second, false), Err("nothing_to_undo"));
+        assert_eq!(takebacks.request(&first, false), Err("nothing_to_undo"));
+    }
+
+    #[tokio::test]
+    async fn acting_from_another_seat_changes_nothing() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "uno", "room", &["alice", "bob"]).await;
+        let (mover, idx, hands) = snapshot(&state, "room");
+        let other = if mover == "alice" { "bob" } else { "alice" };
+
+        let other_tx = member_tx(&state, "room", other).await;
+        uno_handler(draw("room", &mover), &state, other, &other_tx).await;
+        assert_eq!(snapshot(&state, "room"), (mover.clone(), idx, hands.clone()));
+
+        let mover_tx = member_tx(&state, "room", &mover).await;
+        uno_handler(draw("room", &mover), &state, &mover, &mover_tx).await;
+        assert_ne!(snapshot(&state, "room").2, hands);
+    }
+
+    #[tokio::test]
+    async fn acting_in_another_room_under_the_same_name_changes_nothing() {
+        let state = Arc::new(AppState::default());
+        let _connections = started_test_room(&state, "uno", "room", &["alice", "bob"]).await;
+        let _others = started_test_room(&state, "uno", "other", &["alice", "carol"]).await;
+        {
+            let mut rooms = state.rooms.write().await;
+            let GameType::Uno(game) = &mut rooms.get_mut("other").unwrap().game else { unreachable!() };
+            game.table.current_idx = game.table.players.iter().position(|p| p == "alice").unwrap();
         }
+        let before = snapshot(&state, "other");
+
+        // The "alice" of the first room tries to draw for the "alice" of the second
+        let alice_tx = member_tx(&state, "room", "alice").await;
+        assert!(uno_handler(draw("other", "alice"), &state, "alice", &alice_tx).await.is_none());
+        assert_eq!(snapshot(&state, "other"), before);
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,7 @@
+use std::collections::{BTreeMap, HashMap};
+
 use serde::{Deserialize, Serialize};
+use crate::models::cards::Card;
 use crate::models::uno::model::UnoCard;
 
 // -------------------------------------------------------------
@@ -13,8 +16,52 @@ pub enum ClientMessage {
     GameRoom(GameRoomPayload),
     Chat(ChatPayload),
     TicTacToe(TicTacToePayloadToServer),
+    ConnectFour(ConnectFourPayloadToServer),
     RockPaperScissors(RockPaperScissorsPayloadToServer),
     Uno(UnoPayloadToServer),
+    Blackjack(BlackjackPayloadToServer),
+    CrazyEights(CrazyEightsPayloadToServer),
+    GoFish(GoFishPayloadToServer),
+    TexasHoldem(HoldemPayloadToServer),
+    Battleship(BattleshipPayloadToServer),
+    Checkers(CheckersPayloadToServer),
+    Reversi(ReversiPayloadToServer),
+    DotsAndBoxes(DotsAndBoxesPayloadToServer),
+    Hangman(HangmanPayloadToServer),
+    Trivia(TriviaPayloadToServer),
+    Pictionary(PictionaryPayloadToServer),
+    Mancala(MancalaPayloadToServer),
+}
+
+impl ClientMessage {
+    /// The name the message claims to come from, if it carries one
+    pub fn sender(&self) -> Option<&str> {
+        match self {
+            ClientMessage::Echo(_) => None,
+            ClientMessage::GameRoom(p) => Some(&p.player_name),
+            ClientMessage::Chat(p) => Some(&p.player_name),
+            ClientMessage::TicTacToe(p) => Some(&p.whos_turn),
+            ClientMessage::ConnectFour(p) => Some(&p.player_name),
+            ClientMessage::RockPaperScissors(p) => Some(&p.player_name),
+            ClientMessage::Uno(p) => Some(&p.player_name),
+            ClientMessage::Blackjack(p) => Some(&p.player_name),
+            ClientMessage::CrazyEights(p) => Some(&p.player_name),
+            ClientMessage::GoFish(p) => Some(&p.player_name),
+            ClientMessage::TexasHoldem(p) => Some(&p.player_name),
+            ClientMessage::Battleship(p) => Some(&p.player_name),
+            ClientMessage::Checkers(p) => Some(&p.player_name),
+            ClientMessage::Reversi(p) => Some(&p.player_name),
+            ClientMessage::DotsAndBoxes(p) => Some(&p.player_name),
+            ClientMessage::Hangman(p) => Some(&p.player_name),
+            ClientMessage::Trivia(p) => Some(&p.player_name),
+            ClientMessage::Pictionary(p) => Some(&p.player_name),
+            ClientMessage::Mancala(p) => Some(&p.player_name),
+        }
+    }
+
+    pub fn is_join(&self) -> bool {
+        matches!(self, ClientMessage::GameRoom(p) if p.action == "join")
+    }
 }
 
 /// Messages sent from the server to the client.
@@ -25,8 +72,24 @@ pub enum ServerMessage {
     GameRoom(GameRoomPayload),
     Chat(ChatPayload),
     TicTacToe(TicTacToePayloadToClient),
+    UltimateTicTacToe(UltimateTicTacToePayloadToClient),
+    ConnectFour(ConnectFourPayloadToClient),
     RockPaperScissors(RockPaperScissorsPayloadToClient),
+    RpsTournament(RpsTournamentPayloadToClient),
     Uno(UnoPayloadToClient),
+    Blackjack(BlackjackPayloadToClient),
+    CrazyEights(CrazyEightsPayloadToClient),
+    GoFish(GoFishPayloadToClient),
+    TexasHoldem(HoldemPayloadToClient),
+    Battleship(BattleshipPayloadToClient),
+    Checkers(CheckersPayloadToClient),
+    Reversi(ReversiPayloadToClient),
+    DotsAndBoxes(DotsAndBoxesPayloadToClient),
+    Hangman(HangmanPayloadToClient),
+    Trivia(TriviaPayloadToClient),
+    Pictionary(PictionaryPayloadToClient),
+    PictionaryStroke(PictionaryStrokeRelay),
+    Mancala(MancalaPayloadToClient),
 }
 
 // -------------------------------------------------------------
@@ -43,11 +106,148 @@ pub struct EchoPayload {
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct GameRoomPayload {
     pub game: String, // "tictactoe", "rockpaperscissors", etc
-    pub action: String, // "join", "leave", "reset"
+    pub action: String, // "join", "leave", "reset", "ready", "rematch", "takeback", "takeback_accept", "takeback_decline"; server also sends "countdown", "start"
     pub player_name: String,
     pub game_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub players: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Box<RoomOptions>>, // Only read on the join that creates the room
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seats: Option<Vec<String>>, // Seated players (the rest of `players` are spectators)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ready: Option<Vec<String>>, // Seated players who are ready
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>, // "waiting", "countdown", "in_game"
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub countdown: Option<u8>, // Seconds until the game starts
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub takeback: Option<Box<TakebackPayload>>, // Sent with takeback actions
+}
+
+/// Takeback state of a room (TicTacToe and Uno)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TakebackPayload {
+    pub enabled: bool,
+    pub limit: u32, // Takebacks per player per game
+    pub used: HashMap<String, u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_by: Option<String>, // Open request, if any
+    pub accepted_by: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>, // Why the last takeback action was refused
+}
+
+/// Room settings chosen by whoever creates the room
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct RoomOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_players: Option<usize>, // Uno / Crazy Eights / Go Fish / rps_tournament / Blackjack / DotsAndBoxes / Hangman / Trivia / Pictionary: seated players required before the game can start
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_of: Option<u32>, // TicTacToe / RockPaperScissors: series length (1, 3, 5, 7)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rows: Option<usize>, // TicTacToe: board rows (3-26); defaults to `cols`, else 3. DotsAndBoxes: rows of boxes (1-10, default 4)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cols: Option<usize>, // TicTacToe: board columns (3-26); defaults to `rows`. DotsAndBoxes: columns of boxes
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub win_length: Option<usize>, // TicTacToe: marks in a row to win; defaults to the smaller side, capped at 5
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttt_variant: Option<String>, // TicTacToe: "standard", "misere", "wild", "order_and_chaos" (6x6, overrides the board size)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vs_bot: Option<bool>, // ConnectFour / Checkers / Reversi / Mancala: play alone against the server's bot
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_depth: Option<u32>, // ConnectFour / Checkers: bot search depth in plies (1-8, default 6); Mancala: 1-10, default 6
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_level: Option<String>, // Reversi: bot strength, "easy", "medium" (default) or "hard"
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pits_per_side: Option<usize>, // Mancala: pits on each side (3-8, default 6)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seeds_per_pit: Option<u32>, // Mancala: seeds in each pit at the start (1-8, default 4)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rps_variant: Option<String>, // RockPaperScissors: "classic", "rpsls", "rps7", "rps15"
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rps_rules: Option<Vec<[String; 3]>>, // RockPaperScissors: custom [winner, verb, loser] edges; overrides rps_variant
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tournament_format: Option<String>, // rps_tournament: "single_elimination" (default) or "round_robin"
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decks: Option<usize>, // Blackjack: decks in the shoe (1-8, default 6)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_chips: Option<u32>, // Blackjack / Texas Hold'em: chips each player starts with (default 1000)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_bet: Option<u32>, // Blackjack: smallest bet (default 10)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub small_blind: Option<u32>, // Texas Hold'em (default 5)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub big_blind: Option<u32>, // Texas Hold'em (default 10)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_shot_on_hit: Option<bool>, // Battleship: a hit earns another shot (default false: turns always alternate)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word_categories: Option<Vec<String>>, // Hangman / Pictionary: categories from the bundled word list (default all)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hangman_mode: Option<String>, // Hangman: "coop" (default) or "versus"
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lives: Option<u32>, // Hangman: wrong guesses allowed (1-12, default 6)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trivia_pack: Option<String>, // Trivia: question pack file name without extension (default "general")
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub question_secs: Option<u64>, // Trivia: time to answer each question (1-120, default 20)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draw_secs: Option<u64>, // Pictionary: seconds per drawing turn (15-180, default 60)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drawing_rounds: Option<u32>, // Pictionary: times each player draws (1-5, default 2)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranked: Option<bool>, // Ranked rooms don't allow takebacks
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub takeback_limit: Option<u32>, // TicTacToe / Uno: takebacks per player per game (default 3)
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_reveal: Option<bool>, // RockPaperScissors / rps_tournament: commit a hash first, reveal after
+}
+
+/// Best-of-N series score shared by the two-player games
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SeriesPayload {
+    pub best_of: u32,
+    pub game_number: u32,
+    pub wins: Vec<u32>, // [player1 wins, player2 wins]
+    pub ties: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<String>, // Set once the series is decided
+    pub rematch_votes: Vec<String>,
 }
 
 /// Payload for Chat message type
@@ -70,6 +270,41 @@ pub struct TicTacToePayloadToClient {
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>, // "IN_PROGRESS", "gameover_x", "gameover_o", "gameover_draw"
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<SeriesPayload>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>, // "standard", "misere", "wild", "order_and_chaos"
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winning_line: Option<Vec<[usize; 2]>>, // [row, col] cells of the completed line, once the game is won
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moves: Option<Vec<TicTacToeMovePayload>>, // This game's moves in order, so a refresh can replay them
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TicTacToeMovePayload {
+    pub player: String,
+    pub cell: String, // "A1"
+    pub mark: String, // "X" or "O"
+    pub turn: usize, // 1-based
+}
+
+/// Payload sent TO the client for Ultimate TicTacToe. Moves use the regular
+/// TicTacToe message with a cell on the 9x9 grid ("A1" .. "I9").
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct UltimateTicTacToePayloadToClient {
+    pub board: Vec<Vec<i32>>, // 9x9 cells: 1 = player 1, -1 = player 2, 0 = empty
+    pub meta_board: Vec<Vec<i32>>, // 3x3 claimed sub-boards, same values
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forced_board: Option<usize>, // Sub-board (0-8, row-major) the next move must go in; absent = any open board
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whos_turn: Option<String>,
+    pub status: String, // "IN_PROGRESS", "gameover_x", "gameover_o", "gameover_draw", or an error code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<SeriesPayload>,
 }
 
 /// ✅ FIXED: Payload received FROM the client
@@ -77,7 +312,36 @@ pub struct TicTacToePayloadToClient {
 pub struct TicTacToePayloadToServer {
     pub game_id: String, // ✅ Required field
     pub whos_turn: String, // Player name making the move
-    pub choice: String, // "A1", "B2", etc.
+    pub choice: String, // "A1", "B2", etc.: row letter then 1-based column
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mark: Option<String>, // "X" or "O"; required in the wild and order_and_chaos variants
+}
+
+/// Payload received FROM the client for Connect Four
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ConnectFourPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>, // 1-7, left to right; omit to request the latest state
+}
+
+/// Payload sent TO the client for Connect Four
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ConnectFourPayloadToClient {
+    pub game_id: String,
+    pub board: Vec<Vec<i32>>, // 6 rows x 7 columns, top row first: 1 = player 1, -1 = player 2, 0 = empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whos_turn: Option<String>,
+    pub status: String, // "IN_PROGRESS", "gameover_p1", "gameover_p2", "gameover_draw", or an error code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winning_line: Option<Vec<[usize; 2]>>, // [row, col] cells of the four (or more) in a row
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_move: Option<[usize; 2]>, // [row, col] of the latest disc
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<SeriesPayload>,
 }
 
 /// Payload received FROM the client for RockPaperScissors
@@ -86,7 +350,11 @@ pub struct RockPaperScissorsPayloadToServer {
     pub game_id: String,
     pub player_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub choice: Option<String>, // optional so clients may request latest state
+    pub choice: Option<String>, // optional so clients may request latest state; with `nonce` it is a reveal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<String>, // commit-reveal rooms: hex sha256 of "<choice>:<nonce>"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>, // commit-reveal rooms: sent together with `choice` to reveal
 }
 
 /// Payload sent TO the client for RockPaperScissors
@@ -106,6 +374,43 @@ pub struct RockPaperScissorsPayloadToClient {
     pub winner: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<SeriesPayload>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_choices: Option<Vec<String>>, // Weapons of the room's rule set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_reveal: Option<CommitRevealPayload>, // Only in commit-reveal rooms
+}
+
+/// Commitments are public as soon as they arrive; nonces only once the round is decided
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CommitRevealPayload {
+    pub commitments: Vec<Option<String>>, // [player1, player2]
+    pub nonces: Vec<Option<String>>,
+}
+
+/// Payload sent TO the client for an RPS tournament. Clients send moves with the
+/// regular RockPaperScissors message; the server finds the sender's current match.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RpsTournamentPayloadToClient {
+    pub game_id: String,
+    pub format: String, // "single_elimination" or "round_robin"
+    pub status: String, // "in_progress", "complete", "waiting_for_players", or an error code
+    pub current_round: usize,
+    pub rounds: Vec<Vec<RockPaperScissorsPayloadToClient>>, // one RPS snapshot per match; byes have status "bye"
+    pub standings: Vec<RpsStandingPayload>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub champion: Option<String>,
+    pub available_choices: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RpsStandingPayload {
+    pub player: String,
+    pub match_wins: u32,
+    pub game_wins: u32,
 }
 
 /// Payload received FROM the client for Uno
@@ -158,3 +463,548 @@ pub struct UnoPayloadToClient {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub winner: Option<String>,
 }
+
+/// Payload received FROM the client for Blackjack
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BlackjackPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    pub action: String, // "bet", "hit", "stand", "double", "split", "next_round", "request_state"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u32>, // Required for "bet"
+}
+
+/// Payload sent TO the client for Blackjack. The broadcast hides every player's hole card;
+/// each player then gets a private copy (`viewer` set) showing their own.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BlackjackPayloadToClient {
+    pub game_id: String,
+    pub phase: String, // "betting", "player_turns", "round_over", "game_over"
+    pub status: String, // "ok" or an error code
+    pub players: Vec<BlackjackSeatPayload>,
+    pub dealer: Vec<String>, // Card codes; "back" for the face-down hole card
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dealer_value: Option<u8>, // Only once the hole card is shown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_player: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_hand: Option<usize>, // Index into the current player's `hands`
+    pub min_bet: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub viewer: Option<String>, // Set on private views
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BlackjackSeatPayload {
+    pub name: String,
+    pub chips: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bet: Option<u32>, // Bet placed for the next deal
+    pub hands: Vec<BlackjackHandPayload>,
+    pub net: i64, // Chips won or lost last round
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BlackjackHandPayload {
+    pub cards: Vec<String>, // "back" for a hole card the viewer can't see
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<u8>, // Hidden along with the hole card
+    pub bet: u32,
+    pub doubled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>, // "blackjack", "win", "push", "lose" once the round ends
+}
+
+/// Payload received FROM the client for Crazy Eights
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CrazyEightsPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    pub action: String, // "play_card", "draw_card", "pass_turn", "request_state"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card: Option<Card>, // Card code for "play_card", e.g. "8c"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub choose_suit: Option<String>, // "c", "d", "h" or "s"; required when playing an eight
+}
+
+/// Payload sent TO the client for Crazy Eights. `hand` and `viewer` are only set on the
+/// private copy each player receives after the room broadcast.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CrazyEightsPayloadToClient {
+    pub game_id: String,
+    pub status: String, // "ok" or an error code
+    pub players: Vec<String>, // Seat order
+    pub current_idx: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_discard: Option<Card>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chosen_suit: Option<String>, // Suit called with the eight on top
+    pub draw_pile: usize,
+    pub public_counts: Vec<u8>, // Hand sizes in seat order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hand: Option<Vec<Card>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub viewer: Option<String>,
+}
+
+/// Payload received FROM the client for Go Fish
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GoFishPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    pub action: String, // "ask", "request_state"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>, // Player being asked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>, // Rank code being asked for: "2"-"10", "j", "q", "k", "a"
+}
+
+/// Payload sent TO the client for Go Fish. `hand` and `viewer` are only set on private copies.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GoFishPayloadToClient {
+    pub game_id: String,
+    pub status: String, // "ok" or an error code
+    pub players: Vec<String>, // Seat order
+    pub current_idx: usize,
+    pub pond: usize, // Cards left to fish
+    pub public_counts: Vec<u8>, // Hand sizes in seat order
+    pub books: HashMap<String, Vec<String>>, // Rank codes of each player's completed books
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_ask: Option<GoFishAskPayload>,
+    pub game_over: bool,
+    pub winners: Vec<String>, // Most books; several on a tie
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hand: Option<Vec<Card>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub viewer: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GoFishAskPayload {
+    pub asker: String,
+    pub target: String,
+    pub rank: String,
+    pub received: usize, // 0 = "go fish"
+    pub lucky_draw: bool, // Fished the asked rank and kept the turn
+}
+
+/// Payload received FROM the client for Texas Hold'em
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct HoldemPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    pub action: String, // "fold", "check", "call", "bet", "raise", "all_in", "next_hand", "request_state"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u32>, // Total to bet / raise to on this street
+}
+
+/// Payload sent TO the client for Texas Hold'em. The broadcast shows only hole cards revealed
+/// at showdown; each player's private copy (`viewer` set) also shows their own.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct HoldemPayloadToClient {
+    pub game_id: String,
+    pub phase: String, // "waiting", "preflop", "flop", "turn", "river", "hand_over", "game_over"
+    pub status: String, // "ok" or an error code
+    pub hand_number: u32,
+    pub seats: Vec<HoldemSeatPayload>,
+    pub board: Vec<Card>,
+    pub pot: u32,
+    pub dealer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_player: Option<String>,
+    pub current_bet: u32,
+    pub min_raise_to: u32, // Smallest legal "raise" amount right now
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub results: Vec<HoldemPotPayload>, // Filled once a hand is over
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub viewer: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct HoldemSeatPayload {
+    pub name: String,
+    pub stack: u32,
+    pub bet: u32, // On this street
+    pub committed: u32, // This hand
+    pub folded: bool,
+    pub all_in: bool,
+    pub hole: Vec<String>, // "back" for cards the viewer can't see; empty when folded or not dealt in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hand: Option<String>, // Hand category once shown at showdown, e.g. "full_house"
+    pub won: u32, // Chips won last hand
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct HoldemPotPayload {
+    pub amount: u32,
+    pub winners: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hand: Option<String>, // Winning hand category; absent when everyone else folded
+}
+
+/// Payload received FROM the client for Battleship
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BattleshipPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    pub action: String, // "place", "fire", "request_state"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ships: Option<Vec<ShipPlacementPayload>>, // The whole fleet, for "place"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>, // Cell to fire at, e.g. "B7", for "fire"
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ShipPlacementPayload {
+    pub ship: String, // "carrier", "battleship", "cruiser", "submarine", "destroyer"
+    pub start: String, // Bow cell, e.g. "A1": row letter then 1-based column
+    pub horizontal: bool, // Runs right from `start` if true, down if false
+}
+
+/// Payload sent TO the client for Battleship. Shots are public; `fleet` is only
+/// set on the private copy sent to its owner.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BattleshipPayloadToClient {
+    pub game_id: String,
+    pub status: String, // "ok" or an error code
+    pub phase: String, // "placing", "playing", "game_over"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub player1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub player2: Option<String>,
+    pub placed: [bool; 2], // Whether each player has placed their fleet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_player: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<String>,
+    pub shots: [Vec<BattleshipShotPayload>; 2], // Shots fired by player 1 and by player 2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fleet: Option<Vec<BattleshipShipPayload>>, // The viewer's own ships
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub viewer: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BattleshipShotPayload {
+    pub target: String, // "B7"
+    pub hit: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sunk: Option<String>, // Ship this shot sank
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BattleshipShipPayload {
+    pub ship: String,
+    pub cells: Vec<String>,
+    pub hits: Vec<bool>,
+    pub sunk: bool,
+}
+
+/// Payload received FROM the client for Checkers
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CheckersPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<Vec<String>>, // Squares the piece visits, e.g. ["F2", "D4", "B2"]; omit to request the latest state
+}
+
+/// Payload sent TO the client for Checkers
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CheckersPayloadToClient {
+    pub game_id: String,
+    pub board: Vec<Vec<i32>>, // 8x8, top row first: 1 / 2 = player 1 man / king, -1 / -2 = player 2, 0 = empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whos_turn: Option<String>,
+    pub status: String, // "IN_PROGRESS", "gameover_p1", "gameover_p2", "gameover_draw", or an error code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<String>,
+    pub legal_moves: Vec<Vec<String>>, // Every legal path for the player to move
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_move: Option<Vec<String>>,
+    pub captured: Vec<String>, // Squares emptied by the last move's jumps
+    pub quiet_moves: u32, // Moves without a capture or man move; 80 draws the game
+}
+
+/// Payload received FROM the client for Reversi
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ReversiPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub square: Option<String>, // e.g. "D3"; omit to request the latest state
+}
+
+/// Payload sent TO the client for Reversi
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ReversiPayloadToClient {
+    pub game_id: String,
+    pub board: Vec<Vec<i32>>, // 8x8, top row first: 1 = player 1 (black), -1 = player 2 (white), 0 = empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whos_turn: Option<String>,
+    pub status: String, // "IN_PROGRESS", "gameover_p1", "gameover_p2", "gameover_draw", or an error code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<String>,
+    pub legal_moves: Vec<String>, // Squares the player to move may play
+    pub discs: [usize; 2], // Disc counts for player 1 and player 2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_move: Option<String>,
+    pub flipped: Vec<String>, // Discs turned over by the last move
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passed: Option<String>, // Player who had no move after the last move and was skipped
+}
+
+/// An edge between two neighbouring dots
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DotsAndBoxesEdgePayload {
+    pub orientation: String, // "h" (top of box row, col) or "v" (left side of box row, col)
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Payload received FROM the client for Dots and Boxes
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DotsAndBoxesPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edge: Option<DotsAndBoxesEdgePayload>, // Omit to request the latest state
+}
+
+/// Payload sent TO the client for Dots and Boxes
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DotsAndBoxesPayloadToClient {
+    pub game_id: String,
+    pub status: String, // "ok" or an error code
+    pub rows: usize, // Boxes down
+    pub cols: usize, // Boxes across
+    pub players: Vec<String>, // Seat order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whos_turn: Option<String>,
+    pub horizontal: Vec<Vec<bool>>, // (rows + 1) x cols drawn flags
+    pub vertical: Vec<Vec<bool>>, // rows x (cols + 1) drawn flags
+    pub boxes: Vec<Vec<Option<String>>>, // rows x cols owner names
+    pub scores: Vec<u32>, // Boxes per player, in seat order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_edge: Option<DotsAndBoxesEdgePayload>,
+    pub completed: Vec<[usize; 2]>, // Boxes ([row, col]) closed by the last edge
+    pub game_over: bool,
+    pub winners: Vec<String>, // Most boxes; several on a tie
+}
+
+/// Payload received FROM the client for Hangman
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct HangmanPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guess: Option<String>, // One letter or the whole word; omit to request the latest state
+}
+
+/// Payload sent TO the client for Hangman. Never carries the secret while the game is running.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct HangmanPayloadToClient {
+    pub game_id: String,
+    pub status: String, // "ok" or an error code
+    pub mode: String, // "coop" or "versus"
+    pub category: String,
+    pub masked_word: String, // e.g. "ll___"
+    pub correct_letters: Vec<String>,
+    pub wrong_letters: Vec<String>,
+    pub wrong_words: Vec<String>,
+    pub lives: u32,
+    pub max_lives: u32,
+    pub players: Vec<String>, // Seat order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whos_turn: Option<String>,
+    pub scores: Vec<u32>, // Versus only, in seat order
+    pub game_over: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solved_by: Option<String>,
+    pub winners: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>, // Only once the game is over
+}
+
+/// Payload received FROM the client for Trivia
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TriviaPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub choice: Option<usize>, // Index into the question's choices; omit to request the latest state
+}
+
+/// Payload sent TO the client for Trivia: on every question, countdown tick, answer and reveal
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TriviaPayloadToClient {
+    pub game_id: String,
+    pub status: String, // "ok" or an error code
+    pub phase: String, // "waiting", "question", "reveal", "finished"
+    pub pack: String,
+    pub question_number: usize, // 1-based; 0 before the first question
+    pub total_questions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    pub choices: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seconds_left: Option<u64>, // While a question is open
+    pub answered: Vec<String>, // Players who have locked in an answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct_choice: Option<usize>, // Reveal only
+    pub results: Vec<TriviaResultPayload>, // Reveal only
+    pub leaderboard: Vec<TriviaScorePayload>, // Best first
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TriviaResultPayload {
+    pub player: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub choice: Option<usize>,
+    pub points: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TriviaScorePayload {
+    pub player: String,
+    pub score: u32,
+}
+
+/// A batch of pen movement
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PictionaryStrokePayload {
+    pub points: Vec<[f32; 2]>, // Canvas coordinates, up to 512 per stroke
+    pub color: String, // CSS color, e.g. "#1e90ff"
+    pub width: f32, // Pen width, above 0 and up to 64
+}
+
+/// Payload received FROM the client for Pictionary. Guesses go through `Chat`.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PictionaryPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    pub action: String, // "stroke", "clear", "request_state"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stroke: Option<PictionaryStrokePayload>, // For "stroke"
+}
+
+/// A stroke relayed to everyone in the room except the drawer
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PictionaryStrokeRelay {
+    pub game_id: String,
+    pub stroke: PictionaryStrokePayload,
+}
+
+/// Payload sent TO the client for Pictionary
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PictionaryPayloadToClient {
+    pub game_id: String,
+    pub status: String, // "ok", "correct_guess" or an error code
+    pub phase: String, // "waiting", "drawing", "turn_over", "finished"
+    pub players: Vec<String>, // Seat order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drawer: Option<String>,
+    pub turn: usize, // 1-based; 0 before the first turn
+    pub total_turns: usize,
+    pub hint: String, // One "_" per letter of the word
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word: Option<String>, // Only for the drawer, or once the turn is over
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seconds_left: Option<u64>,
+    pub guessed: Vec<String>, // Players who found the word this turn
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct_guess: Option<String>, // Who just guessed it
+    pub scores: Vec<u32>, // Seat order
+    pub strokes: Vec<PictionaryStrokePayload>, // The canvas so far
+    pub winners: Vec<String>,
+}
+
+/// Payload received FROM the client for Mancala
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct MancalaPayloadToServer {
+    pub game_id: String,
+    pub player_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pit: Option<usize>, // 0-based from the mover's left; omit to request the latest state
+}
+
+/// Payload sent TO the client for Mancala
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct MancalaPayloadToClient {
+    pub game_id: String,
+    pub pits: [Vec<u32>; 2], // Seeds per pit for player 1 and player 2, each from that player's left
+    pub stores: [u32; 2],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whos_turn: Option<String>,
+    pub status: String, // "IN_PROGRESS", "gameover_p1", "gameover_p2", "gameover_draw", or an error code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<String>,
+    pub legal_moves: Vec<usize>, // Pits the player to move may sow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_move: Option<(String, usize)>, // [player, pit] of the latest sowing
+    pub extra_turn: bool, // The latest sowing ended in the mover's store
+    pub captured: u32, // Seeds the latest sowing captured
+}
+
+// -------------------------------------------------------------
+// HTTP: ACCOUNTS
+// -------------------------------------------------------------
+
+/// Body of `POST /register` and `POST /login`
+#[derive(Clone, Deserialize, Debug)]
+pub struct AccountRequest {
+    pub player_name: String,
+    pub password: String,
+}
+
+/// Reply to `POST /register` and `POST /login`
+#[derive(Clone, Serialize, Debug)]
+pub struct AccountResponse {
+    pub status: String, // "ok", or an error code: "invalid_name", "weak_password", "name_taken", "invalid_credentials", "storage_error"
+    pub player_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>, // Pass as `/ws?token=...` to play as this account
+}
+
+// -------------------------------------------------------------
+// HTTP: RATINGS AND STATS
+// -------------------------------------------------------------
+
+/// One player's record in one game
+#[derive(Clone, Serialize, Debug)]
+pub struct PlayerRatingPayload {
+    pub rating: i32, // Elo, starting at 1200
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub streak: i32, // +n wins or -n losses in a row
+    pub best_streak: u32,
+}
+
+/// Reply to `GET /players/{name}/stats`
+#[derive(Clone, Serialize, Debug)]
+pub struct PlayerStatsResponse {
+    pub status: String, // "ok", "unknown_player" or "storage_error"
+    pub player_name: String,
+    pub games: BTreeMap<String, PlayerRatingPayload>, // By game name, only games the player has finished
+}
+
+/// Reply to `GET /leaderboard/{game}`
+#[derive(Clone, Serialize, Debug)]
+pub struct LeaderboardResponse {
+    pub status: String, // "ok", "unrated_game" or "storage_error"
+    pub game: String,
+    pub players: Vec<LeaderboardEntryPayload>, // Highest rating first
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct LeaderboardEntryPayload {
+    pub rank: usize, // From 1
+    pub player_name: String,
+    #[serde(flatten)]
+    pub stats: PlayerRatingPayload,
+}
@@ -1,15 +1,18 @@
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
+        Query,
         State,
     },
-    response::IntoResponse,
+    http::StatusCode,
+    response::{IntoResponse, Response},
 };
+use serde::Deserialize;
 use futures::{StreamExt, SinkExt};
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
 
-use crate::{models::appstate::AppState};
+use crate::models::{appstate::AppState, identity::ConnectionIdentity};
 use crate::types::{ClientMessage, EchoPayload, ServerMessage};
 
 use crate::routes::{
@@ -17,17 +20,46 @@ use crate::routes::{
     gameroom_handler::gameroom_handler,
     chat_handler::chat_handler,
     tictactoe_handler::tictactoe_handler,
+    connectfour_handler::connectfour_handler,
+    checkers_handler::checkers_handler,
+    reversi_handler::reversi_handler,
+    mancala_handler::mancala_handler,
+    dotsandboxes_handler::dotsandboxes_handler,
     rockpaperscissors_handler::rockpaperscissors_handler,
     uno_handler::uno_handler,
-    uno_handler::dm_uno_private_hands,
+    battleship_handler::battleship_handler,
+    blackjack_handler::blackjack_handler,
+    crazyeights_handler::crazyeights_handler,
+    gofish_handler::gofish_handler,
+    hangman_handler::hangman_handler,
+    trivia_handler::trivia_handler,
+    pictionary_handler::pictionary_handler,
+    holdem_handler::holdem_handler,
+    gameroom_handler::dm_private_views,
+    gameroom_handler::record_result,
 };
 
+/// Query string of the upgrade request: `/ws?token=...` plays as a registered account
+#[derive(Debug, Deserialize)]
+pub struct WsParams {
+    pub token: Option<String>,
+}
+
 #[axum::debug_handler]
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
+    Query(params): Query<WsParams>,
     State(state): State<Arc<AppState>>,
-) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_socket(socket, state))
+) -> Response {
+    // No token means an anonymous connection; a bad one is refused outright
+    let identity = match params.token {
+        Some(token) => match state.accounts.verify_token(&token) {
+            Some(name) => ConnectionIdentity::account(name),
+            None => return (StatusCode::UNAUTHORIZED, "invalid or expired token").into_response(),
+        },
+        None => ConnectionIdentity::anonymous(),
+    };
+    ws.on_upgrade(move |socket| handle_socket(socket, state, identity))
 }
 
 /// Parse client JSON → enum
@@ -61,7 +93,7 @@ async fn broadcast_to_room(
 
 
 /// Handle the WebSocket connection
-pub async fn handle_socket(socket: WebSocket, app_state: Arc<AppState>) {
+pub async fn handle_socket(socket: WebSocket, app_state: Arc<AppState>, mut identity: ConnectionIdentity) {
     // Create a channel to send messages TO this client
     let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
 
@@ -84,12 +116,25 @@ pub async fn handle_socket(socket: WebSocket, app_state: Arc<AppState>) {
     // Main loop: read client messages
     while let Some(Ok(msg)) = ws_rx.next().await {
         if let Message::Text(text) = msg {
-            let parsed = parse_client_message(&text);
+            let parsed = parse_client_message(&text)
+                .and_then(|msg| identity.admit(&msg, &app_state.accounts).map(|()| msg));
 
             match parsed {
                 Ok(client_msg) => match client_msg {
                     ClientMessage::GameRoom(payload) => {
+                        let joining = payload.action == "join";
                         let response = gameroom_handler(payload, &app_state, tx_for_state.clone(), current_room.clone()).await;
+                        if let ServerMessage::GameRoom(reply) = &response {
+                            if reply.action == "invalid" {
+                                // Refused actions never reached the room; tell only the sender
+                                let serialized = serde_json::to_string(&response).unwrap();
+                                let _ = tx_for_state.send(Message::Text(serialized.into()));
+                                continue;
+                            }
+                            if joining {
+                                identity.bind(&reply.player_name);
+                            }
+                        }
                         broadcast_to_room(response, &app_state, &current_room).await;
                     }
                     ClientMessage::Echo(payload) => {
@@ -98,35 +143,128 @@ pub async fn handle_socket(socket: WebSocket, app_state: Arc<AppState>) {
                         let _ = tx_for_state.send(Message::Text(serialized.into()));
                     }
                     ClientMessage::Chat(payload) => {
-                        let response = chat_handler(payload, &app_state).await;
-                        broadcast_to_room(response, &app_state, &current_room).await;
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = chat_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                        }
                     }
                     ClientMessage::TicTacToe(payload) => {
-                        let response = tictactoe_handler(payload, &app_state, current_room.clone()).await;
-                        broadcast_to_room(response, &app_state, &current_room).await;
+                        let game_id = payload.game_id.clone();
+                        let sender = identity.name().unwrap_or_default();
+                        // None: refused for a room this connection isn't in, already told privately
+                        if let Some(response) = tictactoe_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                            record_result(&app_state, &game_id).await;
+                        }
+                    }
+                    ClientMessage::ConnectFour(payload) => {
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = connectfour_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                        }
+                    }
+                    ClientMessage::Checkers(payload) => {
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = checkers_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                        }
+                    }
+                    ClientMessage::Reversi(payload) => {
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = reversi_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                        }
+                    }
+                    ClientMessage::Mancala(payload) => {
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = mancala_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                        }
+                    }
+                    ClientMessage::DotsAndBoxes(payload) => {
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = dotsandboxes_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                        }
+                    }
+                    ClientMessage::Hangman(payload) => {
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = hangman_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                        }
+                    }
+                    ClientMessage::Trivia(payload) => {
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = trivia_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                        }
+                    }
+                    ClientMessage::Pictionary(payload) => {
+                        // Strokes are relayed inside the handler under a read lock
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = pictionary_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                        }
                     }
                     ClientMessage::RockPaperScissors(payload) => {
-                        let response = rockpaperscissors_handler(payload, &app_state, current_room.clone()).await;
-                        broadcast_to_room(response, &app_state, &current_room).await;
+                        let game_id = payload.game_id.clone();
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = rockpaperscissors_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                            record_result(&app_state, &game_id).await;
+                        }
                     }
                     ClientMessage::Uno(payload) => {
-                        let response = uno_handler(payload, &app_state, current_room.clone()).await;
-                        broadcast_to_room(response, &app_state, &current_room).await;
+                        let game_id = payload.game_id.clone();
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = uno_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                            record_result(&app_state, &game_id).await;
 
-                        if let Some(room_id) = &*current_room.read().await {
-                            dm_uno_private_hands(app_state.clone(), room_id).await;
+                            if let Some(room_id) = &*current_room.read().await {
+                                dm_private_views(app_state.clone(), room_id).await;
+                            }
                         }
                     }
-                    other_variant => {
-                        let err = format!(
-                            "No route defined for message type: {:?}",
-                            other_variant
-                        );
-                        let error_payload =
-                            ServerMessage::Echo(EchoPayload { message: err });
-
-                        let serialized = serde_json::to_string(&error_payload).unwrap();
-                        let _ = tx_for_state.send(Message::Text(serialized.into()));
+                    ClientMessage::Blackjack(payload) => {
+                        let game_id = payload.game_id.clone();
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = blackjack_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                            dm_private_views(app_state.clone(), &game_id).await;
+                        }
+                    }
+                    ClientMessage::CrazyEights(payload) => {
+                        let game_id = payload.game_id.clone();
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = crazyeights_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                            dm_private_views(app_state.clone(), &game_id).await;
+                        }
+                    }
+                    ClientMessage::GoFish(payload) => {
+                        let game_id = payload.game_id.clone();
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = gofish_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                            dm_private_views(app_state.clone(), &game_id).await;
+                        }
+                    }
+                    ClientMessage::TexasHoldem(payload) => {
+                        let game_id = payload.game_id.clone();
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = holdem_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                            dm_private_views(app_state.clone(), &game_id).await;
+                        }
+                    }
+                    ClientMessage::Battleship(payload) => {
+                        let game_id = payload.game_id.clone();
+                        let sender = identity.name().unwrap_or_default();
+                        if let Some(response) = battleship_handler(payload, &app_state, sender, &tx_for_state).await {
+                            broadcast_to_room(response, &app_state, &current_room).await;
+                            dm_private_views(app_state.clone(), &game_id).await;
+                        }
                     }
                 },
 
@@ -149,5 +287,6 @@ async fn cleanup_sender(app_state: &Arc<AppState>, tx: &mpsc::UnboundedSender<Me
 
     for room in rooms.values_mut() {
         room.txs.retain(|t| !t.same_channel(tx));
+        room.members.retain(|_, t| !t.same_channel(tx));
     }
 }